@@ -1,21 +1,32 @@
+use anyhow::{anyhow, bail};
 use ark_cli::{
-    ProgressView, ask_confirmation, press_enter_key, read_ark_key, read_helm_key, read_seed,
+    ProgressView, ask_confirmation, press_enter_key, read_ark_key, read_helm_key,
+    read_keystore_passphrase, read_new_passphrase, read_secret, read_seed, read_seed_share,
+    verify_mnemonic,
 };
 use ark_core::{
-    ArkAddress, ArkCreationSettings, ArkSeed, AutonomiClientConfig, BridgeAddress,
-    ConfidentialString, Core, EitherWorkerKey, HelmKey, ObjectType, PublicWorkerKey, VaultAddress,
-    VaultConfig, VaultCreationSettings,
+    ArkAccessor, ArkAddress, ArkCreationDetails, ArkCreationSettings, ArkMetadataUpdate, ArkSeed,
+    AutonomiClientConfig, BridgeAddress, Budget, CacheKey, CachingStorage, CheckOutcome,
+    ConfidentialString, Core, EitherWorkerKey, Email, FileSystem, HelmKey, Journal, Keystore,
+    LabelValue, Ledger, MnemonicLanguage, ObjectStorage, ObjectType, PaperBackup, Progress,
+    PublicWorkerKey, ReadOnlyCore, Receipt, RecordState, SEED_SHARE_WORDS, SecretProvider,
+    SeedShare, Storage, VaultAddress, VaultConfig, VaultCreationSettings, VaultState,
 };
-use autonomi::{Client, Wallet};
-use clap::{Parser, Subcommand};
+use autonomi::{AttoTokens, Wallet};
+use clap::{Args, Parser, Subcommand, ValueEnum};
 use colored::Colorize;
 use futures_util::future::{BoxFuture, FutureExt};
+use std::collections::BTreeMap;
 use std::fmt::{Debug, Display, Formatter};
+use std::path::PathBuf;
+use std::str::FromStr;
 use std::time::Duration;
+use tokio::sync::OnceCell;
 use tracing::Level;
 use tracing_subscriber::EnvFilter;
 use tracing_subscriber::layer::SubscriberExt;
 use tracing_subscriber::util::SubscriberInitExt;
+use url::Url;
 
 #[derive(Debug, Parser)]
 #[command(version)]
@@ -24,11 +35,77 @@ use tracing_subscriber::util::SubscriberInitExt;
 /// WIP
 struct Arguments {
     /// Autonomi Network Configuration
+    ///
+    /// Use `autonomi:config:dir:///path` to keep everything in a local directory instead
     #[arg(long, short = 'c', env, default_value = "autonomi:config:mainnet")]
     autonomi_config: AutonomiClientConfig,
     /// Wallet Secret Key
+    ///
+    /// Only required for actions that write to the network
     #[arg(env)]
-    secret_key: ConfidentialString,
+    secret_key: Option<ConfidentialString>,
+    /// Take the Wallet Secret Key from a secret provider instead
+    ///
+    /// One of `file:<path>`, `fd:<n>`, `env:<var>`, `cmd:<command>`
+    /// or `secret-service:<attr>=<value>,...`
+    #[arg(long, env, conflicts_with = "secret_key")]
+    secret_key_from: Option<SecretProvider>,
+    /// Take the Ark Seed (its words) from a secret provider instead of asking for it
+    #[arg(long, env)]
+    seed_from: Option<SecretProvider>,
+    /// Take the passphrase of the Ark Seed from a secret provider
+    ///
+    /// Only used together with `--seed-from`
+    #[arg(long, env, requires = "seed_from")]
+    seed_passphrase_from: Option<SecretProvider>,
+    /// Location of the payment ledger
+    ///
+    /// Defaults to `ark/ledger.jsonl` in the user's local data directory
+    #[arg(long, env)]
+    ledger: Option<PathBuf>,
+    /// Location of the operation journal
+    ///
+    /// Used to resume interrupted key rotations.
+    /// Defaults to `ark/journal` in the user's local data directory
+    #[arg(long, env)]
+    journal: Option<PathBuf>,
+    /// Keep an encrypted local copy of all records
+    ///
+    /// Speeds up repeated use and allows reading previously seen records
+    /// while the network is unavailable
+    #[arg(long, env)]
+    cache: bool,
+    /// Location of the record cache
+    ///
+    /// Defaults to `ark/records/<network>` in the user's cache directory
+    #[arg(long, env)]
+    cache_dir: Option<PathBuf>,
+    /// Location of the encrypted keystore
+    ///
+    /// Defaults to `ark/keystore.age` in the user's local data directory
+    #[arg(long, env)]
+    keystore: Option<PathBuf>,
+    /// Take the passphrase of the keystore from a secret provider instead of asking for it
+    #[arg(long, env)]
+    keystore_passphrase_from: Option<SecretProvider>,
+    /// Take the secret key from the keystore instead of asking for it
+    ///
+    /// The name the key was added under with `keystore add`
+    #[arg(long, env)]
+    key_name: Option<String>,
+    /// Take the secret key (Helm, Data or Worker Key) from a secret provider instead of asking for it
+    #[arg(long, env, conflicts_with = "key_name")]
+    key_from: Option<SecretProvider>,
+    /// Abort any operation that would cost more than this many tokens
+    ///
+    /// The whole operation is quoted before its first payment
+    #[arg(long, env)]
+    budget_per_operation: Option<AttoTokens>,
+    /// Abort any operation that would bring the spending of the last 24 hours above this many tokens
+    ///
+    /// Past spending is taken from the payment ledger
+    #[arg(long, env)]
+    budget_daily: Option<AttoTokens>,
     #[command(subcommand)]
     command: Commands,
 }
@@ -44,6 +121,112 @@ enum Commands {
     /// Key rotation and recovery
     #[command(subcommand)]
     Key(KeyCommand),
+    /// Payment ledger
+    #[command(subcommand)]
+    Ledger(LedgerCommand),
+    /// Ark Seed backup
+    #[command(subcommand)]
+    Seed(SeedCommand),
+    /// Encrypted local storage for secret keys
+    #[command(subcommand)]
+    Keystore(KeystoreCommand),
+}
+
+#[derive(Debug, Subcommand)]
+enum KeystoreCommand {
+    /// Add a Helm, Data, Worker or Bridge Key
+    ///
+    /// Creates the keystore if it does not exist yet.
+    Add {
+        /// The Ark Address - e.g. arkaddr1XXXXXX...
+        ark_address: ArkAddress,
+        /// Name to store the key under, unique per Ark
+        name: String,
+    },
+    /// List all stored keys, without revealing them
+    List {
+        /// Only list the keys of the given Ark
+        ark_address: Option<ArkAddress>,
+    },
+    /// Remove a stored key
+    Remove {
+        /// The Ark Address - e.g. arkaddr1XXXXXX...
+        ark_address: ArkAddress,
+        /// Name the key is stored under
+        name: String,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum SeedCommand {
+    /// Split the Ark Seed into shares, a given number of which recover it
+    ///
+    /// Allows spreading custody of the Ark Seed across several people.
+    Split {
+        /// Number of shares required to recover the Ark Seed
+        #[arg(long, short = 't')]
+        threshold: u8,
+        /// Total number of shares to create
+        #[arg(long, short = 'n')]
+        shares: u8,
+    },
+    /// Check that a set of shares recovers the expected Ark
+    Verify {
+        /// The expected Ark Address - e.g. arkaddr1XXXXXX...
+        ark_address: Option<ArkAddress>,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum LedgerCommand {
+    /// Summarize spend per Ark and per Vault
+    Summary,
+    /// Export all recorded payments
+    Export {
+        /// Export format
+        #[arg(long, short = 'f', value_enum, default_value_t = ExportFormat::Csv)]
+        format: ExportFormat,
+        /// Output file, defaults to stdout
+        #[arg(long, short = 'o')]
+        output: Option<PathBuf>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum ExportFormat {
+    Csv,
+    Json,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum SeedLanguage {
+    English,
+    ChineseSimplified,
+    ChineseTraditional,
+    Czech,
+    French,
+    Italian,
+    Japanese,
+    Korean,
+    Portuguese,
+    Spanish,
+}
+
+impl From<SeedLanguage> for MnemonicLanguage {
+    fn from(value: SeedLanguage) -> Self {
+        match value {
+            SeedLanguage::English => MnemonicLanguage::English,
+            SeedLanguage::ChineseSimplified => MnemonicLanguage::SimplifiedChinese,
+            SeedLanguage::ChineseTraditional => MnemonicLanguage::TraditionalChinese,
+            SeedLanguage::Czech => MnemonicLanguage::Czech,
+            SeedLanguage::French => MnemonicLanguage::French,
+            SeedLanguage::Italian => MnemonicLanguage::Italian,
+            SeedLanguage::Japanese => MnemonicLanguage::Japanese,
+            SeedLanguage::Korean => MnemonicLanguage::Korean,
+            SeedLanguage::Portuguese => MnemonicLanguage::Portuguese,
+            SeedLanguage::Spanish => MnemonicLanguage::Spanish,
+        }
+    }
 }
 
 #[derive(Debug, Subcommand)]
@@ -56,7 +239,7 @@ enum VaultCommand {
         name: String,
         /// Type of Objects to store
         ///
-        /// This can NOT be changed later
+        /// This can NOT be changed later, its configuration can
         object_type: ObjectType,
         /// The Ark Address - e.g. arkaddr1XXXXXX...
         ark_address: ArkAddress,
@@ -66,6 +249,8 @@ enum VaultCommand {
         /// Bridge Address
         #[arg(long, short = 'b')]
         bridge: Option<BridgeAddress>,
+        #[command(flatten)]
+        config: ObjectConfigArgs,
     },
     /// Checks if a given Vault Address is valid
     ///
@@ -74,6 +259,325 @@ enum VaultCommand {
         /// The Vault Address - e.g. arkvaultaddr1XXXXXX...
         vault_address: VaultAddress,
     },
+    /// List all Vaults of an Ark
+    ///
+    /// Requires an authorized key
+    List {
+        /// The Ark Address - e.g. arkaddr1XXXXXX...
+        ark_address: ArkAddress,
+    },
+    /// Show the details of a Vault
+    ///
+    /// Requires an authorized key
+    Show {
+        /// The Vault Address - e.g. arkvaultaddr1XXXXXX...
+        vault_address: VaultAddress,
+    },
+    /// Rename a Vault
+    ///
+    /// Requires the Helm Key
+    Rename {
+        /// The Vault Address - e.g. arkvaultaddr1XXXXXX...
+        vault_address: VaultAddress,
+        /// New name of the Vault
+        name: String,
+    },
+    /// Change the description of a Vault
+    ///
+    /// Requires the Helm Key
+    SetDescription {
+        /// The Vault Address - e.g. arkvaultaddr1XXXXXX...
+        vault_address: VaultAddress,
+        /// New description, removes the description if omitted
+        description: Option<String>,
+    },
+    /// Activate a Vault, allowing new uploads
+    ///
+    /// Requires the Helm Key
+    Activate {
+        /// The Vault Address - e.g. arkvaultaddr1XXXXXX...
+        vault_address: VaultAddress,
+        /// Why the state is changed, kept in the status history
+        #[arg(long, short)]
+        reason: Option<String>,
+    },
+    /// Pause a Vault, temporarily refusing new uploads
+    ///
+    /// Requires the Helm Key
+    Deactivate {
+        /// The Vault Address - e.g. arkvaultaddr1XXXXXX...
+        vault_address: VaultAddress,
+        /// Why the state is changed, kept in the status history
+        #[arg(long, short)]
+        reason: Option<String>,
+    },
+    /// Archive a Vault, it is kept readable but no longer receives uploads
+    ///
+    /// Requires the Helm Key
+    Archive {
+        /// The Vault Address - e.g. arkvaultaddr1XXXXXX...
+        vault_address: VaultAddress,
+        /// Why the state is changed, kept in the status history
+        #[arg(long, short)]
+        reason: Option<String>,
+    },
+    /// Decommission a Vault whose source no longer exists
+    ///
+    /// Requires the Helm Key
+    Decommission {
+        /// The Vault Address - e.g. arkvaultaddr1XXXXXX...
+        vault_address: VaultAddress,
+        /// Why the state is changed, kept in the status history
+        #[arg(long, short)]
+        reason: Option<String>,
+    },
+    /// Change the authorized Bridge of a Vault
+    ///
+    /// Requires the Helm Key
+    SetBridge {
+        /// The Vault Address - e.g. arkvaultaddr1XXXXXX...
+        vault_address: VaultAddress,
+        /// New Bridge Address, removes the bridge if omitted
+        bridge: Option<BridgeAddress>,
+    },
+    /// Place a Vault under legal hold, making it write-once
+    ///
+    /// While held, the Vault cannot be deactivated, bound to another Bridge or removed.
+    ///
+    /// Requires the Helm Key
+    Hold {
+        /// The Vault Address - e.g. arkvaultaddr1XXXXXX...
+        vault_address: VaultAddress,
+        /// Why the hold is placed, kept in the hold history
+        #[arg(long, short)]
+        reason: Option<String>,
+    },
+    /// Release the legal hold of a Vault
+    ///
+    /// Requires the Ark Seed
+    ReleaseHold {
+        /// The Vault Address - e.g. arkvaultaddr1XXXXXX...
+        vault_address: VaultAddress,
+        /// Why the hold is released, kept in the hold history
+        #[arg(long, short)]
+        reason: Option<String>,
+    },
+    /// Change the configuration of a Vault's source, e.g. its root path or bucket
+    ///
+    /// Only the given options are changed. Requires the Helm Key
+    Configure {
+        /// The Vault Address - e.g. arkvaultaddr1XXXXXX...
+        vault_address: VaultAddress,
+        #[command(flatten)]
+        config: ObjectConfigArgs,
+    },
+    /// Remove a Vault from the Ark
+    ///
+    /// Data already uploaded to the Vault remains on the network.
+    ///
+    /// Requires the Helm Key
+    Remove {
+        /// The Vault Address - e.g. arkvaultaddr1XXXXXX...
+        vault_address: VaultAddress,
+    },
+}
+
+/// Configuration of a Vault's source, only the options matching its object type apply.
+#[derive(Debug, Args)]
+struct ObjectConfigArgs {
+    /// Root path of a file system
+    #[arg(long, help_heading = "File System")]
+    root: Option<String>,
+    /// Glob pattern of files to include, replaces all existing include rules
+    #[arg(long, help_heading = "File System")]
+    include: Vec<String>,
+    /// Glob pattern of files to exclude, replaces all existing exclude rules
+    #[arg(long, help_heading = "File System")]
+    exclude: Vec<String>,
+    /// IMAP server - e.g. imap.example.com:993
+    #[arg(long, help_heading = "IMAP")]
+    server: Option<String>,
+    /// IMAP account
+    #[arg(long, help_heading = "IMAP")]
+    account: Option<String>,
+    /// IMAP folder to cover, replaces all existing folders
+    #[arg(long, help_heading = "IMAP")]
+    folder: Vec<String>,
+    /// S3 bucket
+    #[arg(long, help_heading = "S3")]
+    bucket: Option<String>,
+    /// S3 key prefix
+    #[arg(long, help_heading = "S3")]
+    prefix: Option<String>,
+    /// Endpoint of an S3 compatible service, AWS if omitted
+    #[arg(long, help_heading = "S3")]
+    endpoint: Option<Url>,
+}
+
+impl ObjectConfigArgs {
+    fn is_empty(&self) -> bool {
+        !self.file_system_given() && !self.imap_given() && !self.s3_given()
+    }
+
+    fn file_system_given(&self) -> bool {
+        self.root.is_some() || !self.include.is_empty() || !self.exclude.is_empty()
+    }
+
+    fn imap_given(&self) -> bool {
+        self.server.is_some() || self.account.is_some() || !self.folder.is_empty()
+    }
+
+    fn s3_given(&self) -> bool {
+        self.bucket.is_some() || self.prefix.is_some() || self.endpoint.is_some()
+    }
+
+    /// Applies the given options to `object_type`, fails if any of them do not fit it.
+    fn apply(self, object_type: &mut ObjectType) -> anyhow::Result<()> {
+        let (file_system, imap, s3) = match object_type {
+            ObjectType::FileSystem(FileSystem::Posix(_) | FileSystem::Windows(_)) => {
+                (true, false, false)
+            }
+            ObjectType::Email(Email::IMAP(_)) => (false, true, false),
+            ObjectType::Email(Email::GMAIL) => (false, false, false),
+            ObjectType::ObjectStorage(ObjectStorage::S3(_)) => (false, false, true),
+        };
+        for (name, given, applies) in [
+            ("file system", self.file_system_given(), file_system),
+            ("IMAP", self.imap_given(), imap),
+            ("S3", self.s3_given(), s3),
+        ] {
+            if given && !applies {
+                bail!(
+                    "{} options do not apply to object type [{}]",
+                    name,
+                    object_type
+                );
+            }
+        }
+
+        match object_type {
+            ObjectType::FileSystem(FileSystem::Posix(config) | FileSystem::Windows(config)) => {
+                if self.root.is_some() {
+                    config.root = self.root;
+                }
+                if !self.include.is_empty() {
+                    config.include = self.include;
+                }
+                if !self.exclude.is_empty() {
+                    config.exclude = self.exclude;
+                }
+            }
+            ObjectType::Email(Email::IMAP(config)) => {
+                if self.server.is_some() {
+                    config.server = self.server;
+                }
+                if self.account.is_some() {
+                    config.account = self.account;
+                }
+                if !self.folder.is_empty() {
+                    config.folders = self.folder;
+                }
+            }
+            ObjectType::Email(Email::GMAIL) => {}
+            ObjectType::ObjectStorage(ObjectStorage::S3(config)) => {
+                if self.bucket.is_some() {
+                    config.bucket = self.bucket;
+                }
+                if self.prefix.is_some() {
+                    config.prefix = self.prefix;
+                }
+                if self.endpoint.is_some() {
+                    config.endpoint = self.endpoint;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A change to an existing vault.
+enum VaultModification {
+    Rename(String),
+    Description(Option<String>),
+    State(VaultState, Option<String>),
+    Bridge(Option<BridgeAddress>),
+    PlaceHold(Option<String>),
+    ReleaseHold(Option<String>),
+    ObjectConfig(ObjectConfigArgs),
+    Remove,
+}
+
+/// The secret a [`VaultModification`] is authorized with.
+enum VaultSecret {
+    HelmKey(HelmKey),
+    ArkSeed(ArkSeed),
+}
+
+impl Display for VaultModification {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Rename(name) => write!(f, "{} {}", "New Name:".bold(), name),
+            Self::Description(description) => write!(
+                f,
+                "{} {}",
+                "New Description:".bold(),
+                description.as_deref().unwrap_or("<no description>")
+            ),
+            Self::State(state, reason) => {
+                write!(f, "{} {}", "New State:".bold(), state)?;
+                if let Some(reason) = reason {
+                    write!(f, " ({})", reason)?;
+                }
+                Ok(())
+            }
+            Self::Bridge(bridge) => write!(
+                f,
+                "{} {}",
+                "Authorized Bridge:".bold(),
+                bridge
+                    .as_ref()
+                    .map(|b| b.to_string())
+                    .unwrap_or("<none>".to_string())
+            ),
+            Self::PlaceHold(reason) => write!(
+                f,
+                "{} {}",
+                "Place Legal Hold:".bold(),
+                reason.as_deref().unwrap_or("<no reason given>")
+            ),
+            Self::ReleaseHold(reason) => write!(
+                f,
+                "{} {}",
+                "Release Legal Hold:".bold(),
+                reason.as_deref().unwrap_or("<no reason given>")
+            ),
+            Self::ObjectConfig(config) => {
+                write!(f, "{}", "New Object Configuration:".bold())?;
+                let values = [
+                    ("Root", config.root.clone()),
+                    ("Include", join_values(&config.include)),
+                    ("Exclude", join_values(&config.exclude)),
+                    ("Server", config.server.clone()),
+                    ("Account", config.account.clone()),
+                    ("Folders", join_values(&config.folder)),
+                    ("Bucket", config.bucket.clone()),
+                    ("Prefix", config.prefix.clone()),
+                    ("Endpoint", config.endpoint.as_ref().map(|e| e.to_string())),
+                ];
+                for (name, value) in values {
+                    if let Some(value) = value {
+                        write!(f, "\n{}: {}", name, value)?;
+                    }
+                }
+                Ok(())
+            }
+            Self::Remove => write!(f, "{}", "Remove Vault".bold()),
+        }
+    }
+}
+
+fn join_values(values: &[String]) -> Option<String> {
+    (!values.is_empty()).then(|| values.join(", "))
 }
 
 #[derive(Debug, Subcommand)]
@@ -88,10 +592,85 @@ enum ArkCommand {
         /// Public Worker Key
         #[arg(long, short = 'w')]
         worker: Option<PublicWorkerKey>,
+        /// Protect the Ark Seed with an additional passphrase
+        ///
+        /// The passphrase is required alongside the 24 words to recover the Ark.
+        /// A different passphrase leads to a different Ark!
+        #[arg(long, short = 'p')]
+        passphrase: bool,
+        /// Language of the Ark Seed words
+        ///
+        /// The language does not affect the keys, only how the words are written down.
+        #[arg(long, short = 'l', value_enum, default_value_t = SeedLanguage::English)]
+        language: SeedLanguage,
+        /// Write a printable paper backup (SVG) to the given file
+        ///
+        /// Contains the Ark Seed words and all secret keys, store it as securely as the seed itself.
+        #[arg(long, short = 'b')]
+        backup: Option<PathBuf>,
+    },
+    /// Resume an interrupted Ark creation
+    ///
+    /// Requires the Ark Seed. Only the missing parts are created.
+    Resume {
+        /// Name of the Ark
+        name: String,
+        /// Description of the Ark
+        #[arg(long, short = 'd')]
+        description: Option<String>,
+        /// Public Worker Key
+        #[arg(long, short = 'w')]
+        worker: Option<PublicWorkerKey>,
+        /// Write a printable paper backup (SVG) of the secret keys to the given file
+        #[arg(long, short = 'b')]
+        backup: Option<PathBuf>,
     },
     /// Show up-to-date details about a given Ark
     #[command(subcommand)]
     Show(ShowArkCommand),
+    /// Change the name, description or labels of an Ark
+    ///
+    /// Requires the Helm Key
+    Update {
+        /// The Ark Address - e.g. arkaddr1XXXXXX...
+        ark_address: ArkAddress,
+        /// New name of the Ark
+        #[arg(long, short = 'n')]
+        name: Option<String>,
+        /// New description of the Ark
+        #[arg(long, short = 'd', conflicts_with = "no_description")]
+        description: Option<String>,
+        /// Remove the description
+        #[arg(long)]
+        no_description: bool,
+        /// Add or overwrite a label, as `KEY=VALUE`
+        ///
+        /// Values are text unless prefixed with a type: `int:`, `bool:`, `time:` (RFC 3339)
+        /// e.g. `owner=finance` or `retention-years=int:7`
+        #[arg(long, short = 'l', value_name = "KEY=VALUE", value_parser = parse_label)]
+        label: Vec<(String, LabelValue)>,
+        /// Remove a label
+        #[arg(long, short = 'r', value_name = "KEY")]
+        remove_label: Vec<String>,
+    },
+    /// Check a given Ark for consistency & suggest repairs
+    ///
+    /// Checking the data keyring requires the Ark Seed or the Data Key.
+    #[command(subcommand)]
+    Verify(ShowArkCommand),
+    /// List all registers, scratchpads & pointers derived from an Ark Address
+    ///
+    /// Shows whether each record exists, its counter, size & state.
+    /// Nothing is decrypted or paid for.
+    Explore {
+        /// The Ark Address - e.g. arkaddr1XXXXXX...
+        ark_address: ArkAddress,
+        /// Also list the vault pointers
+        ///
+        /// Requires an authorized key to read the manifest.
+        #[arg(long, short = 'v')]
+        vaults: bool,
+    },
 }
 
 #[derive(Debug, Subcommand)]
@@ -110,6 +689,17 @@ enum KeyCommand {
     /// Rotate one or more keys
     #[command(subcommand)]
     Rotate(KeyRotateCommand),
+    /// Re-derive the current Helm Key & Data Key from the Ark Seed
+    ///
+    /// Nothing is rotated or paid for. The Worker Key cannot be recovered,
+    /// only its public key is shown.
+    Recover {
+        /// Add the keys to the keystore instead of showing them
+        ///
+        /// Stored as `<NAME>-helm` and `<NAME>-data`
+        #[arg(long, short = 's', value_name = "NAME")]
+        store: Option<String>,
+    },
 }
 
 #[derive(Debug, Subcommand)]
@@ -172,62 +762,344 @@ async fn main() -> anyhow::Result<()> {
 
     let arguments = Arguments::parse();
 
-    let client = (&arguments.autonomi_config).try_new_client().await?;
-    let wallet =
-        Wallet::new_from_private_key(client.evm_network().clone(), arguments.secret_key.as_ref())?;
+    let ledger = || -> anyhow::Result<Ledger> {
+        Ledger::open(match &arguments.ledger {
+            Some(path) => path.clone(),
+            None => dirs::data_local_dir()
+                .ok_or(anyhow!("unable to determine local data directory"))?
+                .join("ark")
+                .join("ledger.jsonl"),
+        })
+    };
+
+    if let Commands::Ledger(command) = &arguments.command {
+        let ledger = ledger()?;
+        return match command {
+            LedgerCommand::Summary => ledger_summary(&ledger),
+            LedgerCommand::Export { format, output } => {
+                ledger_export(&ledger, *format, output.as_ref())
+            }
+        };
+    }
+
+    let seeds = SeedSource {
+        seed: arguments.seed_from.clone(),
+        passphrase: arguments.seed_passphrase_from.clone(),
+    };
+
+    if let Commands::Seed(command) = &arguments.command {
+        return match command {
+            SeedCommand::Split { threshold, shares } => {
+                split_seed(*threshold, *shares, &seeds, &arguments.autonomi_config).await
+            }
+            SeedCommand::Verify { ark_address } => {
+                verify_seed_shares(ark_address.as_ref(), &arguments.autonomi_config).await
+            }
+        };
+    }
+
+    if let Commands::Keystore(command) = &arguments.command {
+        let location = keystore_location(&arguments)?;
+        return match command {
+            KeystoreCommand::Add { ark_address, name } => {
+                keystore_add(&location, ark_address, name).await
+            }
+            KeystoreCommand::List { ark_address } => {
+                keystore_list(&location, ark_address.as_ref()).await
+            }
+            KeystoreCommand::Remove { ark_address, name } => {
+                keystore_remove(&location, ark_address, name).await
+            }
+        };
+    }
+
+    let budget = match (arguments.budget_per_operation, arguments.budget_daily) {
+        (None, None) => None,
+        (per_operation, daily) => Some(
+            Budget::builder()
+                .maybe_per_operation(per_operation)
+                .maybe_daily(daily)
+                .maybe_ledger(daily.map(|_| ledger()).transpose()?)
+                .build()?,
+        ),
+    };
+
+    let journal = || -> anyhow::Result<Journal> {
+        Journal::open(match &arguments.journal {
+            Some(path) => path.clone(),
+            None => dirs::data_local_dir()
+                .ok_or(anyhow!("unable to determine local data directory"))?
+                .join("ark")
+                .join("journal"),
+        })
+    };
+
+    let storage = if arguments.cache {
+        cached_storage(&arguments).await?
+    } else {
+        (&arguments.autonomi_config).try_new_storage().await?
+    };
+    let keystore_location = keystore_location(&arguments)?;
+    let keys = match (&arguments.key_name, &arguments.key_from) {
+        (Some(name), _) => KeySource::Keystore {
+            location: keystore_location.clone(),
+            name: name.clone(),
+            keystore: OnceCell::new(),
+        },
+        (None, Some(provider)) => KeySource::Provider(provider.clone()),
+        (None, None) => KeySource::Prompt,
+    };
+    let wallet = async || -> anyhow::Result<Wallet> {
+        let secret_key = match &arguments.secret_key_from {
+            Some(provider) => provider.resolve().await?,
+            None => arguments
+                .secret_key
+                .clone()
+                .ok_or(anyhow!("a wallet SECRET_KEY is required for this action"))?,
+        };
+        Ok(Wallet::new_from_private_key(
+            arguments.autonomi_config.evm_network(),
+            secret_key.as_ref(),
+        )?)
+    };
 
     match arguments.command {
         Commands::Ark(ArkCommand::Create {
             name,
             description,
             worker,
+            passphrase,
+            language,
+            backup,
         }) => {
             create_ark(
                 name,
                 description,
                 worker,
-                &client,
-                &wallet,
+                passphrase,
+                language.into(),
+                backup.as_ref(),
+                &storage,
+                &wallet().await?,
+                &ledger()?,
+                budget.as_ref(),
+                &arguments.autonomi_config,
+            )
+            .await?;
+        }
+        Commands::Ark(ArkCommand::Resume {
+            name,
+            description,
+            worker,
+            backup,
+        }) => {
+            resume_ark_creation(
+                name,
+                description,
+                worker,
+                backup.as_ref(),
+                &seeds,
+                &storage,
+                &wallet().await?,
+                &ledger()?,
+                budget.as_ref(),
                 &arguments.autonomi_config,
             )
             .await?;
         }
         Commands::Ark(ArkCommand::Show(show)) => {
-            show_ark(show, &client, &wallet, &arguments.autonomi_config).await?;
+            show_ark(show, &keys, &seeds, &storage, &arguments.autonomi_config).await?;
+        }
+        Commands::Ark(ArkCommand::Update {
+            ark_address,
+            name,
+            description,
+            no_description,
+            label,
+            remove_label,
+        }) => {
+            let update = ArkMetadataUpdate::builder()
+                .maybe_name(name)
+                .maybe_description(match no_description {
+                    true => Some(None),
+                    false => description.map(Some),
+                })
+                .labels(label.into_iter().collect())
+                .remove_labels(remove_label.into_iter().collect())
+                .build();
+            update_ark(
+                ark_address,
+                update,
+                &keys,
+                &storage,
+                &wallet().await?,
+                &ledger()?,
+                budget.as_ref(),
+                &arguments.autonomi_config,
+            )
+            .await?;
+        }
+        Commands::Ark(ArkCommand::Verify(verify)) => {
+            verify_ark(verify, &keys, &seeds, &storage, &arguments.autonomi_config).await?;
+        }
+        Commands::Ark(ArkCommand::Explore {
+            ark_address,
+            vaults,
+        }) => {
+            explore_ark(
+                ark_address,
+                vaults,
+                &keys,
+                &storage,
+                &arguments.autonomi_config,
+            )
+            .await?;
         }
         Commands::Vault(VaultCommand::Create {
             name,
             description,
             bridge,
-            object_type,
+            mut object_type,
             ark_address,
+            config,
         }) => {
+            config.apply(&mut object_type)?;
             create_vault(
                 name,
                 description,
                 bridge,
                 object_type,
                 ark_address,
-                &client,
-                &wallet,
+                &keys,
+                &storage,
+                &wallet().await?,
+                &ledger()?,
+                budget.as_ref(),
                 &arguments.autonomi_config,
             )
             .await?;
         }
         Commands::Vault(VaultCommand::Check { vault_address }) => {
-            check_vault_address(vault_address, &client, &arguments.autonomi_config).await?;
+            check_vault_address(vault_address, &storage, &arguments.autonomi_config).await?;
         }
-        Commands::Key(KeyCommand::Rotate(rotate)) => {
-            rotate_key(rotate, &client, &wallet, &arguments.autonomi_config).await?;
+        Commands::Vault(VaultCommand::List { ark_address }) => {
+            list_vaults(ark_address, &keys, &storage, &arguments.autonomi_config).await?;
         }
-    }
-
-    Ok(())
-}
-
+        Commands::Vault(VaultCommand::Show { vault_address }) => {
+            show_vault(vault_address, &keys, &storage, &arguments.autonomi_config).await?;
+        }
+        Commands::Vault(modify) => {
+            let (vault_address, modification) = match modify {
+                VaultCommand::Rename {
+                    vault_address,
+                    name,
+                } => (vault_address, VaultModification::Rename(name)),
+                VaultCommand::SetDescription {
+                    vault_address,
+                    description,
+                } => (vault_address, VaultModification::Description(description)),
+                VaultCommand::Activate {
+                    vault_address,
+                    reason,
+                } => (
+                    vault_address,
+                    VaultModification::State(VaultState::Active, reason),
+                ),
+                VaultCommand::Deactivate {
+                    vault_address,
+                    reason,
+                } => (
+                    vault_address,
+                    VaultModification::State(VaultState::Paused, reason),
+                ),
+                VaultCommand::Archive {
+                    vault_address,
+                    reason,
+                } => (
+                    vault_address,
+                    VaultModification::State(VaultState::Archived, reason),
+                ),
+                VaultCommand::Decommission {
+                    vault_address,
+                    reason,
+                } => (
+                    vault_address,
+                    VaultModification::State(VaultState::Decommissioned, reason),
+                ),
+                VaultCommand::SetBridge {
+                    vault_address,
+                    bridge,
+                } => (vault_address, VaultModification::Bridge(bridge)),
+                VaultCommand::Hold {
+                    vault_address,
+                    reason,
+                } => (vault_address, VaultModification::PlaceHold(reason)),
+                VaultCommand::ReleaseHold {
+                    vault_address,
+                    reason,
+                } => (vault_address, VaultModification::ReleaseHold(reason)),
+                VaultCommand::Configure {
+                    vault_address,
+                    config,
+                } => {
+                    if config.is_empty() {
+                        bail!("no configuration option given");
+                    }
+                    (vault_address, VaultModification::ObjectConfig(config))
+                }
+                VaultCommand::Remove { vault_address } => {
+                    (vault_address, VaultModification::Remove)
+                }
+                _ => unreachable!("handled above"),
+            };
+            modify_vault(
+                vault_address,
+                modification,
+                &keys,
+                &seeds,
+                &storage,
+                &wallet().await?,
+                &ledger()?,
+                budget.as_ref(),
+                &arguments.autonomi_config,
+            )
+            .await?;
+        }
+        Commands::Key(KeyCommand::Rotate(rotate)) => {
+            rotate_key(
+                rotate,
+                &keys,
+                &seeds,
+                &storage,
+                &wallet().await?,
+                &ledger()?,
+                budget.as_ref(),
+                &journal()?,
+                &arguments.autonomi_config,
+            )
+            .await?;
+        }
+        Commands::Key(KeyCommand::Recover { store }) => {
+            recover_keys(
+                store.as_deref(),
+                &seeds,
+                &keystore_location,
+                &storage,
+                &arguments.autonomi_config,
+            )
+            .await?;
+        }
+        Commands::Ledger(_) | Commands::Seed(_) | Commands::Keystore(_) => {
+            unreachable!("handled above")
+        }
+    }
+
+    Ok(())
+}
+
 async fn check_vault_address(
     vault_address: VaultAddress,
-    client: &Client,
+    storage: &Storage,
     autonomi_config: &AutonomiClientConfig,
 ) -> anyhow::Result<()> {
     action_preview(
@@ -237,7 +1109,7 @@ async fn check_vault_address(
         autonomi_config,
     );
 
-    let (mut progress, fut) = Core::ark_from_vault_address(client, &vault_address);
+    let (mut progress, fut) = Core::ark_from_vault_address(storage, &vault_address);
     tokio::pin!(fut);
 
     let mut progress_view = ProgressView::new(&progress.latest(), Duration::from_millis(100));
@@ -274,14 +1146,292 @@ async fn check_vault_address(
     Ok(())
 }
 
+/// Looks up the Ark a vault belongs to via its ark pointer.
+async fn ark_of_vault(
+    vault_address: &VaultAddress,
+    storage: &Storage,
+) -> anyhow::Result<ArkAddress> {
+    let (_, fut) = Core::ark_from_vault_address(storage, vault_address);
+    fut.await?
+        .ok_or(anyhow!("[{}] is not a valid vault address", vault_address))
+}
+
+async fn list_vaults(
+    ark_address: ArkAddress,
+    keys: &KeySource,
+    storage: &Storage,
+    autonomi_config: &AutonomiClientConfig,
+) -> anyhow::Result<()> {
+    action_preview(
+        "List Vaults",
+        Some("Provide the Secret Key now"),
+        None,
+        autonomi_config,
+    );
+    let ark_key = keys.ark_key(&ark_address).await?;
+
+    let core = ReadOnlyCore::builder()
+        .storage(storage.clone())
+        .ark_address(ark_address.clone())
+        .build();
+
+    let (mut progress, fut) = core.vaults(&ark_key);
+    tokio::pin!(fut);
+
+    let mut progress_view = ProgressView::new(&progress.latest(), Duration::from_millis(100));
+    let (vaults, _) = loop {
+        let next_tick_in = progress_view.next_tick_in();
+
+        tokio::select! {
+            res = &mut fut => {
+                break res.map_err(|(err, _)| err)?;
+            },
+            _ = &mut progress => {
+                progress_view.update(&progress.latest());
+            },
+            _ = tokio::time::sleep(next_tick_in) => {
+                progress_view.tick();
+            }
+        }
+    };
+
+    progress_view.clear();
+
+    const INDENT: &str = "    ";
+
+    println!();
+    println!("{}", "VAULTS".cyan().bold());
+    if vaults.is_empty() {
+        println!("{}{}", INDENT, "<none>".italic());
+        println!();
+    }
+    for vault in &vaults {
+        println!(
+            "{}{} ({}, {})",
+            INDENT,
+            vault.name.bold(),
+            vault.object_type,
+            colored_state(vault.status.state)
+        );
+        if vault.legal_hold(&ark_address).is_some() {
+            println!("{}{}", INDENT, "under legal hold".yellow().bold());
+        }
+        println!("{}{}", INDENT, vault.address);
+        println!();
+    }
+    Ok(())
+}
+
+async fn show_vault(
+    vault_address: VaultAddress,
+    keys: &KeySource,
+    storage: &Storage,
+    autonomi_config: &AutonomiClientConfig,
+) -> anyhow::Result<()> {
+    action_preview(
+        "Display Vault Details",
+        Some("Provide the Secret Key now"),
+        None,
+        autonomi_config,
+    );
+    let ark_address = ark_of_vault(&vault_address, storage).await?;
+    let ark_key = keys.ark_key(&ark_address).await?;
+
+    let core = ReadOnlyCore::builder()
+        .storage(storage.clone())
+        .ark_address(ark_address.clone())
+        .build();
+
+    let (mut progress, fut) = core.vault_details(&vault_address, &ark_key);
+    tokio::pin!(fut);
+
+    let mut progress_view = ProgressView::new(&progress.latest(), Duration::from_millis(100));
+    let (vault_config, _) = loop {
+        let next_tick_in = progress_view.next_tick_in();
+
+        tokio::select! {
+            res = &mut fut => {
+                break res.map_err(|(err, _)| err)?;
+            },
+            _ = &mut progress => {
+                progress_view.update(&progress.latest());
+            },
+            _ = tokio::time::sleep(next_tick_in) => {
+                progress_view.tick();
+            }
+        }
+    };
+
+    progress_view.clear();
+
+    const INDENT: &str = "    ";
+
+    println!();
+    println!("{}", "VAULT DETAILS".cyan().bold());
+    println!("{}{}", INDENT, "ARK ADDRESS:".bold());
+    println!("{}{}", INDENT, ark_address);
+    println!();
+    display_vault_config(&vault_config, &ark_address, INDENT);
+    println!();
+    Ok(())
+}
+
+async fn modify_vault(
+    vault_address: VaultAddress,
+    modification: VaultModification,
+    keys: &KeySource,
+    seeds: &SeedSource,
+    storage: &Storage,
+    wallet: &Wallet,
+    ledger: &Ledger,
+    budget: Option<&Budget>,
+    autonomi_config: &AutonomiClientConfig,
+) -> anyhow::Result<()> {
+    let ark_address = ark_of_vault(&vault_address, storage).await?;
+
+    action_preview(
+        "Modify Vault",
+        Some(
+            format!(
+                "{} {}\n{} {}\n\n{}",
+                "Ark:".bold(),
+                ark_address,
+                "Vault:".bold(),
+                vault_address,
+                modification
+            )
+            .as_str(),
+        ),
+        Some(wallet),
+        autonomi_config,
+    );
+
+    let secret = match &modification {
+        VaultModification::ReleaseHold(_) => {
+            let ark_seed = seeds.ark_seed().await?;
+            if ark_seed.address() != &ark_address {
+                bail!(
+                    "the given Ark Seed does not belong to ark [{}]",
+                    ark_address
+                );
+            }
+            VaultSecret::ArkSeed(ark_seed)
+        }
+        _ => VaultSecret::HelmKey(keys.helm_key(&ark_address).await?),
+    };
+
+    println!();
+    println!("✅ {}", "Provided secrets appear valid".green().bold());
+    println!();
+
+    if !ask_proceed().await {
+        println!(" ❌ {}", "Aborting".red());
+        println!();
+        return Ok(());
+    }
+
+    let core = Core::builder()
+        .storage(storage.clone())
+        .wallet(wallet.clone())
+        .maybe_budget(budget.cloned())
+        .ark_address(ark_address.clone())
+        .build();
+
+    let (mut progress, mut fut) = match (modification, &secret) {
+        (VaultModification::Rename(name), VaultSecret::HelmKey(helm_key)) => {
+            let (progress, fut) = core.rename_vault(&vault_address, name, helm_key).await;
+            (progress, fut.boxed())
+        }
+        (VaultModification::Description(description), VaultSecret::HelmKey(helm_key)) => {
+            let (progress, fut) = core
+                .update_vault_description(&vault_address, description, helm_key)
+                .await;
+            (progress, fut.boxed())
+        }
+        (VaultModification::State(state, reason), VaultSecret::HelmKey(helm_key)) => {
+            let (progress, fut) = core
+                .set_vault_state(&vault_address, state, reason, helm_key)
+                .await;
+            (progress, fut.boxed())
+        }
+        (VaultModification::Bridge(bridge), VaultSecret::HelmKey(helm_key)) => {
+            let (progress, fut) = core
+                .update_vault_bridge(&vault_address, bridge, helm_key)
+                .await;
+            (progress, fut.boxed())
+        }
+        (VaultModification::PlaceHold(reason), VaultSecret::HelmKey(helm_key)) => {
+            let (progress, fut) = core
+                .place_legal_hold(&vault_address, reason, helm_key)
+                .await;
+            (progress, fut.boxed())
+        }
+        (VaultModification::ReleaseHold(reason), VaultSecret::ArkSeed(ark_seed)) => {
+            let (progress, fut) = core
+                .release_legal_hold(&vault_address, reason, ark_seed)
+                .await;
+            (progress, fut.boxed())
+        }
+        (VaultModification::ObjectConfig(config), VaultSecret::HelmKey(helm_key)) => {
+            let accessor = ArkAccessor::from(helm_key.clone());
+            let (_, fut) = core.vault_details(&vault_address, &accessor);
+            let (vault, _) = fut.await.map_err(|(err, _)| err)?;
+            let mut object_type = vault.object_type;
+            config.apply(&mut object_type)?;
+            let (progress, fut) = core
+                .update_vault_object_type(&vault_address, object_type, helm_key)
+                .await;
+            (progress, fut.boxed())
+        }
+        (VaultModification::Remove, VaultSecret::HelmKey(helm_key)) => {
+            let (progress, fut) = core.remove_vault(&vault_address, helm_key).await;
+            (progress, fut.boxed())
+        }
+        _ => unreachable!("secret to match the modification"),
+    };
+
+    let mut progress_view = ProgressView::new(&progress.latest(), Duration::from_millis(100));
+    let (_, receipt) = loop {
+        let next_tick_in = progress_view.next_tick_in();
+
+        tokio::select! {
+            res = &mut fut => {
+                break settle(ledger, res)?;
+            },
+            _ = &mut progress => {
+                progress_view.update(&progress.latest());
+            },
+            _ = tokio::time::sleep(next_tick_in) => {
+                progress_view.tick();
+            }
+        }
+    };
+
+    progress_view.clear();
+
+    const INDENT: &str = "    ";
+
+    println!();
+    println!("{} ✅", "Vault Modification Successful".green().bold());
+    println!();
+
+    println!("{}", "TOTAL NETWORK COST:".cyan().bold());
+    println!("{}{}", INDENT, receipt.total_cost().to_string().italic());
+    println!();
+    Ok(())
+}
+
 async fn create_vault(
     name: String,
     description: Option<String>,
     bridge: Option<BridgeAddress>,
     object_type: ObjectType,
     ark_address: ArkAddress,
-    client: &Client,
+    keys: &KeySource,
+    storage: &Storage,
     wallet: &Wallet,
+    ledger: &Ledger,
+    budget: Option<&Budget>,
     autonomi_config: &AutonomiClientConfig,
 ) -> anyhow::Result<()> {
     let settings = VaultCreationSettings::builder()
@@ -318,7 +1468,7 @@ async fn create_vault(
                 settings.active(),
                 "Object Type:".bold(),
                 settings.object_type(),
-                "Warning: can NOT be changed later!".yellow()
+                "Warning: the type can NOT be changed later!".yellow()
             )
             .as_str(),
         ),
@@ -326,27 +1476,28 @@ async fn create_vault(
         autonomi_config,
     );
 
-    if !ask_proceed().await {
-        println!(" ❌ {}", "Aborting".red());
-        println!();
-        return Ok(());
-    }
-
-    println!();
-    println!(" Provide the {} now ", "HELM KEY".bold());
-    println!();
-
-    let helm_key = read_helm_key().await?;
+    let helm_key = keys.helm_key(&ark_address).await?;
 
     println!();
     println!("✅ {}", "Provided secrets appear valid".green().bold());
+    println!();
 
     let core = Core::builder()
-        .client(client.clone())
+        .storage(storage.clone())
         .wallet(wallet.clone())
+        .maybe_budget(budget.cloned())
         .ark_address(ark_address.clone())
         .build();
 
+    let (progress, fut) = core.quote_create_vault(settings.clone(), &helm_key);
+    print_cost_estimate(progress, fut).await?;
+
+    if !ask_proceed().await {
+        println!(" ❌ {}", "Aborting".red());
+        println!();
+        return Ok(());
+    }
+
     let (mut progress, fut) = core.create_vault(settings, &helm_key);
     tokio::pin!(fut);
 
@@ -356,7 +1507,7 @@ async fn create_vault(
 
         tokio::select! {
             res = &mut fut => {
-                break res.map_err(|(err, _)| err)?;
+                break settle(ledger, res)?;
             },
             _ = &mut progress => {
                 progress_view.update(&progress.latest());
@@ -375,7 +1526,7 @@ async fn create_vault(
     println!("{} ✅", "Vault Creation Successful".green().bold());
 
     println!();
-    display_vault_config(&vault_config, INDENT);
+    display_vault_config(&vault_config, &ark_address, INDENT);
     println!();
 
     println!();
@@ -388,58 +1539,58 @@ async fn create_vault(
     Ok(())
 }
 
-async fn show_ark(
-    show: ShowArkCommand,
-    client: &Client,
-    wallet: &Wallet,
+async fn read_ark_accessor(
+    source: ShowArkCommand,
+    action: &str,
+    keys: &KeySource,
+    seeds: &SeedSource,
     autonomi_config: &AutonomiClientConfig,
-) -> anyhow::Result<()> {
-    let (ark_address, ark_accessor) = match show {
+) -> anyhow::Result<(ArkAddress, ArkAccessor)> {
+    Ok(match source {
         ShowArkCommand::WithKey { ark_address } => {
             action_preview(
-                "Display Ark Details",
+                action,
                 Some("Provide the Secret Key now"),
                 None,
                 autonomi_config,
             );
-            (ark_address, read_ark_key().await?)
+            let ark_key = keys.ark_key(&ark_address).await?;
+            (ark_address, ark_key)
         }
         ShowArkCommand::WithSeed => {
             action_preview(
-                "Display Ark Details",
+                action,
                 Some("Provide the Ark Seed now"),
                 None,
                 autonomi_config,
             );
-            let ark_seed = read_seed().await?;
+            let ark_seed = seeds.ark_seed().await?;
             let ark_address = ark_seed.address().clone();
             (ark_address, ark_seed.into())
         }
-    };
-
-    const INDENT: &str = "    ";
-
-    println!();
-    println!("✅ {}", "Provided secrets appear valid".green().bold());
-
-    println!();
-    println!("{}", "DETAILS".cyan().bold());
+    })
+}
 
-    println!("{}{}", INDENT, "ARK ADDRESS:".bold());
-    println!("{}{}", INDENT, ark_address);
-    println!();
+async fn verify_ark(
+    verify: ShowArkCommand,
+    keys: &KeySource,
+    seeds: &SeedSource,
+    storage: &Storage,
+    autonomi_config: &AutonomiClientConfig,
+) -> anyhow::Result<()> {
+    let (ark_address, ark_accessor) =
+        read_ark_accessor(verify, "Verify Ark", keys, seeds, autonomi_config).await?;
 
-    let core = Core::builder()
-        .client(client.clone())
-        .wallet(wallet.clone())
+    let core = ReadOnlyCore::builder()
+        .storage(storage.clone())
         .ark_address(ark_address.clone())
         .build();
 
-    let (mut progress, fut) = core.ark_details(&ark_accessor);
+    let (mut progress, fut) = core.verify_ark(&ark_accessor);
     tokio::pin!(fut);
 
     let mut progress_view = ProgressView::new(&progress.latest(), Duration::from_millis(100));
-    let (manifest, _) = loop {
+    let (report, _) = loop {
         let next_tick_in = progress_view.next_tick_in();
 
         tokio::select! {
@@ -457,34 +1608,125 @@ async fn show_ark(
 
     progress_view.clear();
 
-    println!();
-    println!("{}", "ARK DETAILS".cyan().bold());
+    const INDENT: &str = "    ";
 
-    println!("{}{}", INDENT, "ADDRESS:".bold());
-    println!("{}{}", INDENT, manifest.ark_address);
     println!();
-
-    println!("{}{}", INDENT, "CREATED AT:".bold());
-    println!("{}{}", INDENT, manifest.created);
+    println!("{}", "VERIFICATION REPORT".cyan().bold());
+    println!("{}{}", INDENT, "ARK ADDRESS:".bold());
+    println!("{}{}", INDENT, report.ark_address);
     println!();
 
-    println!("{}{}", INDENT, "LAST MODIFIED AT:".bold());
-    println!("{}{}", INDENT, manifest.last_modified);
+    for check in &report.checks {
+        match &check.outcome {
+            CheckOutcome::Passed => println!("{}✅ {}", INDENT, check.check),
+            CheckOutcome::Failed { problem, .. } => {
+                println!("{}❌ {}", INDENT, check.check.bold());
+                println!("{}{}{}", INDENT, INDENT, problem.red());
+            }
+            CheckOutcome::Skipped(reason) => {
+                println!("{}➖ {} ({})", INDENT, check.check, reason.italic())
+            }
+        }
+    }
     println!();
 
-    println!("{}{}", INDENT, "NAME:".bold());
-    println!("{}{}", INDENT, manifest.name);
-    println!();
+    if report.is_consistent() {
+        println!("{}", "All Good!".green().bold());
+        println!();
+        return Ok(());
+    }
 
-    if let Some(description) = &manifest.description {
-        println!("{}{}", INDENT, "DESCRIPTION:".bold());
-        println!("{}{}", INDENT, description);
+    let repairs = report.repairs();
+    if !repairs.is_empty() {
+        println!("{}", "SUGGESTED REPAIRS".yellow().bold());
+        for repair in repairs {
+            println!("{}• {}", INDENT, repair);
+        }
         println!();
     }
 
-    println!(
-        "{}{}",
-        INDENT,
+    bail!("ark [{}] is inconsistent", report.ark_address);
+}
+
+async fn show_ark(
+    show: ShowArkCommand,
+    keys: &KeySource,
+    seeds: &SeedSource,
+    storage: &Storage,
+    autonomi_config: &AutonomiClientConfig,
+) -> anyhow::Result<()> {
+    let (ark_address, ark_accessor) =
+        read_ark_accessor(show, "Display Ark Details", keys, seeds, autonomi_config).await?;
+
+    const INDENT: &str = "    ";
+
+    println!();
+    println!("✅ {}", "Provided secrets appear valid".green().bold());
+
+    println!();
+    println!("{}", "DETAILS".cyan().bold());
+
+    println!("{}{}", INDENT, "ARK ADDRESS:".bold());
+    println!("{}{}", INDENT, ark_address);
+    println!();
+
+    let core = ReadOnlyCore::builder()
+        .storage(storage.clone())
+        .ark_address(ark_address.clone())
+        .build();
+
+    let (mut progress, fut) = core.ark_details(&ark_accessor);
+    tokio::pin!(fut);
+
+    let mut progress_view = ProgressView::new(&progress.latest(), Duration::from_millis(100));
+    let (manifest, _) = loop {
+        let next_tick_in = progress_view.next_tick_in();
+
+        tokio::select! {
+            res = &mut fut => {
+                break res.map_err(|(err, _)| err)?;
+            },
+            _ = &mut progress => {
+                progress_view.update(&progress.latest());
+            },
+            _ = tokio::time::sleep(next_tick_in) => {
+                progress_view.tick();
+            }
+        }
+    };
+
+    progress_view.clear();
+
+    println!();
+    println!("{}", "ARK DETAILS".cyan().bold());
+
+    println!("{}{}", INDENT, "ADDRESS:".bold());
+    println!("{}{}", INDENT, manifest.ark_address);
+    println!();
+
+    println!("{}{}", INDENT, "CREATED AT:".bold());
+    println!("{}{}", INDENT, manifest.created);
+    println!();
+
+    println!("{}{}", INDENT, "LAST MODIFIED AT:".bold());
+    println!("{}{}", INDENT, manifest.last_modified);
+    println!();
+
+    println!("{}{}", INDENT, "NAME:".bold());
+    println!("{}{}", INDENT, manifest.name);
+    println!();
+
+    if let Some(description) = &manifest.description {
+        println!("{}{}", INDENT, "DESCRIPTION:".bold());
+        println!("{}{}", INDENT, description);
+        println!();
+    }
+
+    display_labels(&manifest.labels, INDENT);
+
+    println!(
+        "{}{}",
+        INDENT,
         "CURRENT AUTHORIZED WORKER PUBLIC KEY:".bold()
     );
     println!("{}{}", INDENT, manifest.authorized_worker);
@@ -508,7 +1750,11 @@ async fn show_ark(
 
     for vault in &manifest.vaults {
         println!();
-        display_vault_config(vault, format!("{}{}", INDENT, INDENT).as_str());
+        display_vault_config(
+            vault,
+            &manifest.ark_address,
+            format!("{}{}", INDENT, INDENT).as_str(),
+        );
         println!("{}{}---", INDENT, INDENT);
     }
 
@@ -516,7 +1762,282 @@ async fn show_ark(
     Ok(())
 }
 
-fn display_vault_config(vault: &VaultConfig, indent: &str) {
+async fn update_ark(
+    ark_address: ArkAddress,
+    update: ArkMetadataUpdate,
+    keys: &KeySource,
+    storage: &Storage,
+    wallet: &Wallet,
+    ledger: &Ledger,
+    budget: Option<&Budget>,
+    autonomi_config: &AutonomiClientConfig,
+) -> anyhow::Result<()> {
+    if update.is_empty() {
+        bail!("nothing to update");
+    }
+
+    action_preview(
+        "Update Ark Metadata",
+        Some(format!("{} {}", "Ark:".bold(), ark_address).as_str()),
+        Some(wallet),
+        autonomi_config,
+    );
+
+    let helm_key = keys.helm_key(&ark_address).await?;
+
+    println!();
+    println!("✅ {}", "Provided secrets appear valid".green().bold());
+    println!();
+
+    if !ask_proceed().await {
+        println!(" ❌ {}", "Aborting".red());
+        println!();
+        return Ok(());
+    }
+
+    let core = Core::builder()
+        .storage(storage.clone())
+        .wallet(wallet.clone())
+        .maybe_budget(budget.cloned())
+        .ark_address(ark_address.clone())
+        .build();
+
+    let (mut progress, fut) = core.update_ark_metadata(update, &helm_key);
+    tokio::pin!(fut);
+
+    let mut progress_view = ProgressView::new(&progress.latest(), Duration::from_millis(100));
+    let (manifest, receipt) = loop {
+        let next_tick_in = progress_view.next_tick_in();
+
+        tokio::select! {
+            res = &mut fut => {
+                break settle(ledger, res)?;
+            },
+            _ = &mut progress => {
+                progress_view.update(&progress.latest());
+            },
+            _ = tokio::time::sleep(next_tick_in) => {
+                progress_view.tick();
+            }
+        }
+    };
+
+    progress_view.clear();
+
+    const INDENT: &str = "    ";
+
+    println!();
+    println!("{} ✅", "Ark Update Successful".green().bold());
+    println!();
+
+    println!("{}{}", INDENT, "NAME:".bold());
+    println!("{}{}", INDENT, manifest.name);
+    println!();
+
+    if let Some(description) = &manifest.description {
+        println!("{}{}", INDENT, "DESCRIPTION:".bold());
+        println!("{}{}", INDENT, description);
+        println!();
+    }
+
+    display_labels(&manifest.labels, INDENT);
+
+    println!("{}", "TOTAL NETWORK COST:".cyan().bold());
+    println!("{}{}", INDENT, receipt.total_cost().to_string().italic());
+    println!();
+    Ok(())
+}
+
+fn display_labels(labels: &BTreeMap<String, LabelValue>, indent: &str) {
+    if labels.is_empty() {
+        return;
+    }
+    println!("{}{}", indent, "LABELS:".bold());
+    for (key, value) in labels {
+        println!("{}{}{}: {}", indent, indent, key, value);
+    }
+    println!();
+}
+
+fn parse_label(s: &str) -> anyhow::Result<(String, LabelValue)> {
+    let (key, value) = s
+        .split_once('=')
+        .ok_or(anyhow!("invalid label [{}], expected KEY=VALUE", s))?;
+    Ok((key.trim().to_string(), LabelValue::from_str(value)?))
+}
+
+async fn explore_ark(
+    ark_address: ArkAddress,
+    vaults: bool,
+    keys: &KeySource,
+    storage: &Storage,
+    autonomi_config: &AutonomiClientConfig,
+) -> anyhow::Result<()> {
+    action_preview(
+        "Explore Ark",
+        vaults.then_some("Provide the Secret Key now"),
+        None,
+        autonomi_config,
+    );
+    let ark_accessor = match vaults {
+        true => Some(keys.ark_key(&ark_address).await?),
+        false => None,
+    };
+
+    let core = ReadOnlyCore::builder()
+        .storage(storage.clone())
+        .ark_address(ark_address.clone())
+        .build();
+
+    let (mut progress, fut) = core.explore_ark(ark_accessor.as_ref());
+    tokio::pin!(fut);
+
+    let mut progress_view = ProgressView::new(&progress.latest(), Duration::from_millis(100));
+    let (records, _) = loop {
+        let next_tick_in = progress_view.next_tick_in();
+
+        tokio::select! {
+            res = &mut fut => {
+                break res.map_err(|(err, _)| err)?;
+            },
+            _ = &mut progress => {
+                progress_view.update(&progress.latest());
+            },
+            _ = tokio::time::sleep(next_tick_in) => {
+                progress_view.tick();
+            }
+        }
+    };
+
+    progress_view.clear();
+
+    const INDENT: &str = "    ";
+
+    println!();
+    println!("{}", "DERIVED RECORDS".cyan().bold());
+    println!("{}{}", INDENT, "ARK ADDRESS:".bold());
+    println!("{}{}", INDENT, ark_address);
+    println!();
+
+    for record in &records {
+        let state = match record.state {
+            RecordState::Live => record.state.to_string().green(),
+            RecordState::Missing => record.state.to_string().red(),
+            RecordState::Finalized | RecordState::Retired => record.state.to_string().yellow(),
+        };
+        println!(
+            "{}{} ({}, {})",
+            INDENT,
+            record.name.bold(),
+            record.kind,
+            record.derived_from
+        );
+        println!("{}{}{}", INDENT, INDENT, record.address);
+        print!("{}{}state: {}", INDENT, INDENT, state);
+        if let Some(counter) = record.counter {
+            print!(", counter: {}", counter);
+        }
+        if let Some(size) = record.size {
+            print!(", size: {} bytes", size);
+        }
+        println!();
+        println!();
+    }
+
+    Ok(())
+}
+
+async fn recover_keys(
+    store: Option<&str>,
+    seeds: &SeedSource,
+    keystore_location: &KeystoreLocation,
+    storage: &Storage,
+    autonomi_config: &AutonomiClientConfig,
+) -> anyhow::Result<()> {
+    action_preview(
+        "Recover Current Keys",
+        Some("Provide the Ark Seed now"),
+        None,
+        autonomi_config,
+    );
+
+    let ark_seed = seeds.ark_seed().await?;
+    let core = ReadOnlyCore::builder()
+        .storage(storage.clone())
+        .ark_address(ark_seed.address().clone())
+        .build();
+
+    let (mut progress, fut) = core.recover_keys(&ark_seed);
+    tokio::pin!(fut);
+
+    let mut progress_view = ProgressView::new(&progress.latest(), Duration::from_millis(100));
+    let (recovered, _) = loop {
+        let next_tick_in = progress_view.next_tick_in();
+
+        tokio::select! {
+            res = &mut fut => {
+                break res.map_err(|(err, _)| err)?;
+            },
+            _ = &mut progress => {
+                progress_view.update(&progress.latest());
+            },
+            _ = tokio::time::sleep(next_tick_in) => {
+                progress_view.tick();
+            }
+        }
+    };
+
+    progress_view.clear();
+
+    const INDENT: &str = "    ";
+
+    println!();
+    println!("{} ✅", "Key Recovery Successful".green().bold());
+    println!();
+
+    println!("{}{}", INDENT, "ARK ADDRESS:".bold());
+    println!("{}{}", INDENT, ark_seed.address());
+    println!();
+
+    println!("{}{}", INDENT, "PUBLIC WORKER KEY:".bold());
+    println!("{}{}", INDENT, recovered.public_worker_key);
+    println!();
+
+    if let Some(name) = store {
+        let mut keystore = open_or_create_keystore(keystore_location).await?;
+        let helm_name = format!("{}-helm", name);
+        let data_name = format!("{}-data", name);
+        keystore.add(ark_seed.address(), &helm_name, &recovered.helm_key)?;
+        keystore.add(ark_seed.address(), &data_name, &recovered.data_key)?;
+        println!(
+            "✅ {} [{}] & [{}]",
+            "Keys added to the keystore as".green().bold(),
+            helm_name,
+            data_name
+        );
+        println!();
+        return Ok(());
+    }
+
+    println!("{}", "SECURITY WARNING".yellow().bold());
+    println!("{}You are about to view your SECRET ARK KEYS", INDENT);
+    println!("{}• Ensure no one is looking at your screen", INDENT);
+    println!("{}• Clear or close your terminal once you are done", INDENT);
+
+    press_enter_key().await;
+
+    println!("{}{}", INDENT, "HELM KEY:".bold());
+    println!("{}{}", INDENT, recovered.helm_key.danger_to_string());
+    println!();
+
+    println!("{}{}", INDENT, "DATA KEY:".bold());
+    println!("{}{}", INDENT, recovered.data_key.danger_to_string());
+    println!();
+
+    Ok(())
+}
+
+fn display_vault_config(vault: &VaultConfig, ark_address: &ArkAddress, indent: &str) {
     println!("{}{}", indent, "VAULT ADDRESS:".bold());
     println!("{}{}", indent, vault.address);
     println!();
@@ -539,15 +2060,61 @@ fn display_vault_config(vault: &VaultConfig, indent: &str) {
         println!();
     }
 
-    println!("{}{}", indent, "ACTIVE:".bold());
-    let active = if vault.active {
-        format!("{}", "YES".green())
-    } else {
-        format!("{}", "NO".red())
-    };
-    println!("{}{}", indent, active);
+    println!("{}{}", indent, "STATE:".bold());
+    println!(
+        "{}{} since {}",
+        indent,
+        colored_state(vault.status.state),
+        vault.status.since
+    );
+    if let Some(reason) = &vault.status.reason {
+        println!("{}{}", indent, reason.italic());
+    }
     println!();
 
+    if !vault.status_history.is_empty() {
+        println!("{}{}", indent, "STATE HISTORY:".bold());
+        for status in vault.status_history.iter().rev() {
+            print!("{}{} since {}", indent, status.state, status.since);
+            if let Some(reason) = &status.reason {
+                print!(" ({})", reason);
+            }
+            println!();
+        }
+        println!();
+    }
+
+    if !vault.legal_holds.is_empty() {
+        println!("{}{}", indent, "LEGAL HOLDS:".bold());
+        for hold in vault.legal_holds.iter().rev() {
+            match &hold.released {
+                Some(release) if hold.is_released(&vault.address, ark_address) => {
+                    print!("{}placed {}, released {}", indent, hold.placed, release.at)
+                }
+                Some(_) => print!(
+                    "{}{} since {}, release signature invalid",
+                    indent,
+                    "IN EFFECT".yellow().bold(),
+                    hold.placed
+                ),
+                None => print!(
+                    "{}{} since {}",
+                    indent,
+                    "IN EFFECT".yellow().bold(),
+                    hold.placed
+                ),
+            }
+            if let Some(reason) = &hold.reason {
+                print!(" ({})", reason);
+            }
+            println!();
+            if let Some(reason) = hold.released.as_ref().and_then(|r| r.reason.as_ref()) {
+                println!("{}released: {}", indent, reason.italic());
+            }
+        }
+        println!();
+    }
+
     println!("{}{}", indent, "AUTHORIZED BRIDGE:".bold());
     println!(
         "{}{}",
@@ -562,12 +2129,57 @@ fn display_vault_config(vault: &VaultConfig, indent: &str) {
 
     println!("{}{}", indent, "OBJECT TYPE:".bold());
     println!("{}{}", indent, vault.object_type);
+    display_object_config(&vault.object_type, indent);
+}
+
+fn display_object_config(object_type: &ObjectType, indent: &str) {
+    let values = match object_type {
+        ObjectType::FileSystem(FileSystem::Posix(config) | FileSystem::Windows(config)) => vec![
+            ("Root", config.root.clone()),
+            ("Include", join_values(&config.include)),
+            ("Exclude", join_values(&config.exclude)),
+        ],
+        ObjectType::Email(Email::IMAP(config)) => vec![
+            ("Server", config.server.clone()),
+            ("Account", config.account.clone()),
+            ("Folders", join_values(&config.folders)),
+        ],
+        ObjectType::Email(Email::GMAIL) => vec![],
+        ObjectType::ObjectStorage(ObjectStorage::S3(config)) => vec![
+            ("Bucket", config.bucket.clone()),
+            ("Prefix", config.prefix.clone()),
+            ("Endpoint", config.endpoint.as_ref().map(|e| e.to_string())),
+        ],
+    };
+    for (name, value) in values {
+        println!(
+            "{}{}: {}",
+            indent,
+            name,
+            value.unwrap_or("<not set>".to_string())
+        );
+    }
+}
+
+fn colored_state(state: VaultState) -> String {
+    match state {
+        VaultState::Active => format!("{}", state.to_string().green()),
+        VaultState::Paused => format!("{}", state.to_string().yellow()),
+        VaultState::Archived | VaultState::Decommissioned => {
+            format!("{}", state.to_string().red())
+        }
+    }
 }
 
 async fn rotate_key(
     rotate: KeyRotateCommand,
-    client: &Client,
+    keys: &KeySource,
+    seeds: &SeedSource,
+    storage: &Storage,
     wallet: &Wallet,
+    ledger: &Ledger,
+    budget: Option<&Budget>,
+    journal: &Journal,
     autonomi_config: &AutonomiClientConfig,
 ) -> anyhow::Result<()> {
     let (key, source) = match &rotate {
@@ -594,7 +2206,7 @@ async fn rotate_key(
         | KeyRotateCommand::Helm
         | KeyRotateCommand::All { .. }
         | KeyRotateCommand::Worker(WorkerKeyRotateCommand::WithSeed { .. }) => {
-            let ark_seed = read_seed().await?;
+            let ark_seed = seeds.ark_seed().await?;
             RotationDetails {
                 address: ark_seed.address().clone(),
                 key: (&rotate).into(),
@@ -602,7 +2214,7 @@ async fn rotate_key(
             }
         }
         KeyRotateCommand::Worker(WorkerKeyRotateCommand::WithHelm { address, .. }) => {
-            let helm_key = read_helm_key().await?;
+            let helm_key = keys.helm_key(address).await?;
             RotationDetails {
                 address: address.clone(),
                 key: (&rotate).into(),
@@ -627,18 +2239,74 @@ async fn rotate_key(
     println!("{}{}", INDENT, details.key);
     println!();
 
-    if !ask_proceed().await {
-        println!(" ❌ {}", "Aborting".red());
+    let ark = details.address.to_string();
+    let interrupted = journal
+        .pending()?
+        .into_iter()
+        .filter(|e| e.ark() == ark.as_str())
+        .collect::<Vec<_>>();
+    if !interrupted.is_empty() {
+        println!("{}", "INTERRUPTED OPERATIONS".yellow().bold());
+        for entry in &interrupted {
+            println!(
+                "{}{} (started {}, completed: {})",
+                INDENT,
+                entry.operation(),
+                entry.started(),
+                entry
+                    .steps()
+                    .map(|s| s.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+        }
+        println!(
+            "{}Interrupted rotations are completed instead of starting over",
+            INDENT
+        );
         println!();
-        return Ok(());
     }
 
     let core = Core::builder()
-        .client(client.clone())
+        .storage(storage.clone())
         .wallet(wallet.clone())
+        .maybe_budget(budget.cloned())
         .ark_address(details.address.clone())
+        .journal(journal.clone())
         .build();
 
+    let (progress, fut) = match (&details.key, &details.source) {
+        (RotatableKey::Worker(new_worker_key), RotationSource::HelmKey(helm_key)) => {
+            let (progress, fut) = core.quote_rotate_worker_key(helm_key, new_worker_key.clone());
+            (progress, fut.boxed())
+        }
+        (RotatableKey::Worker(new_worker_key), RotationSource::ArkSeed(seed)) => {
+            let (progress, fut) =
+                core.quote_rotate_worker_key_with_seed(seed, new_worker_key.clone());
+            (progress, fut.boxed())
+        }
+        (RotatableKey::Data, RotationSource::ArkSeed(seed)) => {
+            let (progress, fut) = core.quote_rotate_data_key(seed);
+            (progress, fut.boxed())
+        }
+        (RotatableKey::Helm, RotationSource::ArkSeed(seed)) => {
+            let (progress, fut) = core.quote_rotate_helm_key(seed);
+            (progress, fut.boxed())
+        }
+        (RotatableKey::All(new_worker_key), RotationSource::ArkSeed(seed)) => {
+            let (progress, fut) = core.quote_rotate_all_keys(seed, new_worker_key.clone());
+            (progress, fut.boxed())
+        }
+        _ => unreachable!("only ark seed can rotate data & helm keys"),
+    };
+    print_cost_estimate(progress, fut).await?;
+
+    if !ask_proceed().await {
+        println!(" ❌ {}", "Aborting".red());
+        println!();
+        return Ok(());
+    }
+
     let (mut progress, fut): (
         _,
         BoxFuture<ark_core::Result<Vec<(RotatableKey, String)>>>,
@@ -735,7 +2403,7 @@ async fn rotate_key(
 
         tokio::select! {
             res = &mut fut => {
-                break res.map_err(|(err, _)| err)?;
+                break settle(ledger, res)?;
             },
             _ = &mut progress => {
                 progress_view.update(&progress.latest());
@@ -830,22 +2498,26 @@ struct RotationDetails {
     source: RotationSource,
 }
 
-async fn create_ark(
+fn ark_creation_settings(
     name: String,
     description: Option<String>,
     public_worker_key: Option<PublicWorkerKey>,
-    client: &Client,
-    wallet: &Wallet,
-    autonomi_config: &AutonomiClientConfig,
-) -> anyhow::Result<()> {
-    let settings = ArkCreationSettings::builder()
+) -> ArkCreationSettings {
+    ArkCreationSettings::builder()
         .name(name)
         .maybe_description(description)
         .maybe_authorized_worker(public_worker_key)
-        .build();
+        .build()
+}
 
+fn ark_creation_preview(
+    action: &str,
+    settings: &ArkCreationSettings,
+    wallet: &Wallet,
+    autonomi_config: &AutonomiClientConfig,
+) {
     action_preview(
-        "Create New Ark",
+        action,
         Some(
             format!(
                 r#"{} {}
@@ -867,6 +2539,32 @@ async fn create_ark(
         Some(wallet),
         autonomi_config,
     );
+}
+
+async fn create_ark(
+    name: String,
+    description: Option<String>,
+    public_worker_key: Option<PublicWorkerKey>,
+    with_passphrase: bool,
+    language: MnemonicLanguage,
+    backup: Option<&PathBuf>,
+    storage: &Storage,
+    wallet: &Wallet,
+    ledger: &Ledger,
+    budget: Option<&Budget>,
+    autonomi_config: &AutonomiClientConfig,
+) -> anyhow::Result<()> {
+    let settings = ark_creation_settings(name, description, public_worker_key);
+    ark_creation_preview("Create New Ark", &settings, wallet, autonomi_config);
+    if let Some(path) = backup {
+        // checked upfront, the backup is written only after everything has been paid for
+        if path.exists() {
+            bail!("backup file [{}] already exists", path.display());
+        }
+    }
+
+    let (progress, fut) = Core::quote_create_ark(settings.clone(), &storage);
+    print_cost_estimate(progress, fut).await?;
 
     if !ask_proceed().await {
         println!(" ❌ {}", "Aborting".red());
@@ -874,16 +2572,183 @@ async fn create_ark(
         return Ok(());
     }
 
-    let (mut progress, fut) = Core::create_ark(settings, &client, &wallet);
+    const INDENT: &str = "    ";
+
+    let mut passphrase = if with_passphrase {
+        println!();
+        println!("{}", "PASSPHRASE".yellow().bold());
+        println!(
+            "{}• The passphrase is required IN ADDITION to the 24-word seed",
+            INDENT
+        );
+        println!(
+            "{}• A different passphrase leads to a different, empty Ark - typos are NOT detected",
+            INDENT
+        );
+        println!(
+            "{}• Without the exact passphrase, the Ark can NOT be recovered",
+            INDENT
+        );
+        println!();
+        Some(read_new_passphrase().await?)
+    } else {
+        None
+    };
+
+    // The seed is shown and confirmed BEFORE anything is paid for.
+    let (ark_seed, mnemonic) = ArkSeed::random_in(language, passphrase.as_ref());
+
+    println!();
+    println!("{}", "SECURITY WARNING".yellow().bold());
+    println!("{}You are about to view your ARK SEED", INDENT);
+    println!(
+        "{}• The ARK SEED is your MASTER KEY - it CANNOT be recovered",
+        INDENT
+    );
+    println!(
+        "{}• All other keys can be regenerated from this seed",
+        INDENT
+    );
+    println!(
+        "{}• Write down the 24-word seed and store it securely offline",
+        INDENT
+    );
+    println!("{}• Verify each word multiple times when copying", INDENT);
+    println!("{}• Ensure no one is looking at your screen", INDENT);
+    println!("{}• Clear or close your terminal once you are done", INDENT);
+
+    press_enter_key().await;
+
+    println!("{}", "ARK SEED (MASTER KEY)".red().bold());
+    println!("{}", "WRITE DOWN THESE 24 WORDS IN EXACT ORDER:".red());
+    println!();
+
+    print_word_grid(&mnemonic, INDENT);
+    println!();
+    println!(
+        "{}",
+        "VERIFY EACH WORD CAREFULLY - THIS SEED CANNOT BE RECOVERED".red()
+    );
+
+    press_enter_key().await;
+
+    println!(
+        "{}",
+        "Confirm you have written down your Ark Seed".cyan().bold()
+    );
+    while !verify_mnemonic(&mnemonic).await? {
+        println!("{}", "Incorrect words entered".red());
+        if !ask_confirmation("Try again (y/n)?").await {
+            println!(" ❌ {}", "Aborting".red());
+            println!();
+            return Ok(());
+        }
+    }
+    if passphrase.take().is_some() {
+        println!();
+        println!("{} {}", "Ark Address:".bold(), ark_seed.address());
+        println!(
+            "{}",
+            "Recovering with the Ark Seed and passphrase must lead to this exact address".dimmed()
+        );
+    }
+
+    let (progress, fut) = Core::create_ark(settings, &ark_seed, &storage, &wallet, budget.cloned());
+    let (ark_details, receipt) = run_ark_creation(progress, fut, ledger).await?;
+
+    println!();
+    println!("{} ✅", "Ark Creation Successful".green().bold());
+
+    if let Some(path) = backup {
+        if let Err(err) = write_paper_backup(path, &ark_details, Some(&mnemonic)) {
+            // the keys are still shown below, failing here would lose them
+            println!(
+                "{} {:#}",
+                "Writing the paper backup failed:".red().bold(),
+                err
+            );
+        }
+    }
+    drop(mnemonic);
+
+    print_ark_creation_details(&ark_details, &receipt).await;
+
+    Ok(())
+}
+
+async fn resume_ark_creation(
+    name: String,
+    description: Option<String>,
+    public_worker_key: Option<PublicWorkerKey>,
+    backup: Option<&PathBuf>,
+    seeds: &SeedSource,
+    storage: &Storage,
+    wallet: &Wallet,
+    ledger: &Ledger,
+    budget: Option<&Budget>,
+    autonomi_config: &AutonomiClientConfig,
+) -> anyhow::Result<()> {
+    let settings = ark_creation_settings(name, description, public_worker_key);
+    ark_creation_preview("Resume Ark Creation", &settings, wallet, autonomi_config);
+    if let Some(path) = backup {
+        // checked upfront, the backup is written only after everything has been paid for
+        if path.exists() {
+            bail!("backup file [{}] already exists", path.display());
+        }
+    }
+
+    println!("Provide the Ark Seed now");
+    let ark_seed = seeds.ark_seed().await?;
+    println!();
+    println!("{} {}", "Ark Address:".bold(), ark_seed.address());
+    println!();
+
+    let (progress, fut) = Core::quote_resume_ark_creation(settings.clone(), &ark_seed, &storage);
+    print_cost_estimate(progress, fut).await?;
+
+    if !ask_proceed().await {
+        println!(" ❌ {}", "Aborting".red());
+        println!();
+        return Ok(());
+    }
+
+    let (progress, fut) =
+        Core::resume_ark_creation(settings, &ark_seed, &storage, &wallet, budget.cloned());
+    let (ark_details, receipt) = run_ark_creation(progress, fut, ledger).await?;
+
+    println!();
+    println!("{} ✅", "Ark Creation Successful".green().bold());
+
+    if let Some(path) = backup {
+        // the seed words are not known when resuming
+        if let Err(err) = write_paper_backup(path, &ark_details, None) {
+            println!(
+                "{} {:#}",
+                "Writing the paper backup failed:".red().bold(),
+                err
+            );
+        }
+    }
+
+    print_ark_creation_details(&ark_details, &receipt).await;
+
+    Ok(())
+}
+
+async fn run_ark_creation(
+    mut progress: Progress,
+    fut: impl Future<Output = ark_core::Result<ArkCreationDetails>>,
+    ledger: &Ledger,
+) -> anyhow::Result<(ArkCreationDetails, Receipt)> {
     tokio::pin!(fut);
 
     let mut progress_view = ProgressView::new(&progress.latest(), Duration::from_millis(100));
-    let (ark_details, receipt) = loop {
+    let res = loop {
         let next_tick_in = progress_view.next_tick_in();
 
         tokio::select! {
             res = &mut fut => {
-                break res.map_err(|(err, _)| err)?;
+                break settle(ledger, res);
             },
             _ = &mut progress => {
                 progress_view.update(&progress.latest());
@@ -896,30 +2761,49 @@ async fn create_ark(
 
     progress_view.clear();
 
+    if res.is_err() {
+        println!();
+        println!("{}", "Ark Creation did not complete".red().bold());
+        println!("Use `ark resume` with your Ark Seed to finish it.");
+        println!("Parts that were already paid for will not be paid again.");
+        println!();
+    }
+    res
+}
+
+fn write_paper_backup(
+    path: &PathBuf,
+    ark_details: &ArkCreationDetails,
+    mnemonic: Option<&ConfidentialString>,
+) -> anyhow::Result<()> {
+    let svg = PaperBackup::builder()
+        .details(ark_details)
+        .maybe_mnemonic(mnemonic)
+        .build()
+        .to_svg()?;
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    std::io::Write::write_all(&mut options.open(path)?, svg.as_ref().as_bytes())?;
+    println!(
+        "{} {}",
+        "Paper backup written to".bold(),
+        path.display().to_string().italic()
+    );
+    println!(
+        "{}",
+        "Print it, store it securely offline and delete the file afterwards".dimmed()
+    );
+    Ok(())
+}
+
+async fn print_ark_creation_details(ark_details: &ArkCreationDetails, receipt: &Receipt) {
     const INDENT: &str = "    ";
 
-    println!();
-    println!("{} ✅", "Ark Creation Successful".green().bold());
-
     println!();
     println!("{}", "SECURITY WARNING".yellow().bold());
-    println!(
-        "{}You are about to view your ARK SEED and SECRET ARK KEYS",
-        INDENT
-    );
-    println!(
-        "{}• The ARK SEED is your MASTER KEY - it CANNOT be recovered",
-        INDENT
-    );
-    println!(
-        "{}• All other keys can be regenerated from this seed",
-        INDENT
-    );
-    println!(
-        "{}• Write down the 24-word seed and store it securely offline",
-        INDENT
-    );
-    println!("{}• Verify each word multiple times when copying", INDENT);
+    println!("{}You are about to view your SECRET ARK KEYS", INDENT);
     println!("{}• Ensure no one is looking at your screen", INDENT);
     println!("{}• Clear or close your terminal once you are done", INDENT);
 
@@ -961,28 +2845,6 @@ async fn create_ark(
     println!("{}{}", INDENT, receipt.total_cost().to_string().italic());
     println!();
 
-    println!("{}", "ARK SEED (MASTER KEY)".red().bold());
-    println!("{}", "WRITE DOWN THESE 24 WORDS IN EXACT ORDER:".red());
-    println!();
-
-    // Format the 24-word mnemonic in a grid (6 rows of 4 words)
-    let words: Vec<&str> = ark_details.mnemonic.as_ref().split_whitespace().collect();
-    for row in 0..6 {
-        let mut row_str = String::from(INDENT);
-        for col in 0..4 {
-            let idx = row * 4 + col;
-            if idx < words.len() {
-                row_str.push_str(&format!("{:<10} ", words[idx]));
-            }
-        }
-        println!("{}", row_str.red());
-    }
-    println!();
-    println!(
-        "{}",
-        "VERIFY EACH WORD CAREFULLY - THIS SEED CANNOT BE RECOVERED".red()
-    );
-
     println!();
     println!("{}", "SECRET ARK KEYS".cyan().bold());
     println!("{}These keys can be regenerated from your Ark Seed", INDENT);
@@ -1004,8 +2866,6 @@ async fn create_ark(
     println!();
     println!("{}", "All Good!".green().bold());
     println!();
-
-    Ok(())
 }
 
 fn action_preview(
@@ -1034,6 +2894,521 @@ fn action_preview(
     }
 }
 
+async fn print_cost_estimate(
+    mut progress: Progress,
+    fut: impl Future<Output = anyhow::Result<Receipt>>,
+) -> anyhow::Result<()> {
+    tokio::pin!(fut);
+
+    let mut progress_view = ProgressView::new(&progress.latest(), Duration::from_millis(100));
+    let receipt = loop {
+        let next_tick_in = progress_view.next_tick_in();
+
+        tokio::select! {
+            res = &mut fut => {
+                break res?;
+            },
+            _ = &mut progress => {
+                progress_view.update(&progress.latest());
+            },
+            _ = tokio::time::sleep(next_tick_in) => {
+                progress_view.tick();
+            }
+        }
+    };
+
+    progress_view.clear();
+
+    println!("{}", "ESTIMATED NETWORK COST:".cyan().bold());
+    println!("    {}", receipt.total_cost().to_string().italic());
+    println!();
+    Ok(())
+}
+
+async fn cached_storage(arguments: &Arguments) -> anyhow::Result<Storage> {
+    let network = arguments.autonomi_config.friendly();
+    let cache_dir = match &arguments.cache_dir {
+        Some(path) => path.clone(),
+        None => dirs::cache_dir()
+            .ok_or(anyhow!("unable to determine cache directory"))?
+            .join("ark")
+            .join("records")
+            .join(
+                network
+                    .chars()
+                    .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+                    .collect::<String>(),
+            ),
+    };
+    let key = CacheKey::load_or_create(
+        dirs::data_local_dir()
+            .ok_or(anyhow!("unable to determine local data directory"))?
+            .join("ark")
+            .join("cache.key"),
+    )?;
+
+    let inner = match (&arguments.autonomi_config).try_new_storage().await {
+        Ok(storage) => Some(storage),
+        Err(err) => {
+            eprintln!(
+                "{} network unavailable, only cached records can be read: {}",
+                "WARNING:".yellow().bold(),
+                err
+            );
+            None
+        }
+    };
+
+    Ok(Storage::from(
+        CachingStorage::builder()
+            .maybe_inner(inner)
+            .dir(cache_dir)
+            .key(key)
+            .build()?,
+    )
+    .with_network(network))
+}
+
+/// Prints the words in rows of 4, e.g. 6 rows for a 24-word mnemonic.
+fn print_word_grid(words: &ConfidentialString, indent: &str) {
+    let words: Vec<&str> = words.as_ref().split_whitespace().collect();
+    for row in words.chunks(4) {
+        let mut row_str = String::from(indent);
+        for word in row {
+            row_str.push_str(&format!("{:<10} ", word));
+        }
+        println!("{}", row_str.red());
+    }
+}
+
+async fn split_seed(
+    threshold: u8,
+    shares: u8,
+    seeds: &SeedSource,
+    autonomi_config: &AutonomiClientConfig,
+) -> anyhow::Result<()> {
+    const INDENT: &str = "    ";
+
+    action_preview(
+        "Split Ark Seed",
+        Some(
+            format!(
+                "{} {} of {} shares required for recovery",
+                "Shares:".bold(),
+                threshold,
+                shares
+            )
+            .as_str(),
+        ),
+        None,
+        autonomi_config,
+    );
+
+    println!("Provide the Ark Seed now");
+    let ark_seed = seeds.ark_seed().await?;
+    let shares = ark_seed.split(threshold, shares)?;
+    // never hand out shares that would not recover the seed
+    if ArkSeed::combine(&shares[..threshold as usize])?.address() != ark_seed.address() {
+        bail!("seed shares do not recover the Ark Seed");
+    }
+
+    println!();
+    println!("{} {}", "Ark Address:".bold(), ark_seed.address());
+    println!();
+    println!("{}", "SECURITY WARNING".yellow().bold());
+    println!(
+        "{}• Any {} shares recover the Ark Seed, fewer reveal nothing",
+        INDENT, threshold
+    );
+    println!(
+        "{}• Hand each share to a different person and store them separately",
+        INDENT
+    );
+    println!(
+        "{}• Write down all {} words of each share in exact order",
+        INDENT, SEED_SHARE_WORDS
+    );
+    println!(
+        "{}• Shares already include the passphrase, if the Ark Seed has one",
+        INDENT
+    );
+
+    for share in &shares {
+        press_enter_key().await;
+        println!(
+            "{}",
+            format!(
+                "SHARE #{} OF {} (SET {}, {} REQUIRED)",
+                share.index(),
+                shares.len(),
+                share.set_id(),
+                share.threshold()
+            )
+            .red()
+            .bold()
+        );
+        println!();
+        print_word_grid(&share.to_words(), INDENT);
+        println!();
+    }
+
+    press_enter_key().await;
+    println!(
+        "{}",
+        "Clear or close your terminal once all shares are written down".yellow()
+    );
+    println!(
+        "{}",
+        "Use `seed verify` to check the written shares".dimmed()
+    );
+    println!();
+    Ok(())
+}
+
+async fn verify_seed_shares(
+    ark_address: Option<&ArkAddress>,
+    autonomi_config: &AutonomiClientConfig,
+) -> anyhow::Result<()> {
+    action_preview(
+        "Verify Ark Seed Shares",
+        ark_address
+            .map(|a| format!("{} {}", "Expected Ark Address:".bold(), a))
+            .as_deref(),
+        None,
+        autonomi_config,
+    );
+
+    let mut shares: Vec<SeedShare> = vec![];
+    loop {
+        println!("Provide seed share #{} now", shares.len() + 1);
+        let share = read_seed_share().await?;
+        println!(
+            "✅ Share #{} of set {} is valid, {} required",
+            share.index(),
+            share.set_id(),
+            share.threshold()
+        );
+        let threshold = share.threshold() as usize;
+        shares.push(share);
+        if shares.len() >= threshold {
+            break;
+        }
+    }
+
+    let ark_seed = ArkSeed::combine(&shares)?;
+    println!();
+    println!("{} {}", "Recovered Ark Address:".bold(), ark_seed.address());
+    println!();
+    match ark_address {
+        Some(expected) if expected != ark_seed.address() => {
+            println!(" ❌ {}", "Shares do NOT recover the expected Ark".red());
+        }
+        Some(_) => {
+            println!("{} ✅", "Shares recover the expected Ark".green().bold());
+        }
+        None => {
+            println!(
+                "{}",
+                "Compare the address above with the Ark's address".yellow()
+            );
+        }
+    }
+    println!();
+    Ok(())
+}
+
+/// Records all payments in the ledger, regardless of the outcome.
+fn settle<T>(ledger: &Ledger, res: ark_core::Result<T>) -> anyhow::Result<(T, Receipt)> {
+    let (res, receipt) = match res {
+        Ok((value, receipt)) => (Ok(value), receipt),
+        Err((err, receipt)) => (Err(err), receipt),
+    };
+    if let Err(err) = ledger.record(&receipt) {
+        eprintln!(
+            "{} unable to record payments in ledger [{}]: {}",
+            "WARNING:".yellow().bold(),
+            ledger.path().display(),
+            err
+        );
+    }
+    if let Err(err) = &res {
+        if let Some(hint) = error_hint(err) {
+            eprintln!("{} {}", "HINT:".cyan().bold(), hint);
+        }
+    }
+    Ok((res?, receipt))
+}
+
+fn error_hint(err: &ark_core::Error) -> Option<&'static str> {
+    match err {
+        ark_core::Error::InsufficientFunds(_) => {
+            Some("top up the wallet belonging to the given secret key and try again")
+        }
+        ark_core::Error::BudgetExceeded(_) => {
+            Some("the configured spending budget would be exceeded")
+        }
+        ark_core::Error::VaultNotWritable { .. } => {
+            Some("activate the vault first if it should accept uploads again")
+        }
+        ark_core::Error::VaultUnderLegalHold { .. } => {
+            Some("releasing the legal hold requires the Ark Seed")
+        }
+        err if err.is_transient() => Some("this is likely temporary, try again"),
+        _ => None,
+    }
+}
+
+fn ledger_summary(ledger: &Ledger) -> anyhow::Result<()> {
+    const INDENT: &str = "    ";
+
+    let summary = ledger.summary()?;
+    if summary.is_empty() {
+        println!("No payments recorded in [{}]", ledger.path().display());
+        return Ok(());
+    }
+
+    println!("{}", "SPEND PER ARK".cyan().bold());
+    let mut current_ark = None;
+    for entry in &summary {
+        if current_ark != Some(&entry.ark) {
+            current_ark = Some(&entry.ark);
+            let ark_total = summary
+                .iter()
+                .filter(|s| s.ark == entry.ark)
+                .fold(AttoTokens::zero(), |acc, s| {
+                    acc.checked_add(s.total).expect("attos not to overflow")
+                });
+            println!();
+            println!("{}{}", INDENT, entry.ark.bold());
+            println!("{}{} {}", INDENT, "TOTAL:".bold(), ark_total);
+        }
+        println!(
+            "{}{}{}: {} ({} payments)",
+            INDENT,
+            INDENT,
+            entry.vault.as_ref().map(|v| v.as_str()).unwrap_or("<ark>"),
+            entry.total,
+            entry.payments
+        );
+    }
+    println!();
+    Ok(())
+}
+
+fn ledger_export(
+    ledger: &Ledger,
+    format: ExportFormat,
+    output: Option<&PathBuf>,
+) -> anyhow::Result<()> {
+    let writer: Box<dyn std::io::Write> = match output {
+        Some(path) => Box::new(std::fs::File::create(path)?),
+        None => Box::new(std::io::stdout().lock()),
+    };
+    match format {
+        ExportFormat::Csv => ledger.export_csv(writer),
+        ExportFormat::Json => ledger.export_json(writer),
+    }
+}
+
+/// Where secret keys are taken from.
+enum KeySource {
+    Prompt,
+    /// The keystore is only opened once a key is actually needed
+    Keystore {
+        location: KeystoreLocation,
+        name: String,
+        keystore: OnceCell<Keystore>,
+    },
+    Provider(SecretProvider),
+}
+
+impl KeySource {
+    async fn helm_key(&self, ark_address: &ArkAddress) -> anyhow::Result<HelmKey> {
+        match self {
+            Self::Prompt => {
+                println!(" Provide the {} now ", "HELM KEY".bold());
+                println!();
+                read_helm_key().await
+            }
+            Self::Keystore {
+                location,
+                name,
+                keystore,
+            } => keystore
+                .get_or_try_init(|| open_keystore(location))
+                .await?
+                .get(ark_address, name),
+            Self::Provider(provider) => provider.resolve_key().await,
+        }
+    }
+
+    async fn ark_key(&self, ark_address: &ArkAddress) -> anyhow::Result<ArkAccessor> {
+        match self {
+            Self::Prompt => read_ark_key().await,
+            Self::Keystore {
+                location,
+                name,
+                keystore,
+            } => keystore
+                .get_or_try_init(|| open_keystore(location))
+                .await?
+                .get_accessor(ark_address, name),
+            Self::Provider(provider) => provider.resolve_key().await,
+        }
+    }
+}
+
+/// Where the Ark Seed is taken from, asked for unless a provider is configured.
+struct SeedSource {
+    seed: Option<SecretProvider>,
+    passphrase: Option<SecretProvider>,
+}
+
+impl SeedSource {
+    async fn ark_seed(&self) -> anyhow::Result<ArkSeed> {
+        let Some(seed) = &self.seed else {
+            return read_seed().await;
+        };
+        let passphrase = match &self.passphrase {
+            Some(provider) => Some(provider.resolve().await?),
+            None => None,
+        };
+        seed.resolve_ark_seed(passphrase.as_ref()).await
+    }
+}
+
+/// Where the keystore is located, its passphrase is asked for unless a provider is configured.
+#[derive(Clone)]
+struct KeystoreLocation {
+    path: PathBuf,
+    passphrase: Option<SecretProvider>,
+}
+
+fn keystore_location(arguments: &Arguments) -> anyhow::Result<KeystoreLocation> {
+    Ok(KeystoreLocation {
+        path: match &arguments.keystore {
+            Some(path) => path.clone(),
+            None => dirs::data_local_dir()
+                .ok_or(anyhow!("unable to determine local data directory"))?
+                .join("ark")
+                .join("keystore.age"),
+        },
+        passphrase: arguments.keystore_passphrase_from.clone(),
+    })
+}
+
+async fn open_keystore(location: &KeystoreLocation) -> anyhow::Result<Keystore> {
+    if !location.path.exists() {
+        bail!(
+            "keystore [{}] does not exist, use `keystore add` to create it",
+            location.path.display()
+        );
+    }
+    let passphrase = match &location.passphrase {
+        Some(provider) => provider.resolve().await?,
+        None => read_keystore_passphrase().await?,
+    };
+    Keystore::open(&location.path, passphrase)
+}
+
+async fn open_or_create_keystore(location: &KeystoreLocation) -> anyhow::Result<Keystore> {
+    if location.path.exists() {
+        return open_keystore(location).await;
+    }
+    println!(
+        "{} {}",
+        "Creating new keystore at".bold(),
+        location.path.display().to_string().italic()
+    );
+    let passphrase = match &location.passphrase {
+        Some(provider) => provider.resolve().await?,
+        None => read_new_passphrase().await?,
+    };
+    Keystore::create(&location.path, passphrase)
+}
+
+async fn keystore_add(
+    location: &KeystoreLocation,
+    ark_address: &ArkAddress,
+    name: &str,
+) -> anyhow::Result<()> {
+    let mut keystore = open_or_create_keystore(location).await?;
+
+    println!();
+    println!("Provide the {} now", "SECRET KEY".bold());
+    let secret = read_secret().await?;
+    let kind = keystore.import(ark_address, name, &secret)?;
+
+    println!();
+    println!(
+        "✅ {} [{}] {}",
+        kind.to_string().green().bold(),
+        name,
+        "added to the keystore".green().bold()
+    );
+    println!();
+    Ok(())
+}
+
+async fn keystore_list(
+    location: &KeystoreLocation,
+    ark_address: Option<&ArkAddress>,
+) -> anyhow::Result<()> {
+    let keystore = open_keystore(location).await?;
+    let entries = keystore
+        .entries()
+        .into_iter()
+        .filter(|e| ark_address.map(|a| &e.ark_address == a).unwrap_or(true))
+        .collect::<Vec<_>>();
+
+    const INDENT: &str = "    ";
+
+    println!();
+    if entries.is_empty() {
+        println!("No keys stored in [{}]", keystore.path().display());
+        println!();
+        return Ok(());
+    }
+
+    println!("{}", "STORED KEYS".cyan().bold());
+    let mut current_ark = None;
+    for entry in &entries {
+        if current_ark != Some(&entry.ark_address) {
+            current_ark = Some(&entry.ark_address);
+            println!();
+            println!("{}{}", INDENT, entry.ark_address.to_string().bold());
+        }
+        println!(
+            "{}{}{}: {} (added {})",
+            INDENT, INDENT, entry.name, entry.kind, entry.added
+        );
+    }
+    println!();
+    Ok(())
+}
+
+async fn keystore_remove(
+    location: &KeystoreLocation,
+    ark_address: &ArkAddress,
+    name: &str,
+) -> anyhow::Result<()> {
+    let mut keystore = open_keystore(location).await?;
+    if !ask_confirmation(format!("Remove key [{}] permanently (y/n)?", name).as_str()).await {
+        println!(" ❌ {}", "Aborting".red());
+        println!();
+        return Ok(());
+    }
+    let entry = keystore.remove(ark_address, name)?;
+    println!(
+        "✅ {} [{}] {}",
+        entry.kind.to_string().green().bold(),
+        entry.name,
+        "removed from the keystore".green().bold()
+    );
+    println!();
+    Ok(())
+}
+
 async fn ask_proceed() -> bool {
     ask_confirmation("Do you want to proceed (y/n)?").await
 }
@@ -1,5 +1,8 @@
-use anyhow::bail;
-use ark_core::{ArkAccessor, ArkSeed, DataKey, HelmKey, ProgressReport, ProgressStatus, WorkerKey};
+use anyhow::{anyhow, bail};
+use ark_core::{
+    ArkAccessor, ArkSeed, ConfidentialString, HelmKey, ProgressReport, ProgressStatus,
+    SEED_SHARE_WORDS, SeedShare,
+};
 use colored::Colorize;
 use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
 use once_cell::sync::Lazy;
@@ -8,6 +11,7 @@ use std::fmt::{Debug, Formatter};
 use std::str::FromStr;
 use std::time::{Duration, SystemTime};
 use tokio::io::{AsyncBufReadExt, BufReader};
+use unicode_normalization::UnicodeNormalization;
 use zeroize::{Zeroize, ZeroizeOnDrop};
 
 static WAITING_STYLE: Lazy<ProgressStyle> = Lazy::new(|| {
@@ -284,13 +288,7 @@ pub async fn read_seed() -> anyhow::Result<ArkSeed> {
         // Use spawn_blocking to run the synchronous rpassword
         let input = tokio::task::spawn_blocking(|| rpassword::read_password()).await??;
 
-        seed_words.as_mut().extend(
-            input
-                .trim()
-                .split_whitespace()
-                .filter(|s| !s.is_empty())
-                .map(|s| s.to_string()),
-        );
+        seed_words.as_mut().extend(split_words(input.as_str()));
 
         if seed_words.as_ref().len() == 24 {
             break;
@@ -298,25 +296,148 @@ pub async fn read_seed() -> anyhow::Result<ArkSeed> {
             bail!("invalid seed, exactly 24 words are expected");
         }
     }
-    Ok(ArkSeed::try_from_mnemonic(seed_words.as_ref().join(" "))?)
+    println!(
+        "Enter the {} (leave empty if the Ark Seed has none):",
+        "PASSPHRASE".bold()
+    );
+    let passphrase = read_passphrase().await?;
+    Ok(ArkSeed::try_from_mnemonic(
+        seed_words.as_ref().join(" "),
+        passphrase.as_ref(),
+    )?)
 }
 
-pub async fn read_helm_key() -> anyhow::Result<HelmKey> {
-    let input = tokio::task::spawn_blocking(|| rpassword::read_password()).await??;
-    Ok(HelmKey::from_str(input.trim())?)
+/// Splits the input into mnemonic words, normalized to NFKD.
+///
+/// Words are separated by any whitespace, including ideographic spaces.
+/// Every word of the Chinese word lists is a single character, so these are
+/// also accepted without any separator.
+fn split_words(input: &str) -> impl Iterator<Item = String> {
+    input.split_whitespace().flat_map(|token| {
+        let token = token.nfkd().collect::<String>();
+        if token.chars().count() > 1 && token.chars().all(is_han) {
+            token.chars().map(|c| c.to_string()).collect::<Vec<_>>()
+        } else {
+            vec![token]
+        }
+    })
 }
 
-pub async fn read_ark_key() -> anyhow::Result<ArkAccessor> {
-    let input = tokio::task::spawn_blocking(|| rpassword::read_password()).await??;
-    let input = input.trim();
-    if let Ok(key) = HelmKey::from_str(input) {
-        return Ok(key.into());
+fn is_han(c: char) -> bool {
+    matches!(c, '\u{4E00}'..='\u{9FFF}' | '\u{3400}'..='\u{4DBF}')
+}
+
+/// Reads a single share of a split Ark Seed, see [`ArkSeed::split`].
+pub async fn read_seed_share() -> anyhow::Result<SeedShare> {
+    let mut words = ConfidentialStrings::from(Vec::with_capacity(SEED_SHARE_WORDS));
+    loop {
+        let mut input = tokio::task::spawn_blocking(|| rpassword::read_password()).await??;
+
+        words
+            .as_mut()
+            .extend(input.split_whitespace().map(|s| s.to_string()));
+        input.zeroize();
+
+        if words.as_ref().len() == SEED_SHARE_WORDS {
+            break;
+        } else if words.as_ref().len() > SEED_SHARE_WORDS {
+            bail!(
+                "invalid seed share, exactly {} words are expected",
+                SEED_SHARE_WORDS
+            );
+        }
+    }
+    let mut phrase = words.as_ref().join(" ");
+    let share = SeedShare::from_str(phrase.as_str());
+    phrase.zeroize();
+    share
+}
+
+async fn read_passphrase() -> anyhow::Result<Option<ConfidentialString>> {
+    let input = ConfidentialString::from(
+        tokio::task::spawn_blocking(|| rpassword::read_password()).await??,
+    );
+    // passphrases are used verbatim, surrounding whitespace is significant
+    if input.as_ref().is_empty() {
+        return Ok(None);
     }
-    if let Ok(key) = DataKey::from_str(input) {
-        return Ok(key.into());
+    Ok(Some(input))
+}
+
+/// Asks for a new passphrase twice, returning it only if both entries match.
+pub async fn read_new_passphrase() -> anyhow::Result<ConfidentialString> {
+    loop {
+        println!("Enter the new {}:", "PASSPHRASE".bold());
+        let Some(passphrase) = read_passphrase().await? else {
+            println!("{}", "The passphrase must not be empty".red());
+            continue;
+        };
+        println!("Repeat the {}:", "PASSPHRASE".bold());
+        let repeated = read_passphrase().await?;
+        if repeated.as_ref().map(|p| p.as_ref()) == Some(passphrase.as_ref()) {
+            return Ok(passphrase);
+        }
+        println!("{}", "Passphrases do not match, please try again".red());
     }
-    if let Ok(key) = WorkerKey::from_str(input) {
-        return Ok(key.into());
+}
+
+/// Asks for a few randomly chosen words of the given mnemonic.
+///
+/// Returns `true` only if all of them were entered correctly.
+pub async fn verify_mnemonic(mnemonic: &ConfidentialString) -> anyhow::Result<bool> {
+    let words = ConfidentialStrings::from(
+        mnemonic
+            .as_ref()
+            .split_whitespace()
+            .map(|s| s.to_string())
+            .collect::<Vec<_>>(),
+    );
+    let mut positions =
+        rand::seq::index::sample(&mut rand::rng(), words.as_ref().len(), 3).into_vec();
+    positions.sort();
+
+    let mut correct = true;
+    for idx in positions {
+        println!("Enter word #{}:", idx + 1);
+        let mut input = tokio::task::spawn_blocking(|| rpassword::read_password()).await??;
+        let mut entered = input.trim().nfkd().collect::<String>();
+        if entered != words.as_ref()[idx].nfkd().collect::<String>() {
+            correct = false;
+        }
+        input.zeroize();
+        entered.zeroize();
     }
-    bail!("no valid secret given");
+    Ok(correct)
+}
+
+/// Reads the passphrase of an existing keystore.
+pub async fn read_keystore_passphrase() -> anyhow::Result<ConfidentialString> {
+    println!("Enter the {}:", "KEYSTORE PASSPHRASE".bold());
+    read_passphrase()
+        .await?
+        .ok_or(anyhow!("the keystore passphrase must not be empty"))
+}
+
+/// Reads any bech32 encoded secret key, without validating it.
+pub async fn read_secret() -> anyhow::Result<ConfidentialString> {
+    read_key_input().await
+}
+
+pub async fn read_helm_key() -> anyhow::Result<HelmKey> {
+    HelmKey::from_str(read_key_input().await?.as_ref())
+}
+
+pub async fn read_ark_key() -> anyhow::Result<ArkAccessor> {
+    ArkAccessor::from_str(read_key_input().await?.as_ref())
+}
+
+/// Reads a secret key as typed in, or as scanned from a QR code of a paper backup,
+/// e.g. with a scanner acting as keyboard.
+///
+/// QR codes hold the key in upper case, which is accepted just like the usual lower case.
+async fn read_key_input() -> anyhow::Result<ConfidentialString> {
+    let input = ConfidentialString::from(
+        tokio::task::spawn_blocking(|| rpassword::read_password()).await??,
+    );
+    Ok(ConfidentialString::from(input.as_ref().trim().to_string()))
 }
@@ -1,15 +1,19 @@
-use crate::data_key::OwnedDataRegister;
-use crate::helm_key::OwnedHelmRegister;
-use crate::manifest::{Manifest, ManifestEncryptor};
+use crate::data_key::{DataRegister, OwnedDataRegister};
+use crate::helm_key::{HelmRegister, OwnedHelmRegister};
+use crate::manifest::{LabelValue, Manifest, ManifestEncryptor};
 use crate::progress::Task;
 use crate::worker_key::{EitherWorkerKey, WorkerKey};
 use crate::{
-    ArkAddress, ArkSeed, AutonomiClient, ConfidentialString, Core, EvmWallet, Progress,
-    PublicWorkerKey, Receipt, with_receipt,
+    ArkAddress, ArkSeed, Budget, Core, EvmWallet, Progress, PublicWorkerKey, ReadOnlyCore, Receipt,
+    Storage, with_quote, with_receipt,
 };
 use crate::{DataKey, HelmKey};
+use anyhow::bail;
 use blsttc::SecretKey;
 use bon::Builder;
+use chrono::Utc;
+use std::collections::{BTreeMap, BTreeSet};
+use std::str::FromStr;
 use zeroize::{Zeroize, ZeroizeOnDrop};
 
 pub enum ArkAccessor {
@@ -30,6 +34,26 @@ impl ArkAccessor {
     }
 }
 
+/// Parses any bech32 encoded Ark key, as shown in the ark creation details
+/// or scanned from the QR codes of a [`crate::PaperBackup`].
+impl FromStr for ArkAccessor {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if let Ok(key) = HelmKey::from_str(s) {
+            return Ok(key.into());
+        }
+        if let Ok(key) = DataKey::from_str(s) {
+            return Ok(key.into());
+        }
+        if let Ok(key) = WorkerKey::from_str(s) {
+            return Ok(key.into());
+        }
+        bail!("no valid secret given");
+    }
+}
+
 impl From<ArkSeed> for ArkAccessor {
     fn from(value: ArkSeed) -> Self {
         Self::ArkSeed(value)
@@ -54,60 +78,145 @@ impl From<WorkerKey> for ArkAccessor {
     }
 }
 
+/// Quotes the complete creation and checks its total against the `budget` (if any)
+/// before anything is paid.
+async fn check_budget(
+    settings: &ArkCreationSettings,
+    ark_seed: &ArkSeed,
+    resume: bool,
+    storage: &Storage,
+    budget: &Option<Budget>,
+    receipt: &mut Receipt,
+    task: &mut Task,
+) -> anyhow::Result<()> {
+    let Some(budget) = budget else {
+        return Ok(());
+    };
+    let mut check = task.child(1, "Check Budget".to_string());
+    check.start();
+    let mut quote = Receipt::new("Budget Check");
+    create(
+        settings.clone(),
+        ark_seed,
+        resume,
+        storage,
+        None,
+        None,
+        &mut quote,
+        check.child(1, None),
+    )
+    .await?;
+    budget.check(receipt, quote.total_cost())?;
+    check.complete();
+    Ok(())
+}
+
+/// Creates the Ark or, without a `wallet`, quotes its creation.
 async fn create(
     mut settings: ArkCreationSettings,
-    client: &AutonomiClient,
-    wallet: &EvmWallet,
+    ark_seed: &ArkSeed,
+    resume: bool,
+    storage: &Storage,
+    wallet: Option<&EvmWallet>,
+    budget: Option<Budget>,
     receipt: &mut Receipt,
     mut task: Task,
 ) -> anyhow::Result<ArkCreationDetails> {
     task.start();
 
-    let mut seed_task = task.child(2, "Ark Seed".to_string());
+    let mut seed_task = task.child(1, "Ark Seed".to_string());
     let mut helm_key_task = task.child(2, "Helm Key".to_string());
     let mut data_key_task = task.child(3, "Data Key".to_string());
     let mut manifest_task = task.child(1, "Manifest".to_string());
 
     seed_task.start();
-    let (ark_seed, mnemonic) = ArkSeed::random();
-    seed_task += 1;
-    let core = Core::builder()
-        .ark_address(ark_seed.address().clone())
-        .client(client.clone())
-        .wallet(wallet.clone())
-        .build();
+    let ark_address = ark_seed.address();
+    let core = match wallet {
+        Some(wallet) => Core::builder()
+            .ark_address(ark_address.clone())
+            .storage(storage.clone())
+            .wallet(wallet.clone())
+            .maybe_budget(budget)
+            .build(),
+        None => ReadOnlyCore::builder()
+            .ark_address(ark_address.clone())
+            .storage(storage.clone())
+            .build()
+            .quoting(),
+    };
+    let helm_register = core
+        .get_register(&HelmRegister::derive_address(ark_address))
+        .await?;
+    if helm_register.is_some() && !resume {
+        bail!("ark [{}] already exists", ark_address);
+    }
     seed_task += 1;
     seed_task.complete();
 
+    // every step below is skipped if a previous attempt already completed it
     helm_key_task.start();
-    let helm_register = OwnedHelmRegister::new_derived(&ark_seed);
-    let helm_key = ark_seed.helm_key(helm_register.value());
-    helm_key_task += 1;
-    core.create_register(helm_register, receipt).await?;
+    let helm_key = match helm_register {
+        Some(helm_register) => {
+            helm_key_task += 1;
+            ark_seed.helm_key(helm_register.value())
+        }
+        None => {
+            let helm_register = OwnedHelmRegister::new_derived(ark_seed);
+            let helm_key = ark_seed.helm_key(helm_register.value());
+            helm_key_task += 1;
+            core.create_register(helm_register, receipt).await?;
+            helm_key
+        }
+    };
     helm_key_task += 1;
     helm_key_task.complete();
 
+    if core
+        .get_scratchpad(&helm_key.public_key().manifest())
+        .await?
+        .is_some()
+    {
+        bail!("ark [{}] is already fully created", ark_address);
+    }
+
     data_key_task.start();
-    let data_register = OwnedDataRegister::new_derived(&ark_seed);
-    let data_key = ark_seed.data_key(data_register.value());
-    data_key_task += 1;
-    core.create_register(data_register, receipt).await?;
+    let data_key = match core
+        .get_register(&DataRegister::derive_address(ark_address))
+        .await?
+    {
+        Some(data_register) => {
+            data_key_task += 1;
+            ark_seed.data_key(data_register.value())
+        }
+        None => {
+            let data_register = OwnedDataRegister::new_derived(ark_seed);
+            let data_key = ark_seed.data_key(data_register.value());
+            data_key_task += 1;
+            core.create_register(data_register, receipt).await?;
+            data_key
+        }
+    };
     data_key_task += 1;
 
-    core.create_encrypted_scratchpad(
-        ark_seed.data_keyring(
-            data_key
-                .public_key()
-                .encrypt_data_keyring(&core.derive_data_keyring(&ark_seed).await?)?,
-        ),
-        receipt,
-    )
-    .await?;
+    if core
+        .get_scratchpad(&ark_address.data_keyring())
+        .await?
+        .is_none()
+    {
+        core.create_encrypted_scratchpad(
+            ark_seed.data_keyring(
+                data_key
+                    .public_key()
+                    .encrypt_data_keyring(&core.derive_data_keyring(ark_seed).await?)?,
+            ),
+            receipt,
+        )
+        .await?;
+    }
     data_key_task += 1;
     data_key_task.complete();
 
     manifest_task.start();
-    let ark_address = ark_seed.address();
 
     let worker_key: EitherWorkerKey = settings
         .authorized_worker
@@ -135,7 +244,6 @@ async fn create(
 
     Ok(ArkCreationDetails {
         address: ark_address.clone(),
-        mnemonic,
         helm_key,
         data_key,
         worker_key,
@@ -165,11 +273,58 @@ impl ArkCreationSettings {
     }
 }
 
+/// Changes to an Ark's name, description & labels. Anything not set is left as is.
+#[derive(Builder, Clone, Debug, Default)]
+pub struct ArkMetadataUpdate {
+    #[builder(into)]
+    pub(crate) name: Option<String>,
+    /// `Some(None)` removes the description
+    pub(crate) description: Option<Option<String>>,
+    /// Labels to add or overwrite
+    #[builder(default)]
+    pub(crate) labels: BTreeMap<String, LabelValue>,
+    #[builder(default)]
+    pub(crate) remove_labels: BTreeSet<String>,
+}
+
+impl ArkMetadataUpdate {
+    pub fn is_empty(&self) -> bool {
+        self.name.is_none()
+            && self.description.is_none()
+            && self.labels.is_empty()
+            && self.remove_labels.is_empty()
+    }
+
+    fn apply(&self, manifest: &mut Manifest) -> anyhow::Result<()> {
+        if let Some(name) = &self.name {
+            let name = name.trim();
+            if name.is_empty() {
+                bail!("ark name must not be empty");
+            }
+            manifest.name = name.to_string();
+        }
+        if let Some(description) = &self.description {
+            manifest.description = description.clone();
+        }
+        for key in &self.remove_labels {
+            manifest.labels.remove(key.trim());
+        }
+        for (key, value) in &self.labels {
+            let key = key.trim();
+            if key.is_empty() {
+                bail!("label key must not be empty");
+            }
+            manifest.labels.insert(key.to_string(), value.clone());
+        }
+        manifest.last_modified = Utc::now();
+        Ok(())
+    }
+}
+
 #[derive(Zeroize, ZeroizeOnDrop)]
 pub struct ArkCreationDetails {
     #[zeroize(skip)]
     pub address: ArkAddress,
-    pub mnemonic: ConfidentialString,
     pub helm_key: HelmKey,
     pub data_key: DataKey,
     pub worker_key: EitherWorkerKey,
@@ -177,37 +332,403 @@ pub struct ArkCreationDetails {
     pub manifest: Manifest,
 }
 
+impl ReadOnlyCore {
+    pub fn ark_details(
+        &self,
+        ark_accessor: &ArkAccessor,
+    ) -> (
+        Progress,
+        impl Future<Output = crate::Result<Manifest>> + Send,
+    ) {
+        let (progress, mut task) = Progress::new(1, "Retrieve Current Manifest".to_string());
+
+        let fut = with_receipt("Ark Details", async move |receipt| {
+            task.start();
+            self.get_manifest(ark_accessor).await
+        });
+
+        (progress, fut)
+    }
+}
+
 impl Core {
-    pub fn create_ark(
+    /// Creates a new Ark from the given `ark_seed`.
+    ///
+    /// The seed is generated by the caller, see [`ArkSeed::random`], so the mnemonic
+    /// can be safely stored **before** anything is paid for. Should the creation fail
+    /// midway, it can be completed with [`Core::resume_ark_creation`].
+    pub fn create_ark<'a>(
         setting: ArkCreationSettings,
-        client: &AutonomiClient,
-        wallet: &EvmWallet,
+        ark_seed: &'a ArkSeed,
+        storage: &'a Storage,
+        wallet: &'a EvmWallet,
+        budget: Option<Budget>,
     ) -> (
         Progress,
-        impl Future<Output = crate::Result<ArkCreationDetails>> + Send,
+        impl Future<Output = crate::Result<ArkCreationDetails>> + Send + 'a,
     ) {
-        let (progress, task) = Progress::new(1, "Ark Creation".to_string());
+        let (progress, mut task) = Progress::new(1, "Ark Creation".to_string());
 
-        let fut =
-            with_receipt(async move |receipt| create(setting, client, wallet, receipt, task).await);
+        let fut = with_receipt("Ark Creation", async move |receipt| {
+            check_budget(
+                &setting, ark_seed, false, storage, &budget, receipt, &mut task,
+            )
+            .await?;
+            create(
+                setting,
+                ark_seed,
+                false,
+                storage,
+                Some(wallet),
+                budget,
+                receipt,
+                task,
+            )
+            .await
+        });
 
         (progress, fut)
     }
 
-    pub fn ark_details(
-        &self,
-        ark_accessor: &ArkAccessor,
+    /// Completes a previously interrupted [`Core::create_ark`].
+    ///
+    /// Detects which of the helm register, data register, data keyring and manifest
+    /// already exist and only creates the missing ones.
+    pub fn resume_ark_creation<'a>(
+        setting: ArkCreationSettings,
+        ark_seed: &'a ArkSeed,
+        storage: &'a Storage,
+        wallet: &'a EvmWallet,
+        budget: Option<Budget>,
     ) -> (
         Progress,
-        impl Future<Output = crate::Result<Manifest>> + Send,
+        impl Future<Output = crate::Result<ArkCreationDetails>> + Send + 'a,
     ) {
-        let (progress, mut task) = Progress::new(1, "Retrieve Current Manifest".to_string());
+        let (progress, mut task) = Progress::new(1, "Resume Ark Creation".to_string());
 
-        let fut = with_receipt(async move |receipt| {
+        let fut = with_receipt("Resume Ark Creation", async move |receipt| {
+            check_budget(
+                &setting, ark_seed, true, storage, &budget, receipt, &mut task,
+            )
+            .await?;
+            create(
+                setting,
+                ark_seed,
+                true,
+                storage,
+                Some(wallet),
+                budget,
+                receipt,
+                task,
+            )
+            .await
+        });
+
+        (progress, fut)
+    }
+
+    /// Estimates the cost of [`Core::create_ark`] without spending anything.
+    pub fn quote_create_ark(
+        setting: ArkCreationSettings,
+        storage: &Storage,
+    ) -> (
+        Progress,
+        impl Future<Output = anyhow::Result<Receipt>> + Send,
+    ) {
+        let (progress, task) = Progress::new(1, "Ark Creation Quote".to_string());
+
+        let fut = with_quote("Ark Creation", async move |receipt| {
+            let (ark_seed, _) = ArkSeed::random(None);
+            create(
+                setting, &ark_seed, false, storage, None, None, receipt, task,
+            )
+            .await
+        });
+
+        (progress, fut)
+    }
+
+    /// Estimates the cost of [`Core::resume_ark_creation`] without spending anything.
+    pub fn quote_resume_ark_creation<'a>(
+        setting: ArkCreationSettings,
+        ark_seed: &'a ArkSeed,
+        storage: &'a Storage,
+    ) -> (
+        Progress,
+        impl Future<Output = anyhow::Result<Receipt>> + Send + 'a,
+    ) {
+        let (progress, task) = Progress::new(1, "Resume Ark Creation Quote".to_string());
+
+        let fut = with_quote("Resume Ark Creation", async move |receipt| {
+            create(setting, ark_seed, true, storage, None, None, receipt, task).await
+        });
+
+        (progress, fut)
+    }
+
+    /// Changes the Ark's name, description and/or labels.
+    ///
+    /// Requires the Helm Key. Concurrent changes to the manifest are preserved,
+    /// the update is re-applied on top of them. Returns the updated manifest.
+    pub fn update_ark_metadata<'a>(
+        &'a self,
+        update: ArkMetadataUpdate,
+        helm_key: &'a HelmKey,
+    ) -> (
+        Progress,
+        impl Future<Output = crate::Result<Manifest>> + Send + 'a,
+    ) {
+        let (progress, mut task) = Progress::new(1, "Update Ark Metadata".to_string());
+
+        let fut = with_receipt("Update Ark Metadata", async move |receipt| {
             task.start();
-            self.get_manifest(ark_accessor).await
+            let mut verify_helm = task.child(1, "Verify Helm Key".to_string());
+            let mut update_manifest = task.child(1, "Updating Manifest".to_string());
+
+            verify_helm.start();
+            self.verify_helm_key(helm_key).await?;
+            verify_helm.complete();
+
+            update_manifest.start();
+            if !update.is_empty() {
+                self.modify_manifest(helm_key, receipt, |manifest| update.apply(manifest))
+                    .await?;
+            }
+            update_manifest.complete();
+
+            let manifest = self.get_manifest(helm_key).await?;
+            task.complete();
+            Ok(manifest)
         });
 
         (progress, fut)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MemoryStorage;
+    use autonomi::AttoTokens;
+
+    fn settings() -> ArkCreationSettings {
+        ArkCreationSettings::builder().name("Test Ark").build()
+    }
+
+    /// Memory storage never charges the wallet, it only needs to exist.
+    fn wallet() -> EvmWallet {
+        EvmWallet::new_with_random_wallet(autonomi::Network::ArbitrumSepoliaTest)
+    }
+
+    fn priced_storage() -> Storage {
+        MemoryStorage::new()
+            .with_price(AttoTokens::from_u64(10))
+            .into()
+    }
+
+    fn core(storage: &Storage, ark_seed: &ArkSeed) -> Core {
+        Core::builder()
+            .storage(storage.clone())
+            .wallet(wallet())
+            .ark_address(ark_seed.address().clone())
+            .build()
+    }
+
+    #[tokio::test]
+    async fn refuse_creating_existing_ark() {
+        let storage: Storage = MemoryStorage::new().into();
+        let (ark_seed, _) = ArkSeed::random(None);
+        let Ok((details, _)) = Core::create_ark(settings(), &ark_seed, &storage, &wallet(), None)
+            .1
+            .await
+        else {
+            panic!("ark creation failed");
+        };
+
+        let Ok((manifest, _)) = core(&storage, &ark_seed)
+            .ark_details(&details.helm_key.clone().into())
+            .1
+            .await
+        else {
+            panic!("ark not readable");
+        };
+        assert_eq!(&manifest.ark_address, ark_seed.address());
+        assert_eq!(manifest.name, "Test Ark");
+        assert_eq!(&manifest.authorized_worker, details.worker_key.public_key());
+
+        let Err((err, receipt)) =
+            Core::create_ark(settings(), &ark_seed, &storage, &wallet(), None)
+                .1
+                .await
+        else {
+            panic!("ark created twice");
+        };
+        assert!(err.to_string().contains("already exists"));
+        assert!(receipt.is_empty());
+    }
+
+    #[tokio::test]
+    async fn resume_interrupted_ark_creation() {
+        let storage: Storage = MemoryStorage::new().into();
+        let (ark_seed, _) = ArkSeed::random(None);
+
+        // an earlier attempt only got as far as creating the helm register
+        let helm_register = OwnedHelmRegister::new_derived(&ark_seed);
+        let helm_key = ark_seed.helm_key(helm_register.value());
+        core(&storage, &ark_seed)
+            .create_register(helm_register, &mut Receipt::new("Interrupted"))
+            .await
+            .unwrap();
+
+        let Err((err, _)) = Core::create_ark(settings(), &ark_seed, &storage, &wallet(), None)
+            .1
+            .await
+        else {
+            panic!("ark created twice");
+        };
+        assert!(err.to_string().contains("already exists"));
+
+        let Ok((details, _)) =
+            Core::resume_ark_creation(settings(), &ark_seed, &storage, &wallet(), None)
+                .1
+                .await
+        else {
+            panic!("ark creation not resumed");
+        };
+        // the existing helm register was kept
+        assert_eq!(details.helm_key, helm_key);
+        let Ok((manifest, _)) = core(&storage, &ark_seed)
+            .ark_details(&details.data_key.clone().into())
+            .1
+            .await
+        else {
+            panic!("ark not readable");
+        };
+        assert_eq!(manifest.name, "Test Ark");
+
+        let Err((err, _)) =
+            Core::resume_ark_creation(settings(), &ark_seed, &storage, &wallet(), None)
+                .1
+                .await
+        else {
+            panic!("complete ark resumed");
+        };
+        assert!(err.to_string().contains("already fully created"));
+    }
+
+    #[tokio::test]
+    async fn update_metadata() {
+        let storage: Storage = MemoryStorage::new().into();
+        let (ark_seed, _) = ArkSeed::random(None);
+        let Ok((details, _)) = Core::create_ark(settings(), &ark_seed, &storage, &wallet(), None)
+            .1
+            .await
+        else {
+            panic!("ark creation failed");
+        };
+        let core = core(&storage, &ark_seed);
+        let helm_key = &details.helm_key;
+        let update = async |update: ArkMetadataUpdate| match core
+            .update_ark_metadata(update, helm_key)
+            .1
+            .await
+        {
+            Ok((manifest, _)) => manifest,
+            Err((err, _)) => panic!("metadata update failed: {:?}", err),
+        };
+
+        let manifest = update(
+            ArkMetadataUpdate::builder()
+                .name(" Renamed ")
+                .description(Some("Archive".to_string()))
+                .labels(BTreeMap::from([
+                    ("owner".to_string(), LabelValue::Text("finance".to_string())),
+                    ("retention".to_string(), LabelValue::Integer(10)),
+                ]))
+                .build(),
+        )
+        .await;
+        assert_eq!(manifest.name, "Renamed");
+        assert_eq!(manifest.description.as_deref(), Some("Archive"));
+        assert_eq!(manifest.labels.len(), 2);
+
+        let manifest = update(
+            ArkMetadataUpdate::builder()
+                .description(None)
+                .labels(BTreeMap::from([(
+                    "retention".to_string(),
+                    LabelValue::Integer(7),
+                )]))
+                .remove_labels(BTreeSet::from(["owner".to_string()]))
+                .build(),
+        )
+        .await;
+        assert_eq!(manifest.name, "Renamed");
+        assert_eq!(manifest.description, None);
+        assert_eq!(
+            manifest.labels,
+            BTreeMap::from([("retention".to_string(), LabelValue::Integer(7))])
+        );
+
+        let Err((err, _)) = core
+            .update_ark_metadata(ArkMetadataUpdate::builder().name(" ").build(), helm_key)
+            .1
+            .await
+        else {
+            panic!("empty name accepted");
+        };
+        assert!(err.to_string().contains("must not be empty"));
+    }
+
+    #[tokio::test]
+    async fn quote_ark_creation() {
+        let storage = priced_storage();
+
+        let quote = Core::quote_create_ark(settings(), &storage)
+            .1
+            .await
+            .unwrap();
+        assert!(!quote.is_empty());
+        assert_eq!(
+            quote.total_cost(),
+            AttoTokens::from_u64(10 * quote.len() as u64)
+        );
+
+        let (ark_seed, _) = ArkSeed::random(None);
+        let Ok((_, receipt)) = Core::create_ark(settings(), &ark_seed, &storage, &wallet(), None)
+            .1
+            .await
+        else {
+            panic!("ark creation failed");
+        };
+        assert_eq!(receipt.len(), quote.len());
+        assert_eq!(receipt.total_cost(), quote.total_cost());
+    }
+
+    #[tokio::test]
+    async fn quote_key_rotation_without_wallet() {
+        let storage = priced_storage();
+        let (ark_seed, _) = ArkSeed::random(None);
+        let Ok((details, _)) = Core::create_ark(settings(), &ark_seed, &storage, &wallet(), None)
+            .1
+            .await
+        else {
+            panic!("ark creation failed");
+        };
+
+        let reader = ReadOnlyCore::builder()
+            .storage(storage.clone())
+            .ark_address(ark_seed.address().clone())
+            .build();
+        let quote = reader.quote_rotate_helm_key(&ark_seed).1.await.unwrap();
+        assert!(!quote.is_empty());
+        // nothing was written
+        reader.verify_helm_key(&details.helm_key).await.unwrap();
+
+        let Ok((_, receipt)) = core(&storage, &ark_seed).rotate_helm_key(&ark_seed).1.await else {
+            panic!("helm key rotation failed");
+        };
+        assert_eq!(receipt.len(), quote.len());
+        assert_eq!(receipt.total_cost(), quote.total_cost());
+    }
+}
@@ -1,30 +1,23 @@
 use crate::crypto::Bech32Public;
 use crate::data_key::DataKeySeed;
+use crate::journal::{JournalOperation, JournalStep};
 use crate::{ConfidentialString, DataKey, EitherWorkerKey, HelmKey, PublicWorkerKey, SealKey};
 
-use crate::{Core, Progress, crypto, with_receipt};
-use anyhow::bail;
+use crate::progress::Task;
+use crate::{
+    Core, Error, KeyKind, Progress, ReadOnlyCore, Receipt, crypto, with_quote, with_receipt,
+};
 use autonomi::PointerAddress;
 use autonomi::pointer::PointerTarget;
-use bip39::Mnemonic;
+use bip39::{Language as MnemonicLanguage, Mnemonic};
 use blsttc::SecretKey;
 use zeroize::Zeroize;
 
 impl TryFrom<Mnemonic> for ArkSeed {
     type Error = anyhow::Error;
 
-    fn try_from(mut value: Mnemonic) -> Result<Self, Self::Error> {
-        let mut seed = value.to_seed_normalized("");
-        value.zeroize();
-        let key_bytes = match crypto::eip2333(&seed) {
-            Ok(key_bytes) => key_bytes,
-            Err(err) => {
-                seed.zeroize();
-                return Err(err);
-            }
-        };
-        seed.zeroize();
-        Ok(Self::new(SecretKey::from_bytes(key_bytes)?))
+    fn try_from(value: Mnemonic) -> Result<Self, Self::Error> {
+        Self::from_mnemonic(value, None)
     }
 }
 
@@ -34,16 +27,39 @@ pub struct ArkRoot;
 pub type ArkSeed = crypto::TypedSecretKey<ArkRoot>;
 
 impl ArkSeed {
-    pub fn random() -> (Self, ConfidentialString) {
-        let mnemonic = Mnemonic::generate(24).expect("24 to be a valid word count");
+    /// Generates a new seed, returning it along with its (English) mnemonic.
+    ///
+    /// If a `passphrase` is given, the mnemonic alone is no longer sufficient:
+    /// the exact same passphrase is required to recover the seed.
+    pub fn random(passphrase: Option<&ConfidentialString>) -> (Self, ConfidentialString) {
+        Self::random_in(MnemonicLanguage::English, passphrase)
+    }
+
+    /// Like [`ArkSeed::random`], but with the mnemonic in the given language.
+    pub fn random_in(
+        language: MnemonicLanguage,
+        passphrase: Option<&ConfidentialString>,
+    ) -> (Self, ConfidentialString) {
+        let mnemonic = Mnemonic::generate_in(language, 24).expect("24 to be a valid word count");
         let s = mnemonic.to_string().into();
 
-        let this = Self::try_from(mnemonic).expect("generated mnemonic to lead to valid ark seed");
+        let this = Self::from_mnemonic(mnemonic, passphrase)
+            .expect("generated mnemonic to lead to valid ark seed");
         (this, s)
     }
 
-    pub fn try_from_mnemonic(mut s: String) -> anyhow::Result<Self> {
-        let mnemonic = match Mnemonic::parse_normalized(s.as_str()) {
+    /// Recovers the seed from its mnemonic and optional passphrase.
+    ///
+    /// The language of the mnemonic is detected automatically.
+    ///
+    /// Every passphrase leads to a valid, but different seed. A mistyped
+    /// passphrase can therefore not be detected here; compare the resulting
+    /// [`ArkSeed::address`] with the expected one instead.
+    pub fn try_from_mnemonic(
+        mut s: String,
+        passphrase: Option<&ConfidentialString>,
+    ) -> anyhow::Result<Self> {
+        let mnemonic = match Mnemonic::parse(s.as_str()) {
             Ok(mnemonic) => mnemonic,
             Err(err) => {
                 s.zeroize();
@@ -52,7 +68,35 @@ impl ArkSeed {
         };
         s.zeroize();
 
-        Ok(Self::try_from(mnemonic)?)
+        Self::from_mnemonic(mnemonic, passphrase)
+    }
+
+    fn from_mnemonic(
+        mut mnemonic: Mnemonic,
+        passphrase: Option<&ConfidentialString>,
+    ) -> anyhow::Result<Self> {
+        // The seed only depends on the entropy, not the language the words are in.
+        // Every mnemonic is therefore translated to English first, which leaves
+        // existing English mnemonics unaffected.
+        if mnemonic.language() != MnemonicLanguage::English {
+            let mut entropy = mnemonic.to_entropy();
+            mnemonic.zeroize();
+            let english = Mnemonic::from_entropy_in(MnemonicLanguage::English, &entropy);
+            entropy.zeroize();
+            mnemonic = english?;
+        }
+        // an empty passphrase is the BIP39 default
+        let mut seed = mnemonic.to_seed(passphrase.map(AsRef::<str>::as_ref).unwrap_or(""));
+        mnemonic.zeroize();
+        let key_bytes = match crypto::eip2333(&seed) {
+            Ok(key_bytes) => key_bytes,
+            Err(err) => {
+                seed.zeroize();
+                return Err(err);
+            }
+        };
+        seed.zeroize();
+        Ok(Self::new(SecretKey::from_bytes(key_bytes)?))
     }
 
     pub fn address(&self) -> &ArkAddress {
@@ -78,51 +122,237 @@ impl From<ArkAddress> for PointerTarget {
     }
 }
 
-impl Core {
+/// The currently active keys of an Ark, re-derived from its Ark Seed.
+pub struct RecoveredKeys {
+    pub helm_key: HelmKey,
+    pub data_key: DataKey,
+    /// Worker Keys are random, only the public key of the active one is known.
+    pub public_worker_key: PublicWorkerKey,
+}
+
+impl ReadOnlyCore {
     pub(super) fn verify_ark_seed(&self, ark_seed: &ArkSeed) -> anyhow::Result<()> {
         if &self.ark_address != ark_seed.address() {
-            bail!("ark_seed not valid for ark_address [{}]", self.ark_address);
+            return Err(Error::InvalidKey {
+                key: KeyKind::ArkSeed,
+                ark_address: self.ark_address.clone(),
+            }
+            .into());
         }
         Ok(())
     }
 
-    pub fn rotate_all_keys<'a>(
+    /// Re-derives the active Helm Key & Data Key from the Ark Seed.
+    ///
+    /// Only reads from the network, nothing is rotated or paid for.
+    pub fn recover_keys<'a>(
         &'a self,
         ark_seed: &'a ArkSeed,
-        new_worker_key: Option<PublicWorkerKey>,
     ) -> (
         Progress,
-        impl Future<Output = crate::Result<(DataKey, HelmKey, EitherWorkerKey)>> + Send + 'a,
+        impl Future<Output = crate::Result<RecoveredKeys>> + Send + 'a,
     ) {
-        let (progress, mut task) = Progress::new(1, "Full Ark Key Rotation".to_string());
+        let (progress, mut task) = Progress::new(1, "Key Recovery".to_string());
         (
             progress,
-            with_receipt(async move |receipt| {
+            with_receipt("Key Recovery", async move |_| {
                 task.start();
                 let mut verify_seed = task.child(1, "Verify Ark Seed".to_string());
-                let helm_key_task = task.child(2, "Helm Key".to_string());
-                let worker_key_task = task.child(1, "Worker Key".to_string());
-                let data_key_task = task.child(1, "Data Key".to_string());
+                let mut helm_key_task = task.child(1, "Helm Key".to_string());
+                let mut data_key_task = task.child(1, "Data Key".to_string());
+                let mut worker_key_task = task.child(1, "Worker Key".to_string());
 
                 verify_seed.start();
                 self.verify_ark_seed(ark_seed)?;
                 verify_seed.complete();
 
-                let helm_key = self
-                    ._rotate_helm_key(&ark_seed, receipt, helm_key_task)
-                    .await?;
+                helm_key_task.start();
+                let helm_key = self.helm_key(ark_seed).await?;
+                helm_key_task.complete();
 
-                let new_worker_key = self
-                    ._rotate_worker_key(&helm_key, new_worker_key, receipt, worker_key_task)
-                    .await?;
+                data_key_task.start();
+                let data_key = self.data_key(ark_seed).await?;
+                data_key_task.complete();
 
-                let data_key = self
-                    ._rotate_data_key(ark_seed, receipt, data_key_task)
-                    .await?;
+                worker_key_task.start();
+                let public_worker_key = self.public_worker_key(&helm_key).await?;
+                worker_key_task.complete();
 
                 task.complete();
-                Ok((data_key, helm_key, new_worker_key))
+                Ok(RecoveredKeys {
+                    helm_key,
+                    data_key,
+                    public_worker_key,
+                })
+            }),
+        )
+    }
+
+    /// Estimates the cost of [`Core::rotate_all_keys`] without spending anything.
+    pub fn quote_rotate_all_keys<'a>(
+        &'a self,
+        ark_seed: &'a ArkSeed,
+        new_worker_key: Option<PublicWorkerKey>,
+    ) -> (
+        Progress,
+        impl Future<Output = anyhow::Result<Receipt>> + Send + 'a,
+    ) {
+        let (progress, task) = Progress::new(1, "Full Ark Key Rotation Quote".to_string());
+        (
+            progress,
+            with_quote("Full Ark Key Rotation", async move |receipt| {
+                self.quoting()
+                    ._rotate_all_keys(ark_seed, new_worker_key, receipt, task)
+                    .await
+            }),
+        )
+    }
+}
+
+impl Core {
+    pub fn rotate_all_keys<'a>(
+        &'a self,
+        ark_seed: &'a ArkSeed,
+        new_worker_key: Option<PublicWorkerKey>,
+    ) -> (
+        Progress,
+        impl Future<Output = crate::Result<(DataKey, HelmKey, EitherWorkerKey)>> + Send + 'a,
+    ) {
+        let (progress, mut task) = Progress::new(1, "Full Ark Key Rotation".to_string());
+        (
+            progress,
+            with_receipt("Full Ark Key Rotation", async move |receipt| {
+                self.check_budget(receipt, &mut task, async |core, receipt, task| {
+                    core._rotate_all_keys(ark_seed, new_worker_key.clone(), receipt, task)
+                        .await
+                })
+                .await?;
+                self._rotate_all_keys(ark_seed, new_worker_key, receipt, task)
+                    .await
             }),
         )
     }
+
+    /// Rotates all keys in turn. An interrupted rotation is rolled forward.
+    async fn _rotate_all_keys(
+        &self,
+        ark_seed: &ArkSeed,
+        new_worker_key: Option<PublicWorkerKey>,
+        receipt: &mut Receipt,
+        mut task: Task,
+    ) -> anyhow::Result<(DataKey, HelmKey, EitherWorkerKey)> {
+        task.start();
+        let mut verify_seed = task.child(1, "Verify Ark Seed".to_string());
+        let helm_key_task = task.child(2, "Helm Key".to_string());
+        let worker_key_task = task.child(1, "Worker Key".to_string());
+        let data_key_task = task.child(1, "Data Key".to_string());
+
+        verify_seed.start();
+        self.verify_ark_seed(ark_seed)?;
+        verify_seed.complete();
+
+        let mut journal = self.journal(JournalOperation::FullKeyRotation)?;
+
+        let helm_key = if journal.is_done(JournalStep::HelmKeyRotated) {
+            helm_key_task.complete();
+            self.helm_key(ark_seed).await?
+        } else {
+            let helm_key = self
+                ._rotate_helm_key(&ark_seed, receipt, helm_key_task)
+                .await?;
+            journal.record(JournalStep::HelmKeyRotated)?;
+            helm_key
+        };
+
+        // a generated worker key is never persisted, so it has to be rotated again
+        let already_authorized = match &new_worker_key {
+            Some(pk) if journal.is_done(JournalStep::WorkerKeyRotated) => {
+                &self.public_worker_key(&helm_key).await? == pk
+            }
+            _ => false,
+        };
+        let new_worker_key = if already_authorized {
+            worker_key_task.complete();
+            new_worker_key.expect("new worker key to be present").into()
+        } else {
+            let new_worker_key = self
+                ._rotate_worker_key(&helm_key, new_worker_key, receipt, worker_key_task)
+                .await?;
+            journal.record(JournalStep::WorkerKeyRotated)?;
+            new_worker_key
+        };
+
+        let data_key = if journal.is_done(JournalStep::DataKeyRotated) {
+            data_key_task.complete();
+            self.data_key(ark_seed).await?
+        } else {
+            let data_key = self
+                ._rotate_data_key(ark_seed, receipt, data_key_task)
+                .await?;
+            journal.record(JournalStep::DataKeyRotated)?;
+            data_key
+        };
+
+        journal.finish()?;
+        task.complete();
+        Ok((data_key, helm_key, new_worker_key))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn passphrase(s: &str) -> ConfidentialString {
+        s.to_string().into()
+    }
+
+    #[test]
+    fn recover_with_same_passphrase_only() {
+        let (ark_seed, mnemonic) = ArkSeed::random(Some(&passphrase("correct horse")));
+        let recover = |given: Option<&ConfidentialString>| {
+            ArkSeed::try_from_mnemonic(mnemonic.as_ref().to_string(), given).unwrap()
+        };
+
+        assert_eq!(
+            recover(Some(&passphrase("correct horse"))).address(),
+            ark_seed.address()
+        );
+        // every passphrase leads to a valid, but different ark
+        assert_ne!(
+            recover(Some(&passphrase("wrong horse"))).address(),
+            ark_seed.address()
+        );
+        assert_ne!(recover(None).address(), ark_seed.address());
+    }
+
+    #[test]
+    fn treat_empty_passphrase_as_none() {
+        let (ark_seed, mnemonic) = ArkSeed::random(None);
+        let recovered =
+            ArkSeed::try_from_mnemonic(mnemonic.as_ref().to_string(), Some(&passphrase("")))
+                .unwrap();
+        assert_eq!(recovered.address(), ark_seed.address());
+    }
+
+    #[test]
+    fn detect_mnemonic_language() {
+        for language in MnemonicLanguage::ALL {
+            let (ark_seed, mnemonic) = ArkSeed::random_in(*language, None);
+            let recovered = ArkSeed::try_from_mnemonic(mnemonic.as_ref().to_string(), None)
+                .unwrap_or_else(|err| panic!("{:?}: {}", language, err));
+            assert_eq!(recovered.address(), ark_seed.address(), "{:?}", language);
+        }
+    }
+
+    #[test]
+    fn derive_same_seed_in_every_language() {
+        let (ark_seed, mnemonic) = ArkSeed::random(None);
+        let entropy = Mnemonic::parse(mnemonic.as_ref()).unwrap().to_entropy();
+        for language in MnemonicLanguage::ALL {
+            let translated = Mnemonic::from_entropy_in(*language, &entropy).unwrap();
+            let recovered = ArkSeed::try_from_mnemonic(translated.to_string(), None).unwrap();
+            assert_eq!(recovered.address(), ark_seed.address(), "{:?}", language);
+        }
+    }
 }
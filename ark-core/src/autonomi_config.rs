@@ -1,11 +1,13 @@
+use crate::{DirectoryStorage, Storage};
 use anyhow::{anyhow, bail};
 use autonomi::{
     Client as AutonomiClient, ClientConfig as AutonomiClientConfig, Multiaddr,
     Network as EvmNetwork,
 };
 use bon::Builder;
+use percent_encoding::{AsciiSet, CONTROLS, percent_decode_str, utf8_percent_encode};
 use std::fmt::{Display, Formatter};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use url::Url;
 
@@ -24,6 +26,9 @@ const NETWORK_MAINNET: &str = "mainnet";
 const NETWORK_ALPHANET: &str = "alphanet";
 const NETWORK_TESTNET: &str = "testnet";
 const NETWORK_LOCAL: &str = "local";
+const NETWORK_DIRECTORY: &str = "dir";
+
+const DIRECTORY_PATH_ENCODE_SET: &AsciiSet = &CONTROLS.add(b' ').add(b'#').add(b'%').add(b'?');
 
 #[derive(Builder, Clone, Debug)]
 pub struct ClientConfig {
@@ -39,38 +44,62 @@ impl ClientConfig {
         Ok(AutonomiClient::init_with_config(self.to_autononomi_config().await?).await?)
     }
 
+    /// Opens the storage this config points to.
+    ///
+    /// This is either a connection to an Autonomi Network or a local directory.
+    pub async fn try_new_storage(&self) -> anyhow::Result<Storage> {
+        let storage: Storage = match &self.network {
+            Network::Directory(path) => DirectoryStorage::open(path)?.into(),
+            _ => self.try_new_client().await?.into(),
+        };
+        Ok(storage.with_network(self.friendly()))
+    }
+
+    pub fn evm_network(&self) -> EvmNetwork {
+        match &self.network {
+            Network::MainNet => EvmNetwork::ArbitrumOne,
+            Network::AlphaNet | Network::TestNet => EvmNetwork::ArbitrumSepoliaTest,
+            Network::Local(local_config) => EvmNetwork::new_custom(
+                local_config.rpc_url.as_str(),
+                local_config.payment_token_addr.as_str(),
+                local_config.data_payments_addr.as_str(),
+            ),
+            // nothing is ever paid for, a wallet still needs a network though
+            Network::Directory(_) => EvmNetwork::ArbitrumSepoliaTest,
+        }
+    }
+
     async fn to_autononomi_config(&self) -> anyhow::Result<AutonomiClientConfig> {
         let mut config = AutonomiClientConfig::default();
+        config.evm_network = self.evm_network();
 
         match &self.network {
             Network::MainNet => {
-                config.evm_network = EvmNetwork::ArbitrumOne;
                 config.network_id = Some(1);
                 self.set_ignore_cache(&mut config);
                 self.set_bootstrap_cache(&mut config);
             }
             Network::AlphaNet => {
-                config.evm_network = EvmNetwork::ArbitrumSepoliaTest;
                 config.network_id = Some(2);
                 self.set_ignore_cache(&mut config);
                 self.set_bootstrap_cache(&mut config);
             }
             Network::TestNet => {
-                config.evm_network = EvmNetwork::ArbitrumSepoliaTest;
                 self.set_network_id(&mut config);
                 self.set_ignore_cache(&mut config);
                 self.set_bootstrap_cache(&mut config);
             }
-            Network::Local(local_config) => {
-                config.evm_network = EvmNetwork::new_custom(
-                    local_config.rpc_url.as_str(),
-                    local_config.payment_token_addr.as_str(),
-                    local_config.data_payments_addr.as_str(),
-                );
+            Network::Local(_) => {
                 self.set_network_id(&mut config);
                 config.init_peers_config.local = true;
                 config.init_peers_config.ignore_cache = true;
             }
+            Network::Directory(path) => {
+                bail!(
+                    "[{}] is a local directory, not an autonomi network",
+                    path.display()
+                );
+            }
         };
 
         if let Some(bootstrap_peers) = &self.bootstrap_peers {
@@ -125,10 +154,13 @@ impl ClientConfig {
 
     pub fn to_url(&self) -> Url {
         let net = match &self.network {
-            Network::MainNet => NETWORK_MAINNET,
-            Network::AlphaNet => NETWORK_ALPHANET,
-            Network::TestNet => NETWORK_TESTNET,
-            Network::Local(_) => NETWORK_LOCAL,
+            Network::MainNet => NETWORK_MAINNET.to_string(),
+            Network::AlphaNet => NETWORK_ALPHANET.to_string(),
+            Network::TestNet => NETWORK_TESTNET.to_string(),
+            Network::Local(_) => NETWORK_LOCAL.to_string(),
+            Network::Directory(path) => {
+                format!("{}:{}", NETWORK_DIRECTORY, directory_to_url_path(path))
+            }
         };
         let mut url = Url::parse(format!("{}:{}:{}", SCHEME, PREFIX, net).as_str())
             .expect("url parsing should never fail");
@@ -199,6 +231,7 @@ impl ClientConfig {
                         .unwrap_or("".to_string())
                 )
             }
+            Network::Directory(path) => format!("Directory ({})", path.display()),
         }
     }
 }
@@ -223,8 +256,16 @@ impl TryFrom<Url> for ClientConfig {
             .path()
             .strip_prefix(format!("{}:", PREFIX).as_str())
             .ok_or(anyhow!("path does not start with '{}:'", PREFIX))?
-            .trim()
-            .to_ascii_lowercase();
+            .trim();
+
+        let directory = match network.split_once(':') {
+            Some((net, path)) if net.eq_ignore_ascii_case(NETWORK_DIRECTORY) => {
+                Some(directory_from_url_path(path)?)
+            }
+            _ => None,
+        };
+
+        let network = network.to_ascii_lowercase();
 
         let mut rpc_url = None;
         let mut payment_token_addr = None;
@@ -280,23 +321,29 @@ impl TryFrom<Url> for ClientConfig {
             }
         }
 
-        let network = match network.as_str() {
-            NETWORK_MAINNET => Network::MainNet,
-            NETWORK_ALPHANET => Network::AlphaNet,
-            NETWORK_TESTNET => Network::TestNet,
-            NETWORK_LOCAL => Network::Local(
-                LocalNetworkConfig::builder()
-                    .rpc_url(rpc_url.ok_or(anyhow!("{} is missing", RPC_URL_PARAM))?)
-                    .payment_token_addr(
-                        payment_token_addr.ok_or(anyhow!("{} is missing", PAYMENT_TOKEN_PARAM))?,
-                    )
-                    .data_payments_addr(
-                        data_payments_addr.ok_or(anyhow!("{} is missing", DATA_PAYMENTS_PARAM))?,
-                    )
-                    .build(),
-            ),
-            _ => {
-                bail!("invalid network: [{}]", url.path())
+        let network = if let Some(path) = directory {
+            Network::Directory(path)
+        } else {
+            match network.as_str() {
+                NETWORK_MAINNET => Network::MainNet,
+                NETWORK_ALPHANET => Network::AlphaNet,
+                NETWORK_TESTNET => Network::TestNet,
+                NETWORK_LOCAL => Network::Local(
+                    LocalNetworkConfig::builder()
+                        .rpc_url(rpc_url.ok_or(anyhow!("{} is missing", RPC_URL_PARAM))?)
+                        .payment_token_addr(
+                            payment_token_addr
+                                .ok_or(anyhow!("{} is missing", PAYMENT_TOKEN_PARAM))?,
+                        )
+                        .data_payments_addr(
+                            data_payments_addr
+                                .ok_or(anyhow!("{} is missing", DATA_PAYMENTS_PARAM))?,
+                        )
+                        .build(),
+                ),
+                _ => {
+                    bail!("invalid network: [{}]", url.path())
+                }
             }
         };
 
@@ -322,6 +369,27 @@ pub enum Network {
     AlphaNet,
     TestNet,
     Local(LocalNetworkConfig),
+    /// Records are kept in a local directory instead of a network
+    Directory(PathBuf),
+}
+
+fn directory_to_url_path(path: &Path) -> String {
+    let path =
+        utf8_percent_encode(path.to_string_lossy().as_ref(), DIRECTORY_PATH_ENCODE_SET).to_string();
+    if path.starts_with('/') {
+        format!("//{}", path)
+    } else {
+        path
+    }
+}
+
+fn directory_from_url_path(path: &str) -> anyhow::Result<PathBuf> {
+    let path = percent_decode_str(path).decode_utf8()?;
+    let path = path.strip_prefix("//").unwrap_or(path.as_ref());
+    if path.is_empty() {
+        bail!("directory path is missing");
+    }
+    Ok(PathBuf::from(path))
 }
 
 #[derive(Builder, Clone, Debug)]
@@ -339,3 +407,38 @@ pub enum BootstrapPeers {
     Urls(Vec<Url>),
     MultiAddresses(Vec<Multiaddr>),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn directory(config: &ClientConfig) -> &Path {
+        match &config.network {
+            Network::Directory(path) => path.as_path(),
+            other => panic!("expected a directory, got [{:?}]", other),
+        }
+    }
+
+    #[test]
+    fn directory_url_round_trip() {
+        for path in ["/var/lib/ark", "/tmp/my ark #1/100%?", "relative/ark"] {
+            let config = ClientConfig::builder()
+                .network(Network::Directory(PathBuf::from(path)))
+                .build();
+            let url = config.to_string();
+            let parsed = ClientConfig::from_str(url.as_str()).unwrap();
+            assert_eq!(directory(&parsed), Path::new(path), "{}", url);
+        }
+    }
+
+    #[test]
+    fn parse_directory_url() {
+        let config = ClientConfig::from_str("autonomi:config:dir:///var/lib/ark").unwrap();
+        assert_eq!(directory(&config), Path::new("/var/lib/ark"));
+
+        let config = ClientConfig::from_str("autonomi:config:DIR:relative").unwrap();
+        assert_eq!(directory(&config), Path::new("relative"));
+
+        assert!(ClientConfig::from_str("autonomi:config:dir:").is_err());
+    }
+}
@@ -0,0 +1,240 @@
+use crate::{Ledger, Receipt};
+use anyhow::{anyhow, bail};
+use autonomi::AttoTokens;
+use bon::bon;
+use chrono::{TimeDelta, Utc};
+use thiserror::Error;
+
+/// Spending limits enforced by `Core` before any payment is made.
+///
+/// Operations consisting of several writes are quoted in full before their
+/// first payment. If the quoted total would exceed either limit, the operation
+/// is aborted with [`BudgetExceeded`] and nothing is paid. Each payment is
+/// checked again right before it is made, as prices can change in between;
+/// only then can an operation be aborted part way.
+///
+/// The daily limit is enforced against the payments recorded in the [`Ledger`],
+/// so it holds across processes sharing the same ledger. Payments only reach
+/// the ledger once their operation is settled, concurrently running operations
+/// are therefore checked independently of each other.
+#[derive(Clone, Debug)]
+pub struct Budget {
+    per_operation: Option<AttoTokens>,
+    daily: Option<AttoTokens>,
+    ledger: Option<Ledger>,
+}
+
+#[bon]
+impl Budget {
+    #[builder]
+    pub fn new(
+        /// Maximum total cost of a single operation, e.g. an ark creation or key rotation
+        per_operation: Option<AttoTokens>,
+        /// Maximum total cost of all operations within any 24-hour window
+        daily: Option<AttoTokens>,
+        /// Source of past payments, required for the daily limit
+        ledger: Option<Ledger>,
+    ) -> anyhow::Result<Self> {
+        if daily.is_some() && ledger.is_none() {
+            bail!("a daily budget requires a ledger");
+        }
+        Ok(Self {
+            per_operation,
+            daily,
+            ledger,
+        })
+    }
+
+    pub fn per_operation(&self) -> Option<AttoTokens> {
+        self.per_operation
+    }
+
+    pub fn daily(&self) -> Option<AttoTokens> {
+        self.daily
+    }
+
+    /// Total recorded in the ledger within the last 24 hours.
+    pub fn spent_last_24h(&self) -> anyhow::Result<AttoTokens> {
+        match &self.ledger {
+            Some(ledger) => ledger.spent_since(Utc::now() - TimeDelta::days(1)),
+            None => Ok(AttoTokens::zero()),
+        }
+    }
+
+    /// Checks if paying `cost` is within budget, on top of what the operation
+    /// `receipt` belongs to has already spent.
+    ///
+    /// The ledger is only read by the first check of an operation, its payments
+    /// only reach the ledger once the operation is settled anyway.
+    pub(crate) fn check(&self, receipt: &mut Receipt, cost: AttoTokens) -> anyhow::Result<()> {
+        let total = sum([receipt.total_cost(), cost])?;
+        if let Some(limit) = self.per_operation {
+            if total > limit {
+                return Err(BudgetExceeded::PerOperation { limit, total }.into());
+            }
+        }
+        if let Some(limit) = self.daily {
+            let spent_before = match receipt.spent_before {
+                Some(spent_before) => spent_before,
+                None => *receipt.spent_before.insert(self.spent_last_24h()?),
+            };
+            let total = sum([spent_before, total])?;
+            if total > limit {
+                return Err(BudgetExceeded::Daily { limit, total }.into());
+            }
+        }
+        Ok(())
+    }
+}
+
+fn sum(costs: impl IntoIterator<Item = AttoTokens>) -> anyhow::Result<AttoTokens> {
+    costs.into_iter().try_fold(AttoTokens::zero(), |acc, cost| {
+        acc.checked_add(cost)
+            .ok_or(anyhow!("token amount overflow while checking the budget"))
+    })
+}
+
+#[derive(Error, Debug, Clone)]
+pub enum BudgetExceeded {
+    #[error("operation would cost [{total}], exceeding the per-operation limit of [{limit}]")]
+    PerOperation {
+        limit: AttoTokens,
+        total: AttoTokens,
+    },
+    #[error(
+        "spending would reach [{total}] within 24 hours, exceeding the daily limit of [{limit}]"
+    )]
+    Daily {
+        limit: AttoTokens,
+        total: AttoTokens,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        ArkCreationSettings, ArkSeed, Core, Error, EvmWallet, MemoryStorage, ObjectType,
+        RecordKind, Storage, VaultCreationSettings,
+    };
+    use std::str::FromStr;
+
+    fn receipt(costs: &[u64]) -> Receipt {
+        let (ark_seed, _) = ArkSeed::random(None);
+        let mut receipt = Receipt::new("Test");
+        for cost in costs {
+            receipt.add(
+                AttoTokens::from_u64(*cost),
+                RecordKind::Chunk,
+                format!("{}", cost),
+                "TestNet",
+                ark_seed.address(),
+            );
+        }
+        receipt
+    }
+
+    #[test]
+    fn refuse_exceeding_per_operation_limit() {
+        let budget = Budget::builder()
+            .per_operation(AttoTokens::from_u64(10))
+            .build()
+            .unwrap();
+        let mut receipt = receipt(&[6]);
+
+        budget.check(&mut receipt, AttoTokens::from_u64(4)).unwrap();
+        let err = budget
+            .check(&mut receipt, AttoTokens::from_u64(5))
+            .unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<BudgetExceeded>(),
+            Some(BudgetExceeded::PerOperation { total, .. }) if total == &AttoTokens::from_u64(11)
+        ));
+    }
+
+    #[test]
+    fn refuse_exceeding_daily_limit() {
+        let dir = tempfile::tempdir().unwrap();
+        let ledger = Ledger::open(dir.path().join("ledger.jsonl")).unwrap();
+        ledger.record(&receipt(&[3, 4])).unwrap();
+        let budget = Budget::builder()
+            .daily(AttoTokens::from_u64(10))
+            .ledger(ledger.clone())
+            .build()
+            .unwrap();
+        assert_eq!(budget.spent_last_24h().unwrap(), AttoTokens::from_u64(7));
+
+        let mut operation = receipt(&[]);
+        budget
+            .check(&mut operation, AttoTokens::from_u64(3))
+            .unwrap();
+        // the ledger is read once per operation
+        ledger.record(&receipt(&[100])).unwrap();
+        budget
+            .check(&mut operation, AttoTokens::from_u64(3))
+            .unwrap();
+
+        let err = budget
+            .check(&mut receipt(&[]), AttoTokens::from_u64(1))
+            .unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<BudgetExceeded>(),
+            Some(BudgetExceeded::Daily { total, .. }) if total == &AttoTokens::from_u64(108)
+        ));
+    }
+
+    #[test]
+    fn require_ledger_for_daily_limit() {
+        assert!(
+            Budget::builder()
+                .daily(AttoTokens::from_u64(10))
+                .build()
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn abort_before_first_payment() {
+        let storage: Storage = MemoryStorage::new()
+            .with_price(AttoTokens::from_u64(10))
+            .into();
+        let wallet = EvmWallet::new_with_random_wallet(autonomi::Network::ArbitrumSepoliaTest);
+        let (ark_seed, _) = ArkSeed::random(None);
+        let Ok((details, _)) = Core::create_ark(
+            ArkCreationSettings::builder().name("Test Ark").build(),
+            &ark_seed,
+            &storage,
+            &wallet,
+            None,
+        )
+        .1
+        .await
+        else {
+            panic!("ark creation failed");
+        };
+
+        // enough for a single write, but not for the whole operation
+        let core = Core::builder()
+            .storage(storage)
+            .wallet(wallet)
+            .ark_address(ark_seed.address().clone())
+            .budget(
+                Budget::builder()
+                    .per_operation(AttoTokens::from_u64(10))
+                    .build()
+                    .unwrap(),
+            )
+            .build();
+        let settings = VaultCreationSettings::builder()
+            .name("Test Vault")
+            .object_type(ObjectType::from_str("posix").unwrap())
+            .build();
+        let Err((err, receipt)) = core.create_vault(settings, &details.helm_key).1.await else {
+            panic!("vault creation within budget");
+        };
+        assert!(matches!(err, Error::BudgetExceeded(_)), "{:?}", err);
+        assert!(receipt.is_empty());
+        let manifest = core.get_manifest(&details.helm_key).await.unwrap();
+        assert!(manifest.vaults.is_empty());
+    }
+}
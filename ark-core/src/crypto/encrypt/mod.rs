@@ -133,7 +133,8 @@ pub(crate) trait TypedDecryptor<T> {
     where
         for<'a> <V as TryFrom<&'a [u8]>>::Error: Display,
     {
-        let mut plaintext = S::decrypt(input.as_ref(), self.decryptor())?;
+        let mut plaintext = S::decrypt(input.as_ref(), self.decryptor())
+            .map_err(|e| crate::Error::DecryptionFailed(e.to_string()))?;
         let res = plaintext
             .as_slice()
             .try_into()
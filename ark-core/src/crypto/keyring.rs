@@ -58,7 +58,9 @@ impl<T> TypedDecryptor<T> for KeyRing<T> {
             .values()
             .map(|sk| S::decrypt(input.as_ref(), sk.as_ref()).ok())
             .find_map(|r| r)
-            .ok_or(anyhow!("no matching key found in keyring"))?;
+            .ok_or(crate::Error::DecryptionFailed(
+                "no matching key found in keyring".to_string(),
+            ))?;
 
         let res = plaintext
             .as_slice()
@@ -26,7 +26,8 @@ pub(crate) use keys::{
 pub(crate) use pointer::{TypedOwnedPointer, TypedPointerAddress};
 pub(crate) use register::{TypedOwnedRegister, TypedRegister, TypedRegisterAddress};
 pub(crate) use scratchpad::{
-    Content as ScratchpadContent, TypedOwnedScratchpad, TypedScratchpadAddress,
+    Content as ScratchpadContent, ScratchpadExt, ScratchpadState, TypedOwnedScratchpad,
+    TypedScratchpadAddress,
 };
 
 #[macro_export]
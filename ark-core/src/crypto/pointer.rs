@@ -1,6 +1,7 @@
 use crate::crypto::Finalizeable;
 use crate::crypto::keys::{TypedPublicKey, TypedSecretKey};
-use crate::{AutonomiClient, Core, Receipt};
+use crate::error::unshare;
+use crate::{Core, Error, ReadOnlyCore, Receipt, RecordKind, Storage};
 use anyhow::{anyhow, bail};
 use autonomi::pointer::PointerTarget;
 use autonomi::{Pointer, PointerAddress};
@@ -181,6 +182,60 @@ impl PointerExt for Pointer {
     }
 }
 
+impl ReadOnlyCore {
+    async fn get_pointer<T, V: TryFrom<PointerTarget>>(
+        &self,
+        address: &TypedPointerAddress<T, V>,
+    ) -> anyhow::Result<Option<TypedPointer<T, V>>>
+    where
+        <V as TryFrom<PointerTarget>>::Error: Send + Sync + Display,
+    {
+        Ok(self
+            ._pointer_get(address.as_ref())
+            .await?
+            .map(|p| TypedPointer::try_from_pointer(p))
+            .transpose()?)
+    }
+
+    async fn read_pointer<T, V: TryFrom<PointerTarget>>(
+        &self,
+        address: &TypedPointerAddress<T, V>,
+    ) -> anyhow::Result<V>
+    where
+        <V as TryFrom<PointerTarget>>::Error: Send + Sync + Display,
+    {
+        Ok(self
+            .get_pointer(address)
+            .await?
+            .map(|p| p.into_target())
+            .ok_or(Error::NotFound {
+                kind: RecordKind::Pointer,
+                address: address.as_ref().to_hex(),
+            })?)
+    }
+
+    async fn _pointer_get(&self, address: &PointerAddress) -> anyhow::Result<Option<Pointer>> {
+        self.pointer_cache
+            .try_get_with_by_ref(address, self.storage.pointer_get(address))
+            .await
+            .map_err(unshare)
+    }
+
+    pub(crate) async fn read_pointer_directly<T, V: TryFrom<PointerTarget> + Into<PointerTarget>>(
+        storage: &Storage,
+        address: &TypedPointerAddress<T, V>,
+    ) -> anyhow::Result<Option<TypedPointer<T, V>>>
+    where
+        <V as TryFrom<PointerTarget>>::Error: Send + Sync + Display,
+    {
+        Ok(storage
+            .pointer_get(address.as_ref())
+            .await?
+            .map(|p| TypedPointer::try_from_pointer(p))
+            .transpose()?)
+    }
+}
+
 impl Core {
     pub(crate) async fn create_pointer<T, V: Into<PointerTarget>>(
         &self,
@@ -209,17 +264,16 @@ impl Core {
             bail!("pointer already exists");
         }
 
-        let res = self
-            .client
-            .pointer_put(pointer, self.payment())
-            .await
-            .map_err(|e| anyhow::Error::from(e));
+        let payment = self
+            .authorize_payment(receipt, self.storage.pointer_cost(&pointer))
+            .await?;
+        let res = self.storage.pointer_put(pointer, payment).await;
 
         self.pointer_cache.invalidate(&address).await;
 
         let (attos, addr) = res?;
 
-        receipt.add(attos);
+        self.record_payment(receipt, RecordKind::Pointer, addr.to_hex(), attos);
         if &address != &addr {
             self.pointer_cache.invalidate(&addr).await;
             bail!("incorrect pointer address returned");
@@ -228,20 +282,6 @@ impl Core {
         Ok(TypedPointerAddress::new(address))
     }
 
-    async fn get_pointer<T, V: TryFrom<PointerTarget>>(
-        &self,
-        address: &TypedPointerAddress<T, V>,
-    ) -> anyhow::Result<Option<TypedPointer<T, V>>>
-    where
-        <V as TryFrom<PointerTarget>>::Error: Send + Sync + Display,
-    {
-        Ok(self
-            ._pointer_get(address.as_ref())
-            .await?
-            .map(|p| TypedPointer::try_from_pointer(p))
-            .transpose()?)
-    }
-
     async fn update_pointer<T, V: Into<PointerTarget>>(
         &self,
         mut pointer: TypedOwnedPointer<T, V>,
@@ -270,55 +310,14 @@ impl Core {
         let address = pointer.address();
         let counter = pointer.counter();
 
-        let res = self.client.pointer_put(pointer, self.payment()).await;
+        let payment = self
+            .authorize_payment(receipt, self.storage.pointer_cost(&pointer))
+            .await?;
+        let res = self.storage.pointer_put(pointer, payment).await;
         self.pointer_cache.invalidate(&address).await;
-        let (attos, _) = res.map_err(|e| anyhow!("{}", e))?;
-        receipt.add(attos);
+        let (attos, _) = res?;
+        self.record_payment(receipt, RecordKind::Pointer, address.to_hex(), attos);
 
         Ok(counter)
     }
-
-    async fn read_pointer<T, V: TryFrom<PointerTarget>>(
-        &self,
-        address: &TypedPointerAddress<T, V>,
-    ) -> anyhow::Result<V>
-    where
-        <V as TryFrom<PointerTarget>>::Error: Send + Sync + Display,
-    {
-        Ok(self
-            .get_pointer(address)
-            .await?
-            .map(|p| p.into_target())
-            .ok_or(anyhow!("pointer not found"))?)
-    }
-
-    async fn _pointer_get(&self, address: &PointerAddress) -> anyhow::Result<Option<Pointer>> {
-        self.pointer_cache
-            .try_get_with_by_ref(address, Self::_pointer_get_live(&self.client, address))
-            .await
-            .map_err(|e| anyhow!("{}", e))
-    }
-
-    async fn _pointer_get_live(
-        client: &AutonomiClient,
-        address: &PointerAddress,
-    ) -> anyhow::Result<Option<Pointer>> {
-        if !client.pointer_check_existance(address).await? {
-            return Ok(None);
-        }
-        Ok(Some(client.pointer_get(address).await?))
-    }
-
-    pub(crate) async fn read_pointer_directly<T, V: TryFrom<PointerTarget> + Into<PointerTarget>>(
-        client: &AutonomiClient,
-        address: &TypedPointerAddress<T, V>,
-    ) -> anyhow::Result<Option<TypedPointer<T, V>>>
-    where
-        <V as TryFrom<PointerTarget>>::Error: Send + Sync + Display,
-    {
-        Ok(Self::_pointer_get_live(client, address.as_ref())
-            .await?
-            .map(|p| TypedPointer::try_from_pointer(p))
-            .transpose()?)
-    }
 }
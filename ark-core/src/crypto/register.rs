@@ -1,9 +1,8 @@
 use crate::crypto::keys::{TypedPublicKey, TypedSecretKey};
-use crate::{Core, Receipt};
-use ant_networking::{GetRecordError, NetworkError};
+use crate::error::unshare;
+use crate::{Core, Error, ReadOnlyCore, Receipt, RecordKind};
 use anyhow::{anyhow, bail};
-use autonomi::pointer::PointerError;
-use autonomi::register::{RegisterAddress, RegisterError, RegisterValue};
+use autonomi::register::{RegisterAddress, RegisterValue};
 use blsttc::SecretKey;
 use std::fmt::Display;
 use std::marker::PhantomData;
@@ -134,61 +133,7 @@ impl<V> HistoricValue<V> {
     }
 }
 
-impl Core {
-    pub(crate) async fn create_register<T, V: Into<RegisterValue>>(
-        &self,
-        register: TypedOwnedRegister<T, V>,
-        receipt: &mut Receipt,
-    ) -> anyhow::Result<TypedRegisterAddress<T, V>> {
-        if self
-            ._register_get(register.address().as_ref())
-            .await?
-            .is_some()
-        {
-            bail!("register already exists")
-        }
-
-        let (sk, value) = register.into_register();
-        let (attos, address) = self
-            .client
-            .register_create(&sk, value, self.payment())
-            .await?;
-
-        self.register_cache.invalidate(&address).await;
-        self.register_history_cache.invalidate(&address).await;
-        receipt.add(attos);
-
-        Ok(TypedRegisterAddress::new(address))
-    }
-
-    pub(crate) async fn update_register<T, V: Into<RegisterValue>>(
-        &self,
-        register: TypedOwnedRegister<T, V>,
-        receipt: &mut Receipt,
-    ) -> anyhow::Result<()> {
-        if self
-            ._register_get(register.address().as_ref())
-            .await?
-            .is_none()
-        {
-            bail!("register does not exists")
-        }
-
-        let address = register.address().as_ref().clone();
-
-        let (sk, value) = register.into_register();
-        let res = self
-            .client
-            .register_update(&sk, value, self.payment())
-            .await;
-
-        self.register_cache.invalidate(&address).await;
-        self.register_history_cache.invalidate(&address).await;
-        receipt.add(res?);
-
-        Ok(())
-    }
-
+impl ReadOnlyCore {
     pub(crate) async fn get_register<T, V: TryFrom<RegisterValue>>(
         &self,
         address: &TypedRegisterAddress<T, V>,
@@ -214,7 +159,10 @@ impl Core {
             .get_register(address)
             .await?
             .map(|r| r.into_value())
-            .ok_or(anyhow!("register not found"))?)
+            .ok_or(Error::NotFound {
+                kind: RecordKind::Register,
+                address: address.as_ref().to_hex(),
+            })?)
     }
 
     async fn _register_get(
@@ -222,20 +170,9 @@ impl Core {
         address: &RegisterAddress,
     ) -> anyhow::Result<Option<RegisterValue>> {
         self.register_cache
-            .try_get_with_by_ref(address, async move {
-                match self.client.register_get(address).await {
-                    Ok(reg) => Ok(Some(reg)),
-                    Err(RegisterError::PointerError(PointerError::Network(
-                        NetworkError::GetRecordError(GetRecordError::RecordNotFound),
-                    ))) => {
-                        // if there is a better way to check for a register's existence, please update!
-                        Ok(None)
-                    }
-                    Err(err) => Err(err),
-                }
-            })
+            .try_get_with_by_ref(address, self.storage.register_get(address))
             .await
-            .map_err(|e| e.into())
+            .map_err(unshare)
     }
 
     pub(crate) async fn register_history<T, V: TryFrom<RegisterValue>>(
@@ -257,13 +194,84 @@ impl Core {
             .collect::<anyhow::Result<Vec<_>>>()?)
     }
 
+    /// Makes sure the next read of the register, including its history, reaches the storage.
+    ///
+    /// Used wherever acting on an outdated value would do harm, such as when
+    /// verifying or rotating keys.
+    pub(crate) async fn invalidate_register<T, V>(
+        &self,
+        address: &TypedRegisterAddress<T, V>,
+    ) -> anyhow::Result<()> {
+        self.register_cache.invalidate(address.as_ref()).await;
+        self.register_history_cache
+            .invalidate(address.as_ref())
+            .await;
+        self.storage.register_invalidate(address.as_ref()).await
+    }
+
     async fn _register_history(
         &self,
         address: &RegisterAddress,
     ) -> anyhow::Result<Vec<RegisterValue>> {
         self.register_history_cache
-            .try_get_with_by_ref(address, self.client.register_history(address).collect())
+            .try_get_with_by_ref(address, self.storage.register_history(address))
             .await
-            .map_err(|e| e.into())
+            .map_err(unshare)
+    }
+}
+
+impl Core {
+    pub(crate) async fn create_register<T, V: Into<RegisterValue>>(
+        &self,
+        register: TypedOwnedRegister<T, V>,
+        receipt: &mut Receipt,
+    ) -> anyhow::Result<TypedRegisterAddress<T, V>> {
+        if self
+            ._register_get(register.address().as_ref())
+            .await?
+            .is_some()
+        {
+            bail!("register already exists")
+        }
+
+        let (sk, value) = register.into_register();
+        let payment = self
+            .authorize_payment(receipt, self.storage.register_create_cost(&sk.public_key()))
+            .await?;
+        let (attos, address) = self.storage.register_create(&sk, value, payment).await?;
+
+        self.register_cache.invalidate(&address).await;
+        self.register_history_cache.invalidate(&address).await;
+        self.record_payment(receipt, RecordKind::Register, address.to_hex(), attos);
+
+        Ok(TypedRegisterAddress::new(address))
+    }
+
+    pub(crate) async fn update_register<T, V: Into<RegisterValue>>(
+        &self,
+        register: TypedOwnedRegister<T, V>,
+        receipt: &mut Receipt,
+    ) -> anyhow::Result<()> {
+        if self
+            ._register_get(register.address().as_ref())
+            .await?
+            .is_none()
+        {
+            bail!("register does not exists")
+        }
+
+        let address = register.address().as_ref().clone();
+
+        let (sk, value) = register.into_register();
+        let payment = self
+            .authorize_payment(receipt, self.storage.register_update_cost(&sk.public_key()))
+            .await?;
+        let res = self.storage.register_update(&sk, value, payment).await;
+
+        self.register_cache.invalidate(&address).await;
+        self.register_history_cache.invalidate(&address).await;
+        self.record_payment(receipt, RecordKind::Register, address.to_hex(), res?);
+
+        Ok(())
     }
 }
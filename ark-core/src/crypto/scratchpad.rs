@@ -1,7 +1,8 @@
 use crate::crypto::encrypt::{DefaultEncryptionScheme, EncryptedData, EncryptionScheme};
 use crate::crypto::keys::{TypedPublicKey, TypedSecretKey};
 use crate::crypto::{Finalizeable, Retirable, ScratchpadContent};
-use crate::{Core, Receipt};
+use crate::error::unshare;
+use crate::{Core, Error, ReadOnlyCore, Receipt, RecordKind};
 use anyhow::{anyhow, bail};
 use autonomi::{Client, Scratchpad, ScratchpadAddress};
 use bytes::Bytes;
@@ -73,6 +74,10 @@ impl<T, V> PlaintextScratchpad<T, V> {
     pub fn size(&self) -> usize {
         size_of::<Scratchpad>() + self.content.len()
     }
+
+    pub fn counter(&self) -> u64 {
+        self.counter
+    }
 }
 
 impl<T, V: Content> PlaintextScratchpad<T, V> {
@@ -263,6 +268,13 @@ impl<T, V: Content> TypedOwnedScratchpad<T, V> {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ScratchpadState {
+    Missing,
+    Live,
+    Retired,
+}
+
 pub(crate) trait ScratchpadExt {
     fn is_mutable(&self) -> bool;
     fn is_retired(&self) -> bool;
@@ -280,47 +292,7 @@ impl ScratchpadExt for Scratchpad {
     }
 }
 
-impl Core {
-    /// Creates a new **ENCRYPTED** scratchpad owned by the given owner yet readable by `R`.
-    pub(crate) async fn create_encrypted_scratchpad<
-        O: Clone + PartialEq,
-        R,
-        V: Content,
-        S: EncryptionScheme,
-    >(
-        &self,
-        pad: TypedOwnedScratchpad<O, EncryptedContent<R, V, S>>,
-        //encrypted_content: EncryptedScratchpadContent<R, V, S>,
-        //owner: TypedOwnedScratchpad<O, EncryptedData<R, V, S>>,
-        receipt: &mut Receipt,
-    ) -> anyhow::Result<TypedScratchpadAddress<O, EncryptedContent<R, V, S>>> {
-        self.create_scratchpad(pad, receipt).await
-    }
-
-    /// Creates a new **PLAINTEXT** scratchpad owned by the given owner.
-    async fn create_scratchpad<T: Clone + PartialEq, V: Content>(
-        &self,
-        pad: TypedOwnedScratchpad<T, V>,
-        receipt: &mut Receipt,
-    ) -> anyhow::Result<TypedScratchpadAddress<T, V>> {
-        let pad = pad.try_into_scratchpad()?;
-        if self.scratchpad_cache.contains_key(pad.address())
-            || self
-                .client
-                .scratchpad_check_existance(pad.address())
-                .await?
-        {
-            bail!("scratchpad already exists");
-        }
-        let address = pad.address().clone();
-        let res = self.client.scratchpad_put(pad, self.payment()).await;
-        self.scratchpad_cache.invalidate(&address).await;
-        let (attos, address) = res?;
-        receipt.add(attos);
-
-        Ok(TypedScratchpadAddress::new(address))
-    }
-
+impl ReadOnlyCore {
     pub(crate) async fn read_scratchpad<T, V: Content>(
         &self,
         address: &TypedScratchpadAddress<T, V>,
@@ -333,7 +305,10 @@ impl Core {
             .await?
             .map(|s| s.try_into_inner())
             .transpose()?
-            .ok_or(anyhow!("scratchpad not found"))?)
+            .ok_or(Error::NotFound {
+                kind: RecordKind::Scratchpad,
+                address: address.as_ref().to_hex(),
+            })?)
     }
 
     pub(crate) async fn get_scratchpad<T, V: Content>(
@@ -347,28 +322,92 @@ impl Core {
             .transpose()?)
     }
 
+    /// Makes sure the next read of the scratchpad reaches the storage.
+    pub(crate) async fn invalidate_scratchpad<T, V>(
+        &self,
+        address: &TypedScratchpadAddress<T, V>,
+    ) -> anyhow::Result<()> {
+        self.scratchpad_cache.invalidate(address.as_ref()).await;
+        self.storage.scratchpad_invalidate(address.as_ref()).await
+    }
+
+    /// Retrieves the current counter, bypassing the cache.
+    pub(crate) async fn scratchpad_counter<T, V>(
+        &self,
+        address: &TypedScratchpadAddress<T, V>,
+    ) -> anyhow::Result<Option<u64>> {
+        self.invalidate_scratchpad(address).await?;
+        Ok(self
+            ._scratchpad_get(address.as_ref())
+            .await?
+            .map(|pad| pad.counter()))
+    }
+
+    /// Tells apart missing, live & retired scratchpads, regardless of their content.
+    ///
+    /// Bypasses the cache, key rotations & verification act on the state.
+    pub(crate) async fn scratchpad_state<T, V>(
+        &self,
+        address: &TypedScratchpadAddress<T, V>,
+    ) -> anyhow::Result<ScratchpadState> {
+        self.invalidate_scratchpad(address).await?;
+        Ok(match self._scratchpad_get(address.as_ref()).await? {
+            None => ScratchpadState::Missing,
+            Some(pad) if pad.is_retired() => ScratchpadState::Retired,
+            Some(_) => ScratchpadState::Live,
+        })
+    }
+
     async fn _scratchpad_get(
         &self,
         address: &ScratchpadAddress,
     ) -> anyhow::Result<Option<Scratchpad>> {
         self.scratchpad_cache
-            .try_get_with_by_ref(address, self._scratchpad_get_live(address))
+            .try_get_with_by_ref(address, self.storage.scratchpad_get(address))
             .await
-            .map_err(|e| anyhow!("{}", e))
+            .map_err(unshare)
     }
+}
 
-    async fn _scratchpad_get_live(
+impl Core {
+    /// Creates a new **ENCRYPTED** scratchpad owned by the given owner yet readable by `R`.
+    pub(crate) async fn create_encrypted_scratchpad<
+        O: Clone + PartialEq,
+        R,
+        V: Content,
+        S: EncryptionScheme,
+    >(
         &self,
-        address: &ScratchpadAddress,
-    ) -> anyhow::Result<Option<Scratchpad>> {
-        if !self.client.scratchpad_check_existance(address).await? {
-            return Ok(None);
+        pad: TypedOwnedScratchpad<O, EncryptedContent<R, V, S>>,
+        //encrypted_content: EncryptedScratchpadContent<R, V, S>,
+        //owner: TypedOwnedScratchpad<O, EncryptedData<R, V, S>>,
+        receipt: &mut Receipt,
+    ) -> anyhow::Result<TypedScratchpadAddress<O, EncryptedContent<R, V, S>>> {
+        self.create_scratchpad(pad, receipt).await
+    }
+
+    /// Creates a new **PLAINTEXT** scratchpad owned by the given owner.
+    async fn create_scratchpad<T: Clone + PartialEq, V: Content>(
+        &self,
+        pad: TypedOwnedScratchpad<T, V>,
+        receipt: &mut Receipt,
+    ) -> anyhow::Result<TypedScratchpadAddress<T, V>> {
+        let pad = pad.try_into_scratchpad()?;
+        if self.scratchpad_cache.contains_key(pad.address())
+            || self.storage.scratchpad_exists(pad.address()).await?
+        {
+            bail!("scratchpad already exists");
         }
-        Ok(Some(
-            self.client
-                .scratchpad_get_from_public_key(address.owner())
-                .await?,
-        ))
+        let address = pad.address().clone();
+        let payment = self
+            .authorize_payment(receipt, self.storage.scratchpad_cost(&pad))
+            .await?;
+        let res = self.storage.scratchpad_put(pad, payment).await;
+        self.scratchpad_cache.invalidate(&address).await;
+        let (attos, address) = res?;
+        self.record_payment(receipt, RecordKind::Scratchpad, address.to_hex(), attos);
+
+        Ok(TypedScratchpadAddress::new(address))
     }
 
     pub(crate) async fn update_scratchpad<T: Clone + PartialEq, V: Content>(
@@ -408,6 +447,42 @@ impl Core {
         Ok(counter)
     }
 
+    /// Writes `pad` as the successor of version `counter`, i.e. with exactly `counter + 1`.
+    ///
+    /// Unlike [`Core::update_scratchpad`], the counter is not derived from whatever version
+    /// is current at the time of writing. The network does not accept a counter lower than
+    /// or equal to the one it holds, so of two writers succeeding the same version, only
+    /// the first one gets through.
+    pub(crate) async fn update_scratchpad_after<T: Clone + PartialEq, V: Content>(
+        &self,
+        mut pad: TypedOwnedScratchpad<T, V>,
+        counter: u64,
+        receipt: &mut Receipt,
+    ) -> anyhow::Result<u64> {
+        let existing = self
+            ._scratchpad_get(pad.address().as_ref())
+            .await?
+            .ok_or(anyhow!("scratchpad does not exist"))?;
+        if existing.is_retired() {
+            bail!("scratchpad is retired");
+        }
+        if !existing.is_mutable() {
+            bail!("scratchpad is immutable");
+        }
+
+        pad.inner.counter = counter;
+        if pad.is_equivalent(&existing) {
+            // already up-to-date
+            // no need to send to the network
+            return Ok(counter);
+        }
+
+        pad.inner.counter = counter + 1;
+        self._scratchpad_put(pad.try_into_scratchpad()?, receipt)
+            .await?;
+        Ok(counter + 1)
+    }
+
     pub(crate) async fn danger_retire_scratchpad<
         T: Clone + PartialEq,
         V: ScratchpadContent + Retirable,
@@ -434,10 +509,13 @@ impl Core {
 
     async fn _scratchpad_put(&self, pad: Scratchpad, receipt: &mut Receipt) -> anyhow::Result<()> {
         let address = pad.address().clone();
-        let res = self.client.scratchpad_put(pad, self.payment()).await;
+        let payment = self
+            .authorize_payment(receipt, self.storage.scratchpad_cost(&pad))
+            .await?;
+        let res = self.storage.scratchpad_put(pad, payment).await;
         self.scratchpad_cache.invalidate(&address).await;
         let (attos, _) = res?;
-        receipt.add(attos);
+        self.record_payment(receipt, RecordKind::Scratchpad, address.to_hex(), attos);
         Ok(())
     }
 }
@@ -4,8 +4,12 @@ use crate::crypto::{
     TypedDerivationIndex, TypedEncryptor, TypedOwnedRegister, TypedOwnedScratchpad, TypedPublicKey,
     TypedRegister, TypedRegisterAddress, TypedScratchpadAddress, TypedSecretKey,
 };
+use crate::journal::{JournalOperation, JournalStep};
 use crate::progress::Task;
-use crate::{ArkAddress, ArkSeed, Core, Progress, Receipt, crypto, with_receipt};
+use crate::{
+    ArkAddress, ArkSeed, Core, Error, KeyKind, Progress, ReadOnlyCore, Receipt, RecordKind, crypto,
+    with_quote, with_receipt,
+};
 use anyhow::{anyhow, bail};
 use autonomi::register::RegisterAddress;
 use once_cell::sync::Lazy;
@@ -13,13 +17,13 @@ use std::ops::Deref;
 
 const DATA_KEYRING_SCRATCHPAD_ENCODING: u64 = 845573457394578892;
 
-const DATA_KEYRING_NAME: &str = "/ark/v0/data/keyring/scratchpad";
+pub(crate) const DATA_KEYRING_NAME: &str = "/ark/v0/data/keyring/scratchpad";
 static DATA_KEYRING_DERIVATION_IDX: Lazy<DataKeyringDerivator> =
     Lazy::new(|| DataKeyringDerivator::from_name(DATA_KEYRING_NAME));
 
 type DataKeyringDerivator = TypedDerivationIndex<DataKeyRing>;
 
-const DATA_REGISTER_NAME: &str = "/ark/v0/data/register";
+pub(crate) const DATA_REGISTER_NAME: &str = "/ark/v0/data/register";
 static DATA_REGISTER_DERIVATOR: Lazy<DataRegisterDerivator> =
     Lazy::new(|| DataRegisterDerivator::from_name(DATA_REGISTER_NAME));
 
@@ -135,7 +139,7 @@ impl ArkAddress {
     }
 }
 
-impl Core {
+impl ReadOnlyCore {
     pub(super) async fn get_data_keyring(&self, data_key: &DataKey) -> anyhow::Result<DataKeyRing> {
         self.verify_data_key(data_key).await?;
         data_key.decrypt_data_keyring(
@@ -149,35 +153,29 @@ impl Core {
     /// Ensures the key is the current, active one for the Ark.
     pub(super) async fn verify_data_key(&self, data_key: &DataKey) -> anyhow::Result<()> {
         if &self.seal_key().await? != data_key.public_key() {
-            bail!("data_key not valid for ark [{}]", self.ark_address)
+            return Err(Error::InvalidKey {
+                key: KeyKind::DataKey,
+                ark_address: self.ark_address.clone(),
+            }
+            .into());
         }
         Ok(())
     }
 
-    /// Retrieves the active `SealKey`.
+    /// Retrieves the active `SealKey`, bypassing the cache.
     pub(super) async fn seal_key(&self) -> anyhow::Result<SealKey> {
-        Ok(self.ark_address.seal_key(
-            &self
-                .read_register(&DataRegister::derive_address(&self.ark_address))
-                .await?,
-        ))
+        let address = DataRegister::derive_address(&self.ark_address);
+        self.invalidate_register(&address).await?;
+        Ok(self
+            .ark_address
+            .seal_key(&self.read_register(&address).await?))
     }
 
-    /// Does a full refresh of the data keyring.
-    pub async fn update_data_keyring(&self, ark_seed: &ArkSeed) -> crate::Result<u64> {
-        with_receipt(async move |receipt| {
-            self.verify_ark_seed(ark_seed)?;
-            self.update_scratchpad(
-                ark_seed.data_keyring(
-                    self.seal_key()
-                        .await?
-                        .encrypt_data_keyring(&self.derive_data_keyring(&ark_seed).await?)?,
-                ),
-                receipt,
-            )
-            .await
-        })
-        .await
+    /// Retrieves the active `DataKey`, bypassing the cache.
+    pub(super) async fn data_key(&self, ark_seed: &ArkSeed) -> anyhow::Result<DataKey> {
+        let address = DataRegister::derive_address(ark_seed.address());
+        self.invalidate_register(&address).await?;
+        Ok(ark_seed.data_key(&self.read_register(&address).await?))
     }
 
     /// Retrieves the **FULL** data key history given a valid `ArkSeed`.
@@ -186,8 +184,10 @@ impl Core {
         ark_seed: &ArkSeed,
     ) -> anyhow::Result<DataKeyRing> {
         self.verify_ark_seed(ark_seed)?;
+        let address = DataRegister::derive_address(&self.ark_address);
+        self.invalidate_register(&address).await?;
         let keyring: DataKeyRing = self
-            .register_history(&DataRegister::derive_address(&self.ark_address))
+            .register_history(&address)
             .await?
             .into_iter()
             .map(|seed| ark_seed.data_key(seed.as_ref()))
@@ -198,6 +198,74 @@ impl Core {
         Ok(keyring)
     }
 
+    /// Checks if the keyring is readable with `data_key` and holds all `expected_len` keys.
+    ///
+    /// Only a failed decryption means "not sealed", any other error is returned.
+    async fn is_keyring_sealed(
+        &self,
+        data_key: &DataKey,
+        expected_len: usize,
+    ) -> anyhow::Result<bool> {
+        self.invalidate_scratchpad(&self.ark_address.data_keyring())
+            .await?;
+        match self.get_data_keyring(data_key).await {
+            Ok(keyring) => Ok(keyring.len() == expected_len),
+            Err(err) if is_decryption_failure(&err) => Ok(false),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Checks if the manifest is readable with `data_key`.
+    ///
+    /// Only a failed decryption means "not sealed", any other error is returned.
+    async fn is_manifest_sealed(&self, data_key: &DataKey) -> anyhow::Result<bool> {
+        self.invalidate_scratchpad(&self.public_helm_key().await?.manifest())
+            .await?;
+        match self.get_manifest(data_key).await {
+            Ok(_) => Ok(true),
+            Err(err) if is_decryption_failure(&err) => Ok(false),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Estimates the cost of [`Core::rotate_data_key`] without spending anything.
+    pub fn quote_rotate_data_key<'a>(
+        &'a self,
+        ark_seed: &'a ArkSeed,
+    ) -> (
+        Progress,
+        impl Future<Output = anyhow::Result<Receipt>> + Send + 'a,
+    ) {
+        let (progress, task) = Progress::new(1, "Data Key Rotation Quote".to_string());
+        (
+            progress,
+            with_quote("Data Key Rotation", async move |receipt| {
+                self.quoting()
+                    ._rotate_data_key(ark_seed, receipt, task)
+                    .await
+            }),
+        )
+    }
+}
+
+impl Core {
+    /// Does a full refresh of the data keyring.
+    pub async fn update_data_keyring(&self, ark_seed: &ArkSeed) -> crate::Result<u64> {
+        with_receipt("Data Keyring Update", async move |receipt| {
+            self.verify_ark_seed(ark_seed)?;
+            self.update_scratchpad(
+                ark_seed.data_keyring(
+                    self.seal_key()
+                        .await?
+                        .encrypt_data_keyring(&self.derive_data_keyring(&ark_seed).await?)?,
+                ),
+                receipt,
+            )
+            .await
+        })
+        .await
+    }
+
     pub fn rotate_data_key<'a>(
         &'a self,
         ark_seed: &'a ArkSeed,
@@ -205,13 +273,23 @@ impl Core {
         Progress,
         impl Future<Output = crate::Result<DataKey>> + Send + 'a,
     ) {
-        let (progress, task) = Progress::new(1, "Data Key Rotation".to_string());
+        let (progress, mut task) = Progress::new(1, "Data Key Rotation".to_string());
         (
             progress,
-            with_receipt(async move |receipt| self._rotate_data_key(ark_seed, receipt, task).await),
+            with_receipt("Data Key Rotation", async move |receipt| {
+                self.check_budget(receipt, &mut task, async |core, receipt, task| {
+                    core._rotate_data_key(ark_seed, receipt, task).await
+                })
+                .await?;
+                self._rotate_data_key(ark_seed, receipt, task).await
+            }),
         )
     }
 
+    /// Rotates the data key or, if an earlier rotation was interrupted, rolls it forward.
+    ///
+    /// An interrupted rotation is detected from the journal as well as from the network:
+    /// a keyring or manifest that is not yet sealed with the current data key.
     pub(super) async fn _rotate_data_key(
         &self,
         ark_seed: &ArkSeed,
@@ -229,42 +307,161 @@ impl Core {
         self.verify_ark_seed(ark_seed)?;
         verify_seed.complete();
 
+        let mut journal = self.journal(JournalOperation::DataKeyRotation)?;
+
         read_current.start();
-        let mut data_register = self
-            .get_register(&DataRegister::derive_address(ark_seed.address()))
-            .await?
-            .ok_or(anyhow!("data register not found"))?
-            .into_owned(ark_seed)?;
+        let address = DataRegister::derive_address(ark_seed.address());
+        self.invalidate_register(&address).await?;
+        let history = self.register_history(&address).await?;
+        let current_data_key = ark_seed.data_key(
+            history
+                .last()
+                .ok_or(anyhow!("data register is empty"))?
+                .as_ref(),
+        );
+        let in_progress = history.len() > 1
+            && (!self
+                .is_keyring_sealed(&current_data_key, history.len())
+                .await?
+                || !self.is_manifest_sealed(&current_data_key).await?);
         read_current.complete();
 
-        update_key.start();
-        data_register.update(DataKeySeed::random())?;
-        update_key += 1;
-        let new_data_key = ark_seed.data_key(data_register.value());
-        self.update_register(data_register, receipt).await?;
-        update_key.complete();
+        let new_data_key = if in_progress {
+            // the register has already been updated, roll forward
+            update_key.complete();
+            current_data_key
+        } else if journal.is_done(JournalStep::RegisterUpdated) {
+            // all network writes have succeeded already
+            task.complete();
+            journal.finish()?;
+            return Ok(current_data_key);
+        } else {
+            update_key.start();
+            let mut data_register = self
+                .get_register(&DataRegister::derive_address(ark_seed.address()))
+                .await?
+                .ok_or(Error::NotFound {
+                    kind: RecordKind::Register,
+                    address: DataRegister::derive_address(ark_seed.address())
+                        .as_ref()
+                        .to_hex(),
+                })?
+                .into_owned(ark_seed)?;
+            data_register.update(DataKeySeed::random())?;
+            update_key += 1;
+            let new_data_key = ark_seed.data_key(data_register.value());
+            self.update_register(data_register, receipt).await?;
+            journal.record(JournalStep::RegisterUpdated)?;
+            update_key.complete();
+            new_data_key
+        };
 
         update_keyring.start();
-        self.update_scratchpad(
-            ark_seed.data_keyring(
-                new_data_key
-                    .public_key()
-                    .encrypt_data_keyring(&self.derive_data_keyring(&ark_seed).await?)?,
-            ),
-            receipt,
-        )
-        .await?;
+        let keyring = self.derive_data_keyring(&ark_seed).await?;
+        if !self.is_keyring_sealed(&new_data_key, keyring.len()).await? {
+            self.update_scratchpad(
+                ark_seed.data_keyring(new_data_key.public_key().encrypt_data_keyring(&keyring)?),
+                receipt,
+            )
+            .await?;
+        }
+        journal.record(JournalStep::KeyringUpdated)?;
         update_keyring.complete();
 
         update_manifest.start();
-        let manifest = self.get_manifest(ark_seed).await?;
-        update_manifest += 1;
-        let helm_key = self.helm_key(ark_seed).await?;
-        update_manifest += 1;
-        self.update_manifest(&manifest, &helm_key, receipt).await?;
+        if !self.is_manifest_sealed(&new_data_key).await? {
+            let (manifest, counter) = self.get_manifest_for_update(ark_seed).await?;
+            update_manifest += 1;
+            let helm_key = self.helm_key(ark_seed).await?;
+            update_manifest += 1;
+            self.update_manifest(&manifest, counter, &helm_key, receipt)
+                .await?;
+        }
+        journal.record(JournalStep::ManifestUpdated)?;
         update_manifest.complete();
 
+        journal.finish()?;
         task.complete();
         Ok(new_data_key)
     }
 }
+
+fn is_decryption_failure(err: &anyhow::Error) -> bool {
+    err.chain().any(|cause| {
+        matches!(
+            cause.downcast_ref::<Error>(),
+            Some(Error::DecryptionFailed(_))
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ArkCreationDetails, ArkCreationSettings, EvmWallet, MemoryStorage, Storage};
+
+    async fn create_ark() -> (ArkSeed, ArkCreationDetails, Core) {
+        let storage: Storage = MemoryStorage::new().into();
+        let wallet = EvmWallet::new_with_random_wallet(autonomi::Network::ArbitrumSepoliaTest);
+        let (ark_seed, _) = ArkSeed::random(None);
+        let settings = ArkCreationSettings::builder().name("Test Ark").build();
+        let Ok((details, _)) = Core::create_ark(settings, &ark_seed, &storage, &wallet, None)
+            .1
+            .await
+        else {
+            panic!("ark creation failed");
+        };
+        let core = Core::builder()
+            .storage(storage)
+            .wallet(wallet)
+            .ark_address(ark_seed.address().clone())
+            .build();
+        (ark_seed, details, core)
+    }
+
+    #[tokio::test]
+    async fn rotate_data_key() {
+        let (ark_seed, details, core) = create_ark().await;
+        let previous = details.data_key.clone();
+
+        let Ok((data_key, _)) = core.rotate_data_key(&ark_seed).1.await else {
+            panic!("data key rotation failed");
+        };
+        assert_ne!(data_key, previous);
+        assert!(core.verify_data_key(&previous).await.is_err());
+        // the keyring holds every data key ever used, so existing data stays readable
+        let keyring = core.get_data_keyring(&data_key).await.unwrap();
+        assert_eq!(keyring.len(), 2);
+        assert!(core.is_manifest_sealed(&data_key).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn roll_forward_interrupted_data_key_rotation() {
+        let (ark_seed, _, core) = create_ark().await;
+        let address = DataRegister::derive_address(ark_seed.address());
+
+        // an earlier rotation only got as far as updating the register
+        let mut data_register = core
+            .get_register(&address)
+            .await
+            .unwrap()
+            .unwrap()
+            .into_owned(&ark_seed)
+            .unwrap();
+        data_register.update(DataKeySeed::random()).unwrap();
+        let pending = ark_seed.data_key(data_register.value());
+        core.update_register(data_register, &mut Receipt::new("Interrupted"))
+            .await
+            .unwrap();
+        assert!(!core.is_keyring_sealed(&pending, 2).await.unwrap());
+        assert!(!core.is_manifest_sealed(&pending).await.unwrap());
+
+        let Ok((data_key, _)) = core.rotate_data_key(&ark_seed).1.await else {
+            panic!("data key rotation failed");
+        };
+        assert_eq!(data_key, pending);
+        assert_eq!(core.register_history(&address).await.unwrap().len(), 2);
+        assert!(core.is_keyring_sealed(&data_key, 2).await.unwrap());
+        assert!(core.is_manifest_sealed(&data_key).await.unwrap());
+    }
+}
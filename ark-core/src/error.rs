@@ -0,0 +1,204 @@
+use crate::{ArkAddress, BudgetExceeded, ManifestConflict, RecordKind, VaultAddress, VaultState};
+use anyhow::anyhow;
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use std::sync::Arc;
+use thiserror::Error;
+
+/// Errors returned by all costly `Core` operations.
+///
+/// Variants are stable, callers can rely on them for retries and user facing messages.
+/// Anything not covered by a dedicated variant is reported as [`Error::Other`].
+#[derive(Error, Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// The record does not exist (yet).
+    #[error("{kind} not found at [{address}]")]
+    NotFound { kind: RecordKind, address: String },
+    /// The vault is not part of the ark's manifest.
+    #[error("vault [{vault_address}] not found")]
+    VaultNotFound { vault_address: VaultAddress },
+    /// The vault exists but does not accept new uploads in its current state.
+    #[error("vault [{vault_address}] is {state} and does not accept uploads")]
+    VaultNotWritable {
+        vault_address: VaultAddress,
+        state: VaultState,
+    },
+    /// The vault is under legal hold, which prevents the requested change.
+    #[error("vault [{vault_address}] is under legal hold")]
+    VaultUnderLegalHold { vault_address: VaultAddress },
+    /// The given key is not, or no longer, the active one for the ark.
+    #[error("{key} not valid for ark [{ark_address}]")]
+    InvalidKey {
+        key: KeyKind,
+        ark_address: ArkAddress,
+    },
+    #[error("decryption failed: {0}")]
+    DecryptionFailed(String),
+    /// The wallet cannot cover the cost of a write.
+    #[error("insufficient funds: {0}")]
+    InsufficientFunds(String),
+    /// The network did not respond in time. Retrying may succeed.
+    #[error("network timeout: {0}")]
+    Timeout(String),
+    #[error(transparent)]
+    BudgetExceeded(#[from] BudgetExceeded),
+    #[error(transparent)]
+    ManifestConflict(#[from] ManifestConflict),
+    #[error(transparent)]
+    Other(anyhow::Error),
+}
+
+impl Error {
+    /// Whether retrying the same operation might succeed.
+    pub fn is_transient(&self) -> bool {
+        matches!(self, Self::Timeout(_) | Self::ManifestConflict(_))
+    }
+
+    fn try_clone(&self) -> Option<Self> {
+        Some(match self {
+            Self::NotFound { kind, address } => Self::NotFound {
+                kind: *kind,
+                address: address.clone(),
+            },
+            Self::VaultNotFound { vault_address } => Self::VaultNotFound {
+                vault_address: vault_address.clone(),
+            },
+            Self::VaultNotWritable {
+                vault_address,
+                state,
+            } => Self::VaultNotWritable {
+                vault_address: vault_address.clone(),
+                state: *state,
+            },
+            Self::VaultUnderLegalHold { vault_address } => Self::VaultUnderLegalHold {
+                vault_address: vault_address.clone(),
+            },
+            Self::InvalidKey { key, ark_address } => Self::InvalidKey {
+                key: *key,
+                ark_address: ark_address.clone(),
+            },
+            Self::DecryptionFailed(msg) => Self::DecryptionFailed(msg.clone()),
+            Self::InsufficientFunds(msg) => Self::InsufficientFunds(msg.clone()),
+            Self::Timeout(msg) => Self::Timeout(msg.clone()),
+            Self::BudgetExceeded(err) => Self::BudgetExceeded(err.clone()),
+            Self::ManifestConflict(err) => Self::ManifestConflict(err.clone()),
+            Self::Other(_) => return None,
+        })
+    }
+
+    /// Finds the first typed error within the chain of `err`.
+    fn find(err: &anyhow::Error) -> Option<Self> {
+        err.chain().find_map(|cause| {
+            if let Some(err) = cause.downcast_ref::<Error>() {
+                err.try_clone()
+            } else if let Some(err) = cause.downcast_ref::<BudgetExceeded>() {
+                Some(err.clone().into())
+            } else if let Some(err) = cause.downcast_ref::<ManifestConflict>() {
+                Some(err.clone().into())
+            } else {
+                None
+            }
+        })
+    }
+}
+
+impl From<anyhow::Error> for Error {
+    fn from(err: anyhow::Error) -> Self {
+        let err = match err.downcast::<Error>() {
+            Ok(err) => return err,
+            Err(err) => err,
+        };
+        Self::find(&err).unwrap_or(Self::Other(err))
+    }
+}
+
+/// Errors of failed cache loads are shared between all waiting callers.
+/// Retains their type wherever possible.
+pub(crate) fn unshare(err: Arc<anyhow::Error>) -> anyhow::Error {
+    match Arc::try_unwrap(err) {
+        Ok(err) => err,
+        Err(err) => match Error::find(&err) {
+            Some(typed) => typed.into(),
+            None => anyhow!("{:#}", err),
+        },
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KeyKind {
+    ArkSeed,
+    HelmKey,
+    DataKey,
+    WorkerKey,
+    BridgeKey,
+}
+
+impl Display for KeyKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::ArkSeed => "ark_seed",
+            Self::HelmKey => "helm_key",
+            Self::DataKey => "data_key",
+            Self::WorkerKey => "worker_key",
+            Self::BridgeKey => "bridge_key",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ArkSeed;
+    use anyhow::Context;
+    use autonomi::AttoTokens;
+
+    fn invalid_key() -> Error {
+        let (ark_seed, _) = ArkSeed::random(None);
+        Error::InvalidKey {
+            key: KeyKind::HelmKey,
+            ark_address: ark_seed.address().clone(),
+        }
+    }
+
+    #[test]
+    fn keep_typed_error_behind_context() {
+        let err = anyhow::Error::from(invalid_key()).context("unable to rotate helm key");
+        assert!(matches!(
+            Error::from(err),
+            Error::InvalidKey {
+                key: KeyKind::HelmKey,
+                ..
+            }
+        ));
+
+        let err = Err::<(), _>(BudgetExceeded::PerOperation {
+            limit: AttoTokens::from_u64(1),
+            total: AttoTokens::from_u64(2),
+        })
+        .context("checking budget")
+        .context("creating vault")
+        .unwrap_err();
+        assert!(matches!(
+            Error::from(err),
+            Error::BudgetExceeded(BudgetExceeded::PerOperation { .. })
+        ));
+    }
+
+    #[test]
+    fn report_anything_else_as_other() {
+        let err = Error::from(anyhow!("something went wrong"));
+        assert!(matches!(err, Error::Other(_)));
+        assert!(!err.is_transient());
+        assert_eq!(err.to_string(), "something went wrong");
+    }
+
+    #[test]
+    fn retry_transient_errors_only() {
+        assert!(Error::Timeout("slow".to_string()).is_transient());
+        assert!(!Error::InsufficientFunds("empty".to_string()).is_transient());
+        assert!(!invalid_key().is_transient());
+    }
+}
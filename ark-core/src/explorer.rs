@@ -0,0 +1,303 @@
+use crate::crypto::{ScratchpadExt, TypedRegisterAddress};
+use crate::data_key::{DATA_KEYRING_NAME, DATA_REGISTER_NAME, DataRegister};
+use crate::helm_key::{HELM_REGISTER_NAME, HelmRegister};
+use crate::manifest::MANIFEST_NAME;
+use crate::vault::{ARK_POINTER_NAME, ArkPointerAddress};
+use crate::{ArkAccessor, Progress, ReadOnlyCore, RecordKind, with_receipt};
+use autonomi::register::RegisterValue;
+use autonomi::{PointerAddress, ScratchpadAddress};
+use std::fmt::{Display, Formatter};
+
+/// A record whose address is derived from an Ark, as found on the network.
+#[derive(Debug, Clone)]
+pub struct DerivedRecord {
+    /// Name of the derivation index, e.g. `/ark/v0/helm/register`
+    pub name: &'static str,
+    /// What the address was derived from, e.g. `ark address` or `helm key #2`
+    pub derived_from: String,
+    pub kind: RecordKind,
+    pub address: String,
+    pub state: RecordState,
+    /// Scratchpad & pointer counter, or the number of values in a register's history
+    pub counter: Option<u64>,
+    /// Size in bytes, scratchpads only
+    pub size: Option<usize>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecordState {
+    Missing,
+    Live,
+    /// Exists but can no longer be updated
+    Finalized,
+    /// Replaced by a newer record and tombstoned
+    Retired,
+}
+
+impl Display for RecordState {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::Missing => "missing",
+            Self::Live => "live",
+            Self::Finalized => "finalized",
+            Self::Retired => "retired",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl ReadOnlyCore {
+    /// Lists every register, scratchpad & pointer derived from the Ark Address, including their state.
+    ///
+    /// Nothing is decrypted or modified. Vault pointers can only be found via the manifest,
+    /// they are listed if an `ark_accessor` is given.
+    pub fn explore_ark<'a>(
+        &'a self,
+        ark_accessor: Option<&'a ArkAccessor>,
+    ) -> (
+        Progress,
+        impl Future<Output = crate::Result<Vec<DerivedRecord>>> + Send + 'a,
+    ) {
+        let (progress, mut task) = Progress::new(1, "Explore Ark".to_string());
+        (
+            progress,
+            with_receipt("Explore Ark", async move |_| {
+                task.start();
+                let mut helm_task = task.child(1, "Helm Register & Manifests".to_string());
+                let mut data_task = task.child(1, "Data Register & Keyring".to_string());
+                let mut vault_task =
+                    ark_accessor.map(|_| task.child(1, "Vault Pointers".to_string()));
+
+                let mut records = vec![];
+
+                helm_task.start();
+                let helm_register = HelmRegister::derive_address(&self.ark_address);
+                let (record, helm_key_seeds) = self
+                    .explore_register(HELM_REGISTER_NAME, &helm_register)
+                    .await?;
+                records.push(record);
+                for (i, seed) in helm_key_seeds.iter().enumerate() {
+                    let manifest = self.ark_address.helm_key(seed).manifest();
+                    records.push(
+                        self.explore_scratchpad(
+                            MANIFEST_NAME,
+                            format!("helm key #{}", i + 1),
+                            manifest.as_ref(),
+                        )
+                        .await?,
+                    );
+                }
+                helm_task.complete();
+
+                data_task.start();
+                let data_register = DataRegister::derive_address(&self.ark_address);
+                let (record, _) = self
+                    .explore_register(DATA_REGISTER_NAME, &data_register)
+                    .await?;
+                records.push(record);
+                records.push(
+                    self.explore_scratchpad(
+                        DATA_KEYRING_NAME,
+                        "ark address".to_string(),
+                        self.ark_address.data_keyring().as_ref(),
+                    )
+                    .await?,
+                );
+                data_task.complete();
+
+                if let (Some(ark_accessor), Some(vault_task)) = (ark_accessor, vault_task.as_mut())
+                {
+                    vault_task.start();
+                    let manifest = self.get_manifest(ark_accessor).await?;
+                    for vault in manifest.vaults {
+                        let pointer = ArkPointerAddress::from(vault.address.clone());
+                        records.push(
+                            self.explore_pointer(
+                                ARK_POINTER_NAME,
+                                format!("vault [{}]", vault.name),
+                                pointer.as_ref(),
+                            )
+                            .await?,
+                        );
+                    }
+                    vault_task.complete();
+                }
+
+                task.complete();
+                Ok(records)
+            }),
+        )
+    }
+
+    /// Returns the register as a [`DerivedRecord`], alongside its decoded history.
+    async fn explore_register<T, V: TryFrom<RegisterValue>>(
+        &self,
+        name: &'static str,
+        address: &TypedRegisterAddress<T, V>,
+    ) -> anyhow::Result<(DerivedRecord, Vec<V>)>
+    where
+        <V as TryFrom<RegisterValue>>::Error: Send + Sync + Display,
+    {
+        let history = if self.storage.register_exists(address.as_ref()).await? {
+            self.register_history(address)
+                .await?
+                .into_iter()
+                .map(|v| v.into_inner())
+                .collect::<Vec<_>>()
+        } else {
+            vec![]
+        };
+        let record = DerivedRecord {
+            name,
+            derived_from: "ark address".to_string(),
+            kind: RecordKind::Register,
+            address: address.as_ref().to_hex(),
+            state: if history.is_empty() {
+                RecordState::Missing
+            } else {
+                RecordState::Live
+            },
+            counter: (!history.is_empty()).then_some(history.len() as u64),
+            size: None,
+        };
+        Ok((record, history))
+    }
+
+    async fn explore_scratchpad(
+        &self,
+        name: &'static str,
+        derived_from: String,
+        address: &ScratchpadAddress,
+    ) -> anyhow::Result<DerivedRecord> {
+        let pad = self.storage.scratchpad_get(address).await?;
+        Ok(DerivedRecord {
+            name,
+            derived_from,
+            kind: RecordKind::Scratchpad,
+            address: address.to_hex(),
+            state: match &pad {
+                None => RecordState::Missing,
+                Some(pad) if pad.is_retired() => RecordState::Retired,
+                Some(pad) if !pad.is_mutable() => RecordState::Finalized,
+                Some(_) => RecordState::Live,
+            },
+            counter: pad.as_ref().map(|p| p.counter()),
+            size: pad.as_ref().map(|p| p.size()),
+        })
+    }
+
+    async fn explore_pointer(
+        &self,
+        name: &'static str,
+        derived_from: String,
+        address: &PointerAddress,
+    ) -> anyhow::Result<DerivedRecord> {
+        let pointer = self.storage.pointer_get(address).await?;
+        Ok(DerivedRecord {
+            name,
+            derived_from,
+            kind: RecordKind::Pointer,
+            address: address.to_hex(),
+            state: match &pointer {
+                None => RecordState::Missing,
+                Some(pointer) if pointer.counter() == u32::MAX => RecordState::Finalized,
+                Some(_) => RecordState::Live,
+            },
+            counter: pointer.as_ref().map(|p| p.counter() as u64),
+            size: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        ArkCreationSettings, ArkSeed, Core, EvmWallet, MemoryStorage, ObjectType, Storage,
+        VaultCreationSettings,
+    };
+    use std::str::FromStr;
+
+    fn states(records: &[DerivedRecord]) -> Vec<(&'static str, RecordState)> {
+        records.iter().map(|r| (r.name, r.state)).collect()
+    }
+
+    #[tokio::test]
+    async fn explore_ark() {
+        let storage: Storage = MemoryStorage::new().into();
+        let wallet = EvmWallet::new_with_random_wallet(autonomi::Network::ArbitrumSepoliaTest);
+        let (ark_seed, _) = ArkSeed::random(None);
+        let settings = ArkCreationSettings::builder().name("Test Ark").build();
+        if Core::create_ark(settings, &ark_seed, &storage, &wallet, None)
+            .1
+            .await
+            .is_err()
+        {
+            panic!("ark creation failed");
+        }
+        let core = Core::builder()
+            .storage(storage)
+            .wallet(wallet)
+            .ark_address(ark_seed.address().clone())
+            .build();
+        let Ok((helm_key, _)) = core.rotate_helm_key(&ark_seed).1.await else {
+            panic!("helm key rotation failed");
+        };
+        let settings = VaultCreationSettings::builder()
+            .name("Test Vault")
+            .object_type(ObjectType::from_str("posix").unwrap())
+            .build();
+        if core.create_vault(settings, &helm_key).1.await.is_err() {
+            panic!("vault creation failed");
+        }
+
+        let Ok((records, _)) = core.explore_ark(None).1.await else {
+            panic!("exploration failed");
+        };
+        assert_eq!(
+            states(&records),
+            vec![
+                (HELM_REGISTER_NAME, RecordState::Live),
+                (MANIFEST_NAME, RecordState::Retired),
+                (MANIFEST_NAME, RecordState::Live),
+                (DATA_REGISTER_NAME, RecordState::Live),
+                (DATA_KEYRING_NAME, RecordState::Live),
+            ]
+        );
+        assert_eq!(records[0].counter, Some(2));
+        assert_eq!(records[1].derived_from, "helm key #1");
+        assert_eq!(records[3].counter, Some(1));
+
+        // vault pointers are only found via the manifest
+        let accessor = ArkAccessor::from(helm_key);
+        let Ok((records, _)) = core.explore_ark(Some(&accessor)).1.await else {
+            panic!("exploration failed");
+        };
+        assert_eq!(records.len(), 6);
+        assert_eq!(records[5].name, ARK_POINTER_NAME);
+        assert_eq!(records[5].kind, RecordKind::Pointer);
+        assert_eq!(records[5].state, RecordState::Finalized);
+        assert_eq!(records[5].derived_from, "vault [Test Vault]");
+    }
+
+    #[tokio::test]
+    async fn list_missing_records_of_unknown_ark() {
+        let (ark_seed, _) = ArkSeed::random(None);
+        let core = ReadOnlyCore::builder()
+            .storage(MemoryStorage::new())
+            .ark_address(ark_seed.address().clone())
+            .build();
+
+        let Ok((records, _)) = core.explore_ark(None).1.await else {
+            panic!("exploration failed");
+        };
+        assert_eq!(
+            states(&records),
+            vec![
+                (HELM_REGISTER_NAME, RecordState::Missing),
+                (DATA_REGISTER_NAME, RecordState::Missing),
+                (DATA_KEYRING_NAME, RecordState::Missing),
+            ]
+        );
+    }
+}
@@ -1,17 +1,21 @@
 use crate::ark_seed::ArkRoot;
 use crate::crypto::{
-    AllowDerivation, Bech32Secret, Derived, TypedDerivationIndex, TypedOwnedRegister,
-    TypedPublicKey, TypedRegister, TypedRegisterAddress, TypedSecretKey,
+    AllowDerivation, Bech32Secret, Derived, ScratchpadState, TypedDerivationIndex,
+    TypedOwnedRegister, TypedPublicKey, TypedRegister, TypedRegisterAddress, TypedSecretKey,
 };
-use crate::manifest::{EncryptedManifest, ManifestAddress, OwnedManifest};
+use crate::journal::{JournalOperation, JournalStep};
+use crate::manifest::{EncryptedManifest, ManifestAddress, ManifestEncryptor, OwnedManifest};
 use crate::progress::Task;
-use crate::{ArkAddress, ArkSeed, Core, Progress, Receipt, with_receipt};
-use anyhow::{anyhow, bail};
+use crate::{
+    ArkAddress, ArkSeed, Core, Error, KeyKind, Progress, ReadOnlyCore, Receipt, RecordKind,
+    with_quote, with_receipt,
+};
+use anyhow::anyhow;
 use autonomi::register::RegisterAddress;
 use once_cell::sync::Lazy;
 use std::ops::Deref;
 
-const HELM_REGISTER_NAME: &str = "/ark/v0/helm/register";
+pub(crate) const HELM_REGISTER_NAME: &str = "/ark/v0/helm/register";
 static HELM_REGISTER_DERIVATOR: Lazy<HelmRegisterDerivator> =
     Lazy::new(|| HelmRegisterDerivator::from_name(HELM_REGISTER_NAME));
 
@@ -95,34 +99,57 @@ impl ArkAddress {
     }
 }
 
-impl Core {
-    /// Retrieves the active `PublicHelmKey`.
+impl ReadOnlyCore {
+    /// Retrieves the active `PublicHelmKey`, bypassing the cache.
     pub(super) async fn public_helm_key(&self) -> anyhow::Result<PublicHelmKey> {
-        Ok(self.ark_address.helm_key(
-            &self
-                .read_register(&HelmRegister::derive_address(&self.ark_address))
-                .await?,
-        ))
+        let address = HelmRegister::derive_address(&self.ark_address);
+        self.invalidate_register(&address).await?;
+        Ok(self
+            .ark_address
+            .helm_key(&self.read_register(&address).await?))
     }
 
-    /// Retrieves the active `HelmKey`.
+    /// Retrieves the active `HelmKey`, bypassing the cache.
     pub(super) async fn helm_key(&self, ark_seed: &ArkSeed) -> anyhow::Result<HelmKey> {
-        Ok(ark_seed.helm_key(
-            &self
-                .read_register(&HelmRegister::derive_address(ark_seed.address()))
-                .await?,
-        ))
+        let address = HelmRegister::derive_address(ark_seed.address());
+        self.invalidate_register(&address).await?;
+        Ok(ark_seed.helm_key(&self.read_register(&address).await?))
     }
 
     /// Verify the given `helm_key` against the Ark.
     /// Ensures the key is the current, active one for the Ark.
     pub(super) async fn verify_helm_key(&self, helm_key: &HelmKey) -> anyhow::Result<()> {
         if &self.public_helm_key().await? != helm_key.public_key() {
-            bail!("helm_key not valid for ark [{}]", self.ark_address)
+            return Err(Error::InvalidKey {
+                key: KeyKind::HelmKey,
+                ark_address: self.ark_address.clone(),
+            }
+            .into());
         }
         Ok(())
     }
 
+    /// Estimates the cost of [`Core::rotate_helm_key`] without spending anything.
+    pub fn quote_rotate_helm_key<'a>(
+        &'a self,
+        ark_seed: &'a ArkSeed,
+    ) -> (
+        Progress,
+        impl Future<Output = anyhow::Result<Receipt>> + Send + 'a,
+    ) {
+        let (progress, task) = Progress::new(1, "Helm Key Rotation Quote".to_string());
+        (
+            progress,
+            with_quote("Helm Key Rotation", async move |receipt| {
+                self.quoting()
+                    ._rotate_helm_key(ark_seed, receipt, task)
+                    .await
+            }),
+        )
+    }
+}
+
+impl Core {
     pub fn rotate_helm_key<'a>(
         &'a self,
         ark_seed: &'a ArkSeed,
@@ -130,13 +157,23 @@ impl Core {
         Progress,
         impl Future<Output = crate::Result<HelmKey>> + Send + 'a,
     ) {
-        let (progress, task) = Progress::new(1, "Helm Key Rotation".to_string());
+        let (progress, mut task) = Progress::new(1, "Helm Key Rotation".to_string());
         (
             progress,
-            with_receipt(async move |receipt| self._rotate_helm_key(ark_seed, receipt, task).await),
+            with_receipt("Helm Key Rotation", async move |receipt| {
+                self.check_budget(receipt, &mut task, async |core, receipt, task| {
+                    core._rotate_helm_key(ark_seed, receipt, task).await
+                })
+                .await?;
+                self._rotate_helm_key(ark_seed, receipt, task).await
+            }),
         )
     }
 
+    /// Rotates the helm key or, if an earlier rotation was interrupted, rolls it forward.
+    ///
+    /// An interrupted rotation is detected from the journal as well as from the network:
+    /// a current helm key without manifest or a previous manifest that was never retired.
     pub(super) async fn _rotate_helm_key(
         &self,
         ark_seed: &ArkSeed,
@@ -156,42 +193,190 @@ impl Core {
         self.verify_ark_seed(ark_seed)?;
         verify_seed.complete();
 
+        let mut journal = self.journal(JournalOperation::HelmKeyRotation)?;
+
         read_current_keys.start();
-        let previous_helm_key = self.helm_key(ark_seed).await?;
+        let (current_helm_key, previous_helm_key) = {
+            let address = HelmRegister::derive_address(ark_seed.address());
+            self.invalidate_register(&address).await?;
+            let history = self.register_history(&address).await?;
+            let mut keys = history
+                .iter()
+                .rev()
+                .map(|seed| ark_seed.helm_key(seed.as_ref()));
+            (
+                keys.next().ok_or(anyhow!("helm register is empty"))?,
+                keys.next(),
+            )
+        };
+        let in_progress = match &previous_helm_key {
+            Some(previous_helm_key) => {
+                self.scratchpad_state(&current_helm_key.public_key().manifest())
+                    .await?
+                    != ScratchpadState::Live
+                    || self
+                        .scratchpad_state(&previous_helm_key.public_key().manifest())
+                        .await?
+                        != ScratchpadState::Retired
+            }
+            None => false,
+        };
         read_current_keys.complete();
 
-        read_manifest.start();
-        let manifest = self.get_manifest(&previous_helm_key).await?;
-        read_manifest += 1;
-        let mut manifest_encryptor = self.manifest_encryptor(&previous_helm_key).await?;
-        read_manifest.complete();
-
-        update_keys.start();
-        let new_helm_key_seed = HelmKeySeed::random();
-        let new_helm_key = ark_seed.helm_key(&new_helm_key_seed);
-        update_keys += 1;
-
-        let mut helm_register = self
-            .get_register(&HelmRegister::derive_address(ark_seed.public_key()))
-            .await?
-            .ok_or(anyhow!("helm register not found"))?
-            .into_owned(ark_seed)?;
-        helm_register.update(new_helm_key_seed)?;
-
-        self.update_register(helm_register, receipt).await?;
-        manifest_encryptor.public_helm_key = new_helm_key.public_key().clone();
-        update_keys.complete();
+        let (previous_helm_key, new_helm_key) = if in_progress {
+            // the register has already been updated, roll forward
+            read_manifest.complete();
+            update_keys.complete();
+            (
+                previous_helm_key.expect("previous helm key to be present"),
+                current_helm_key,
+            )
+        } else if journal.is_done(JournalStep::RegisterUpdated) {
+            // all network writes have succeeded already
+            task.complete();
+            journal.finish()?;
+            return Ok(current_helm_key);
+        } else {
+            read_manifest.start();
+            self.get_manifest(&current_helm_key).await?;
+            read_manifest.complete();
+
+            update_keys.start();
+            let new_helm_key_seed = HelmKeySeed::random();
+            let new_helm_key = ark_seed.helm_key(&new_helm_key_seed);
+            update_keys += 1;
+
+            let mut helm_register = self
+                .get_register(&HelmRegister::derive_address(ark_seed.public_key()))
+                .await?
+                .ok_or(Error::NotFound {
+                    kind: RecordKind::Register,
+                    address: HelmRegister::derive_address(ark_seed.public_key())
+                        .as_ref()
+                        .to_hex(),
+                })?
+                .into_owned(ark_seed)?;
+            helm_register.update(new_helm_key_seed)?;
+
+            self.update_register(helm_register, receipt).await?;
+            journal.record(JournalStep::RegisterUpdated)?;
+            update_keys.complete();
+            (current_helm_key, new_helm_key)
+        };
 
         new_manifest.start();
-        self.create_manifest(&manifest, &new_helm_key, &manifest_encryptor, receipt)
-            .await?;
+        if self
+            .scratchpad_state(&new_helm_key.public_key().manifest())
+            .await?
+            == ScratchpadState::Missing
+        {
+            let manifest = self
+                .get_specific_manifest(&previous_helm_key, previous_helm_key.public_key())
+                .await?;
+            let manifest_encryptor = ManifestEncryptor::new(
+                self.ark_address.clone(),
+                new_helm_key.public_key().clone(),
+                manifest.authorized_worker.clone(),
+                self.seal_key().await?,
+            );
+            self.create_manifest(&manifest, &new_helm_key, &manifest_encryptor, receipt)
+                .await?;
+        }
+        journal.record(JournalStep::ManifestCreated)?;
         new_manifest.complete();
 
         retire_previous.start();
-        self.retire_manifest(&previous_helm_key, receipt).await?;
+        if self
+            .scratchpad_state(&previous_helm_key.public_key().manifest())
+            .await?
+            == ScratchpadState::Live
+        {
+            self.retire_manifest(&previous_helm_key, receipt).await?;
+        }
+        journal.record(JournalStep::PreviousManifestRetired)?;
         retire_previous.complete();
 
+        journal.finish()?;
         task.complete();
         Ok(new_helm_key)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ArkCreationDetails, ArkCreationSettings, EvmWallet, MemoryStorage, Storage};
+
+    async fn create_ark() -> (ArkSeed, ArkCreationDetails, Core) {
+        let storage: Storage = MemoryStorage::new().into();
+        let wallet = EvmWallet::new_with_random_wallet(autonomi::Network::ArbitrumSepoliaTest);
+        let (ark_seed, _) = ArkSeed::random(None);
+        let settings = ArkCreationSettings::builder().name("Test Ark").build();
+        let Ok((details, _)) = Core::create_ark(settings, &ark_seed, &storage, &wallet, None)
+            .1
+            .await
+        else {
+            panic!("ark creation failed");
+        };
+        let core = Core::builder()
+            .storage(storage)
+            .wallet(wallet)
+            .ark_address(ark_seed.address().clone())
+            .build();
+        (ark_seed, details, core)
+    }
+
+    #[tokio::test]
+    async fn rotate_helm_key() {
+        let (ark_seed, details, core) = create_ark().await;
+        let previous = details.helm_key.clone();
+
+        let Ok((helm_key, _)) = core.rotate_helm_key(&ark_seed).1.await else {
+            panic!("helm key rotation failed");
+        };
+        assert_ne!(helm_key, previous);
+        core.verify_helm_key(&helm_key).await.unwrap();
+        assert!(core.verify_helm_key(&previous).await.is_err());
+        assert_eq!(core.get_manifest(&helm_key).await.unwrap().name, "Test Ark");
+        assert!(
+            core.scratchpad_state(&previous.public_key().manifest())
+                .await
+                .unwrap()
+                == ScratchpadState::Retired
+        );
+    }
+
+    #[tokio::test]
+    async fn roll_forward_interrupted_helm_key_rotation() {
+        let (ark_seed, details, core) = create_ark().await;
+        let previous = details.helm_key.clone();
+        let address = HelmRegister::derive_address(ark_seed.address());
+
+        // an earlier rotation only got as far as updating the register
+        let mut helm_register = core
+            .get_register(&address)
+            .await
+            .unwrap()
+            .unwrap()
+            .into_owned(&ark_seed)
+            .unwrap();
+        helm_register.update(HelmKeySeed::random()).unwrap();
+        let pending = ark_seed.helm_key(helm_register.value());
+        core.update_register(helm_register, &mut Receipt::new("Interrupted"))
+            .await
+            .unwrap();
+
+        let Ok((helm_key, _)) = core.rotate_helm_key(&ark_seed).1.await else {
+            panic!("helm key rotation failed");
+        };
+        assert_eq!(helm_key, pending);
+        assert_eq!(core.register_history(&address).await.unwrap().len(), 2);
+        assert_eq!(core.get_manifest(&helm_key).await.unwrap().name, "Test Ark");
+        assert!(
+            core.scratchpad_state(&previous.public_key().manifest())
+                .await
+                .unwrap()
+                == ScratchpadState::Retired
+        );
+    }
+}
@@ -0,0 +1,253 @@
+use crate::ArkAddress;
+use anyhow::Context;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use uuid::Uuid;
+
+const EXTENSION: &str = "json";
+
+/// Local record of multi-step operations that are still in progress.
+///
+/// Every completed step is persisted before the next one starts. If an operation
+/// gets interrupted, running it again picks up its entry and rolls forward
+/// instead of starting over. Entries are removed once the operation completes.
+///
+/// The journal only complements what can be detected from the network; it never
+/// contains secrets.
+#[derive(Clone, Debug)]
+pub struct Journal {
+    dir: Arc<PathBuf>,
+}
+
+impl Journal {
+    pub fn open(dir: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let dir = dir.as_ref().to_path_buf();
+        std::fs::create_dir_all(&dir)?;
+        Ok(Self { dir: Arc::new(dir) })
+    }
+
+    pub fn dir(&self) -> &Path {
+        self.dir.as_path()
+    }
+
+    /// All operations that were started but never completed.
+    pub fn pending(&self) -> anyhow::Result<Vec<JournalEntry>> {
+        let mut entries = vec![];
+        for file in std::fs::read_dir(self.dir.as_path())? {
+            let path = file?.path();
+            if path.extension().map(|e| e != EXTENSION).unwrap_or(true) {
+                continue;
+            }
+            if let Some(entry) = self.read(&path)? {
+                entries.push(entry);
+            }
+        }
+        entries.sort_by_key(|e| e.started);
+        Ok(entries)
+    }
+
+    fn path(&self, ark: &str, operation: JournalOperation) -> PathBuf {
+        self.dir
+            .join(format!("{}.{}.{}", ark, operation.file_name(), EXTENSION))
+    }
+
+    fn read(&self, path: &Path) -> anyhow::Result<Option<JournalEntry>> {
+        match std::fs::read(path) {
+            Ok(bytes) => Ok(Some(serde_json::from_slice(&bytes).with_context(|| {
+                format!("corrupt journal entry at [{}]", path.display())
+            })?)),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    fn write(&self, entry: &JournalEntry) -> anyhow::Result<()> {
+        let path = self.path(entry.ark(), entry.operation);
+        let tmp = path.with_extension(format!("{}.tmp", Uuid::now_v7()));
+        std::fs::write(&tmp, serde_json::to_vec_pretty(entry)?)?;
+        if let Err(err) = std::fs::rename(&tmp, &path) {
+            let _ = std::fs::remove_file(&tmp);
+            return Err(err.into());
+        }
+        Ok(())
+    }
+
+    /// Returns the pending entry for the given operation or starts a new one.
+    pub(crate) fn begin(
+        &self,
+        ark: &ArkAddress,
+        operation: JournalOperation,
+    ) -> anyhow::Result<JournalEntry> {
+        let ark = ark.to_string();
+        if let Some(entry) = self.read(&self.path(ark.as_str(), operation))? {
+            return Ok(entry);
+        }
+        let entry = JournalEntry {
+            ark,
+            operation,
+            started: Utc::now(),
+            steps: vec![],
+        };
+        self.write(&entry)?;
+        Ok(entry)
+    }
+
+    pub(crate) fn record(&self, entry: &mut JournalEntry, step: JournalStep) -> anyhow::Result<()> {
+        if entry.is_done(step) {
+            return Ok(());
+        }
+        entry.steps.push(CompletedStep {
+            step,
+            completed: Utc::now(),
+        });
+        self.write(entry)
+    }
+
+    pub(crate) fn finish(&self, entry: JournalEntry) -> anyhow::Result<()> {
+        match std::fs::remove_file(self.path(entry.ark(), entry.operation)) {
+            Ok(()) => Ok(()),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(()),
+            Err(err) => Err(err.into()),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct JournalEntry {
+    ark: String,
+    operation: JournalOperation,
+    started: DateTime<Utc>,
+    steps: Vec<CompletedStep>,
+}
+
+impl JournalEntry {
+    pub fn ark(&self) -> &str {
+        self.ark.as_str()
+    }
+
+    pub fn operation(&self) -> JournalOperation {
+        self.operation
+    }
+
+    pub fn started(&self) -> &DateTime<Utc> {
+        &self.started
+    }
+
+    /// Completed steps, in order.
+    pub fn steps(&self) -> impl Iterator<Item = JournalStep> {
+        self.steps.iter().map(|s| s.step)
+    }
+
+    pub fn is_done(&self, step: JournalStep) -> bool {
+        self.steps.iter().any(|s| s.step == step)
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct CompletedStep {
+    step: JournalStep,
+    completed: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JournalOperation {
+    HelmKeyRotation,
+    DataKeyRotation,
+    FullKeyRotation,
+}
+
+impl JournalOperation {
+    fn file_name(&self) -> &'static str {
+        match self {
+            Self::HelmKeyRotation => "helm_key_rotation",
+            Self::DataKeyRotation => "data_key_rotation",
+            Self::FullKeyRotation => "full_key_rotation",
+        }
+    }
+}
+
+impl Display for JournalOperation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::HelmKeyRotation => "Helm Key Rotation",
+            Self::DataKeyRotation => "Data Key Rotation",
+            Self::FullKeyRotation => "Full Ark Key Rotation",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JournalStep {
+    RegisterUpdated,
+    KeyringUpdated,
+    ManifestCreated,
+    ManifestUpdated,
+    PreviousManifestRetired,
+    HelmKeyRotated,
+    WorkerKeyRotated,
+    DataKeyRotated,
+}
+
+impl Display for JournalStep {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::RegisterUpdated => "register updated",
+            Self::KeyringUpdated => "keyring updated",
+            Self::ManifestCreated => "manifest created",
+            Self::ManifestUpdated => "manifest updated",
+            Self::PreviousManifestRetired => "previous manifest retired",
+            Self::HelmKeyRotated => "helm key rotated",
+            Self::WorkerKeyRotated => "worker key rotated",
+            Self::DataKeyRotated => "data key rotated",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Tracks a single operation in the journal of a `Core`, if it has one.
+pub(crate) struct JournalHandle {
+    journal: Option<Journal>,
+    entry: Option<JournalEntry>,
+}
+
+impl JournalHandle {
+    pub(crate) fn new(
+        journal: Option<&Journal>,
+        ark: &ArkAddress,
+        operation: JournalOperation,
+    ) -> anyhow::Result<Self> {
+        let entry = journal.map(|j| j.begin(ark, operation)).transpose()?;
+        Ok(Self {
+            journal: journal.cloned(),
+            entry,
+        })
+    }
+
+    pub(crate) fn is_done(&self, step: JournalStep) -> bool {
+        self.entry
+            .as_ref()
+            .map(|e| e.is_done(step))
+            .unwrap_or(false)
+    }
+
+    pub(crate) fn record(&mut self, step: JournalStep) -> anyhow::Result<()> {
+        if let (Some(journal), Some(entry)) = (&self.journal, &mut self.entry) {
+            journal.record(entry, step)?;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn finish(self) -> anyhow::Result<()> {
+        if let (Some(journal), Some(entry)) = (self.journal, self.entry) {
+            journal.finish(entry)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,359 @@
+use crate::{
+    ArkAccessor, ArkAddress, BridgeKey, ConfidentialString, DataKey, HelmKey, KeyKind, WorkerKey,
+};
+use age::secrecy::SecretString;
+use anyhow::{Context, anyhow, bail, ensure};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+const VERSION: u16 = 1;
+
+/// Local file holding named secret keys of any number of Arks.
+///
+/// The file is encrypted with a passphrase (age, scrypt). Every change is written
+/// to disk immediately. Keys are stored per Ark, their names only need to be unique
+/// within the same Ark.
+///
+/// The Ark Seed is never stored, it is meant to be kept offline.
+pub struct Keystore {
+    path: PathBuf,
+    passphrase: ConfidentialString,
+    records: Vec<Record>,
+}
+
+impl Keystore {
+    /// Creates a new, empty keystore. Fails if the file already exists.
+    pub fn create(path: impl AsRef<Path>, passphrase: ConfidentialString) -> anyhow::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        ensure!(
+            !path.exists(),
+            "keystore [{}] already exists",
+            path.display()
+        );
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let keystore = Self {
+            path,
+            passphrase,
+            records: vec![],
+        };
+        keystore.save()?;
+        Ok(keystore)
+    }
+
+    pub fn open(path: impl AsRef<Path>, passphrase: ConfidentialString) -> anyhow::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let ciphertext = std::fs::read(&path)
+            .with_context(|| format!("unable to read keystore [{}]", path.display()))?;
+        let identity =
+            age::scrypt::Identity::new(SecretString::from(passphrase.as_ref().to_string()));
+        let mut plaintext = age::decrypt(&identity, ciphertext.as_slice())
+            .context("unable to decrypt keystore, wrong passphrase?")?;
+        let res = serde_json::from_slice::<Contents>(&plaintext);
+        plaintext.zeroize();
+        let contents = res.context("invalid keystore contents")?;
+        ensure!(
+            contents.version == VERSION,
+            "unsupported keystore version [{}]",
+            contents.version
+        );
+        for record in &contents.keys {
+            ArkAddress::from_str(record.ark_address.as_str())
+                .context("invalid ark address in keystore")?;
+        }
+        Ok(Self {
+            path,
+            passphrase,
+            records: contents.keys,
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        self.path.as_path()
+    }
+
+    /// All stored keys, without their secrets.
+    pub fn entries(&self) -> Vec<KeystoreEntry> {
+        let mut records = self.records.iter().collect::<Vec<_>>();
+        records.sort_by(|a, b| (&a.ark_address, &a.name).cmp(&(&b.ark_address, &b.name)));
+        records.into_iter().map(Record::entry).collect()
+    }
+
+    pub fn add<K: KeystoreKey>(
+        &mut self,
+        ark_address: &ArkAddress,
+        name: impl AsRef<str>,
+        key: &K,
+    ) -> anyhow::Result<()> {
+        let name = name.as_ref().trim();
+        ensure!(!name.is_empty(), "key name must not be empty");
+        let ark_address = ark_address.to_string();
+        ensure!(
+            self.find(&ark_address, name).is_none(),
+            "key [{}] already exists for ark [{}]",
+            name,
+            ark_address
+        );
+        self.records.push(Record {
+            ark_address,
+            name: name.to_string(),
+            kind: K::KIND,
+            secret: key.to_bech32(),
+            added: Utc::now(),
+        });
+        self.save()
+    }
+
+    /// Adds a bech32 encoded Helm, Data, Worker or Bridge Key, detecting its kind.
+    pub fn import(
+        &mut self,
+        ark_address: &ArkAddress,
+        name: impl AsRef<str>,
+        secret: &ConfidentialString,
+    ) -> anyhow::Result<KeyKind> {
+        let secret = secret.as_ref().trim();
+        if let Ok(key) = HelmKey::from_str(secret) {
+            self.add(ark_address, name, &key)?;
+            return Ok(KeyKind::HelmKey);
+        }
+        if let Ok(key) = DataKey::from_str(secret) {
+            self.add(ark_address, name, &key)?;
+            return Ok(KeyKind::DataKey);
+        }
+        if let Ok(key) = WorkerKey::from_str(secret) {
+            self.add(ark_address, name, &key)?;
+            return Ok(KeyKind::WorkerKey);
+        }
+        if let Ok(key) = BridgeKey::from_str(secret) {
+            self.add(ark_address, name, &key)?;
+            return Ok(KeyKind::BridgeKey);
+        }
+        bail!("no valid secret given");
+    }
+
+    pub fn remove(
+        &mut self,
+        ark_address: &ArkAddress,
+        name: impl AsRef<str>,
+    ) -> anyhow::Result<KeystoreEntry> {
+        let ark_address = ark_address.to_string();
+        let name = name.as_ref().trim();
+        let pos = self
+            .records
+            .iter()
+            .position(|r| r.ark_address == ark_address && r.name == name)
+            .ok_or_else(|| not_found(&ark_address, name))?;
+        let record = self.records.remove(pos);
+        self.save()?;
+        Ok(record.entry())
+    }
+
+    pub fn get<K: KeystoreKey>(
+        &self,
+        ark_address: &ArkAddress,
+        name: impl AsRef<str>,
+    ) -> anyhow::Result<K> {
+        let ark_address = ark_address.to_string();
+        let name = name.as_ref().trim();
+        let record = self
+            .find(&ark_address, name)
+            .ok_or_else(|| not_found(&ark_address, name))?;
+        ensure!(
+            record.kind == K::KIND,
+            "key [{}] is a {}, not a {}",
+            name,
+            record.kind,
+            K::KIND
+        );
+        K::from_str(record.secret.as_str())
+    }
+
+    /// Returns a stored Helm, Data or Worker Key, whichever is stored under `name`.
+    pub fn get_accessor(
+        &self,
+        ark_address: &ArkAddress,
+        name: impl AsRef<str>,
+    ) -> anyhow::Result<ArkAccessor> {
+        let ark_address_str = ark_address.to_string();
+        let name = name.as_ref().trim();
+        let record = self
+            .find(&ark_address_str, name)
+            .ok_or_else(|| not_found(&ark_address_str, name))?;
+        Ok(match record.kind {
+            KeyKind::HelmKey => self.get::<HelmKey>(ark_address, name)?.into(),
+            KeyKind::DataKey => self.get::<DataKey>(ark_address, name)?.into(),
+            KeyKind::WorkerKey => self.get::<WorkerKey>(ark_address, name)?.into(),
+            other => bail!("key [{}] is a {}, which cannot access the ark", name, other),
+        })
+    }
+
+    fn find(&self, ark_address: &str, name: &str) -> Option<&Record> {
+        self.records
+            .iter()
+            .find(|r| r.ark_address == ark_address && r.name == name)
+    }
+
+    /// Replaces the file atomically, so an interrupted write never loses keys.
+    fn save(&self) -> anyhow::Result<()> {
+        let mut plaintext = serde_json::to_vec(&ContentsRef {
+            version: VERSION,
+            keys: &self.records,
+        })?;
+        let recipient =
+            age::scrypt::Recipient::new(SecretString::from(self.passphrase.as_ref().to_string()));
+        let res = age::encrypt(&recipient, plaintext.as_slice());
+        plaintext.zeroize();
+        let ciphertext = res?;
+
+        let tmp_path = self.path.with_extension("tmp");
+        let mut options = OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        let mut file = options.open(&tmp_path)?;
+        file.write_all(&ciphertext)?;
+        file.sync_data()?;
+        std::fs::rename(&tmp_path, &self.path)?;
+        Ok(())
+    }
+}
+
+fn not_found(ark_address: &str, name: &str) -> anyhow::Error {
+    anyhow!("key [{}] not found for ark [{}]", name, ark_address)
+}
+
+/// Publicly visible details of a key held in a [`Keystore`].
+#[derive(Debug, Clone)]
+pub struct KeystoreEntry {
+    pub ark_address: ArkAddress,
+    pub name: String,
+    pub kind: KeyKind,
+    pub added: DateTime<Utc>,
+}
+
+/// Secret key types a [`Keystore`] can hold.
+pub trait KeystoreKey: FromStr<Err = anyhow::Error> {
+    const KIND: KeyKind;
+
+    fn to_bech32(&self) -> String;
+}
+
+macro_rules! keystore_key {
+    ($key:ty, $kind:expr) => {
+        impl KeystoreKey for $key {
+            const KIND: KeyKind = $kind;
+
+            fn to_bech32(&self) -> String {
+                self.danger_to_string()
+            }
+        }
+    };
+}
+
+keystore_key!(HelmKey, KeyKind::HelmKey);
+keystore_key!(DataKey, KeyKind::DataKey);
+keystore_key!(WorkerKey, KeyKind::WorkerKey);
+keystore_key!(BridgeKey, KeyKind::BridgeKey);
+
+#[derive(Serialize, Deserialize, Zeroize, ZeroizeOnDrop)]
+struct Record {
+    #[zeroize(skip)]
+    ark_address: String,
+    #[zeroize(skip)]
+    name: String,
+    #[zeroize(skip)]
+    kind: KeyKind,
+    secret: String,
+    #[zeroize(skip)]
+    added: DateTime<Utc>,
+}
+
+impl Record {
+    fn entry(&self) -> KeystoreEntry {
+        KeystoreEntry {
+            ark_address: ArkAddress::from_str(self.ark_address.as_str())
+                .expect("ark address to be validated on open"),
+            name: self.name.clone(),
+            kind: self.kind,
+            added: self.added,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct Contents {
+    version: u16,
+    keys: Vec<Record>,
+}
+
+#[derive(Serialize)]
+struct ContentsRef<'a> {
+    version: u16,
+    keys: &'a [Record],
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ArkSeed;
+
+    fn passphrase(s: &str) -> ConfidentialString {
+        s.to_string().into()
+    }
+
+    #[test]
+    fn save_and_open() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("keys").join("keystore.age");
+        let (ark_seed, _) = ArkSeed::random(None);
+        let helm_key = HelmKey::new(blsttc::SecretKey::random());
+        let worker_key = WorkerKey::random();
+
+        let mut keystore = Keystore::create(&path, passphrase("correct horse")).unwrap();
+        keystore.add(ark_seed.address(), "helm", &helm_key).unwrap();
+        keystore
+            .import(
+                ark_seed.address(),
+                "worker",
+                &worker_key.danger_to_string().into(),
+            )
+            .unwrap();
+        assert!(
+            keystore
+                .add(ark_seed.address(), " helm ", &helm_key)
+                .is_err()
+        );
+        drop(keystore);
+
+        assert!(Keystore::open(&path, passphrase("wrong horse")).is_err());
+        assert!(Keystore::create(&path, passphrase("correct horse")).is_err());
+
+        let mut keystore = Keystore::open(&path, passphrase("correct horse")).unwrap();
+        let entries = keystore.entries();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].name, "helm");
+        assert_eq!(entries[0].kind, KeyKind::HelmKey);
+        assert_eq!(entries[1].kind, KeyKind::WorkerKey);
+        assert_eq!(
+            keystore.get::<HelmKey>(ark_seed.address(), "helm").unwrap(),
+            helm_key
+        );
+        assert_eq!(
+            keystore
+                .get::<WorkerKey>(ark_seed.address(), "worker")
+                .unwrap(),
+            worker_key
+        );
+        assert!(keystore.get::<DataKey>(ark_seed.address(), "helm").is_err());
+
+        keystore.remove(ark_seed.address(), "helm").unwrap();
+        let keystore = Keystore::open(&path, passphrase("correct horse")).unwrap();
+        assert_eq!(keystore.entries().len(), 1);
+    }
+}
@@ -0,0 +1,272 @@
+use crate::{LineItem, Receipt, RecordKind};
+use anyhow::{Context, anyhow};
+use autonomi::AttoTokens;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+/// Persistent, append-only record of every payment made.
+///
+/// Entries are stored as JSON Lines, one [`LedgerEntry`] per line.
+#[derive(Clone, Debug)]
+pub struct Ledger {
+    path: PathBuf,
+}
+
+impl Ledger {
+    pub fn open(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        Ok(Self { path })
+    }
+
+    pub fn path(&self) -> &Path {
+        self.path.as_path()
+    }
+
+    /// Appends all line items of the given receipt.
+    pub fn record(&self, receipt: &Receipt) -> anyhow::Result<()> {
+        if receipt.is_empty() {
+            return Ok(());
+        }
+        let mut buf = Vec::new();
+        for item in receipt.iter() {
+            serde_json::to_writer(&mut buf, &LedgerEntry::from(item))?;
+            buf.push(b'\n');
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        file.write_all(&buf)?;
+        file.sync_data()?;
+        Ok(())
+    }
+
+    pub fn entries(&self) -> anyhow::Result<Vec<LedgerEntry>> {
+        let file = match File::open(&self.path) {
+            Ok(file) => file,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(vec![]),
+            Err(err) => return Err(err.into()),
+        };
+        let mut entries = vec![];
+        for (no, line) in BufReader::new(file).lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            entries.push(
+                serde_json::from_str(&line)
+                    .with_context(|| format!("invalid ledger entry on line {}", no + 1))?,
+            );
+        }
+        Ok(entries)
+    }
+
+    pub fn export_csv(&self, writer: impl Write) -> anyhow::Result<()> {
+        let mut writer = csv::Writer::from_writer(writer);
+        for entry in self.entries()? {
+            writer.serialize(entry)?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+
+    pub fn export_json(&self, writer: impl Write) -> anyhow::Result<()> {
+        serde_json::to_writer_pretty(writer, &self.entries()?)?;
+        Ok(())
+    }
+
+    /// Total spend per ark and per vault.
+    ///
+    /// Payments not attributable to a specific vault are listed with `vault: None`.
+    pub fn summary(&self) -> anyhow::Result<Vec<SpendSummary>> {
+        let mut totals: BTreeMap<(String, Option<String>), SpendSummary> = BTreeMap::new();
+        for entry in self.entries()? {
+            let summary = totals
+                .entry((entry.ark.clone(), entry.vault.clone()))
+                .or_insert_with(|| SpendSummary {
+                    ark: entry.ark,
+                    vault: entry.vault,
+                    payments: 0,
+                    total: AttoTokens::zero(),
+                });
+            summary.payments += 1;
+            summary.total = summary
+                .total
+                .checked_add(entry.cost)
+                .ok_or(anyhow!("total spend of ark [{}] overflows", summary.ark))?;
+        }
+        Ok(totals.into_values().collect())
+    }
+
+    /// Total of all payments made at or after `since`.
+    pub fn spent_since(&self, since: DateTime<Utc>) -> anyhow::Result<AttoTokens> {
+        self.entries()?
+            .into_iter()
+            .filter(|entry| entry.timestamp >= since)
+            .try_fold(AttoTokens::zero(), |acc, entry| {
+                acc.checked_add(entry.cost)
+                    .ok_or(anyhow!("total spend since [{}] overflows", since))
+            })
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LedgerEntry {
+    pub timestamp: DateTime<Utc>,
+    pub network: String,
+    pub ark: String,
+    pub vault: Option<String>,
+    pub operation: String,
+    pub kind: RecordKind,
+    pub address: String,
+    #[serde(with = "tokens")]
+    pub cost: AttoTokens,
+}
+
+impl From<&LineItem> for LedgerEntry {
+    fn from(item: &LineItem) -> Self {
+        Self {
+            timestamp: *item.timestamp(),
+            network: item.network().to_string(),
+            ark: item.ark().to_string(),
+            vault: item.vault().map(|v| v.to_string()),
+            operation: item.operation().to_string(),
+            kind: item.kind(),
+            address: item.address().to_string(),
+            cost: item.cost(),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct SpendSummary {
+    pub ark: String,
+    pub vault: Option<String>,
+    pub payments: usize,
+    pub total: AttoTokens,
+}
+
+/// Amounts are stored in their human-readable token representation.
+mod tokens {
+    use autonomi::AttoTokens;
+    use serde::{Deserialize, Deserializer, Serializer};
+    use std::str::FromStr;
+
+    pub fn serialize<S: Serializer>(value: &AttoTokens, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(value)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<AttoTokens, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        AttoTokens::from_str(s.as_str()).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ArkSeed;
+    use chrono::TimeDelta;
+
+    fn ledger_with_payments(dir: &Path) -> Ledger {
+        let (ark_seed, _) = ArkSeed::random(None);
+        let mut receipt = Receipt::new("Ark Creation");
+        for (kind, cost) in [(RecordKind::Register, 3), (RecordKind::Scratchpad, 4)] {
+            receipt.add(
+                AttoTokens::from_u64(cost),
+                kind,
+                format!("{}", cost),
+                "TestNet",
+                ark_seed.address(),
+            );
+        }
+        let ledger = Ledger::open(dir.join("ledger.jsonl")).unwrap();
+        ledger.record(&receipt).unwrap();
+        ledger
+    }
+
+    #[test]
+    fn export_csv() {
+        let dir = tempfile::tempdir().unwrap();
+        let ledger = ledger_with_payments(dir.path());
+
+        let mut csv = vec![];
+        ledger.export_csv(&mut csv).unwrap();
+        let mut reader = csv::Reader::from_reader(csv.as_slice());
+        assert_eq!(
+            reader.headers().unwrap(),
+            vec![
+                "timestamp",
+                "network",
+                "ark",
+                "vault",
+                "operation",
+                "kind",
+                "address",
+                "cost"
+            ]
+        );
+        let entries = reader
+            .deserialize::<LedgerEntry>()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].kind, RecordKind::Register);
+        assert_eq!(entries[0].cost, AttoTokens::from_u64(3));
+        assert_eq!(entries[1].operation, "Ark Creation");
+        assert_eq!(entries[1].vault, None);
+    }
+
+    #[test]
+    fn export_json() {
+        let dir = tempfile::tempdir().unwrap();
+        let ledger = ledger_with_payments(dir.path());
+
+        let mut json = vec![];
+        ledger.export_json(&mut json).unwrap();
+        let entries = serde_json::from_slice::<Vec<LedgerEntry>>(&json).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[1].kind, RecordKind::Scratchpad);
+        assert_eq!(entries[1].cost, AttoTokens::from_u64(4));
+        assert_eq!(entries[0].network, "TestNet");
+    }
+
+    #[test]
+    fn summarise_per_ark() {
+        let dir = tempfile::tempdir().unwrap();
+        let ledger = ledger_with_payments(dir.path());
+        ledger.record(&Receipt::new("Nothing Paid")).unwrap();
+
+        let summary = ledger.summary().unwrap();
+        assert_eq!(summary.len(), 1);
+        assert_eq!(summary[0].vault, None);
+        assert_eq!(summary[0].payments, 2);
+        assert_eq!(summary[0].total, AttoTokens::from_u64(7));
+    }
+
+    #[test]
+    fn spent_since() {
+        let dir = tempfile::tempdir().unwrap();
+        let ledger = ledger_with_payments(dir.path());
+
+        assert_eq!(
+            ledger
+                .spent_since(Utc::now() - TimeDelta::hours(1))
+                .unwrap(),
+            AttoTokens::from_u64(7)
+        );
+        assert_eq!(
+            ledger
+                .spent_since(Utc::now() + TimeDelta::hours(1))
+                .unwrap(),
+            AttoTokens::zero()
+        );
+    }
+}
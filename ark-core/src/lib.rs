@@ -3,50 +3,155 @@ mod ark;
 mod ark_seed;
 mod autonomi_config;
 mod bridge_key;
+mod budget;
 mod crypto;
 mod data_key;
+mod error;
+mod explorer;
 mod helm_key;
+mod journal;
+mod keystore;
+mod ledger;
 mod manifest;
 pub(crate) mod objects;
+mod paper_backup;
 mod progress;
+mod secret_provider;
+mod seed_share;
+mod storage;
 mod vault;
+mod verify;
 mod worker_key;
 
-pub use ark::{ArkAccessor, ArkCreationDetails, ArkCreationSettings};
-pub use ark_seed::{ArkAddress, ArkSeed};
+pub use ark::{ArkAccessor, ArkCreationDetails, ArkCreationSettings, ArkMetadataUpdate};
+pub use ark_seed::{ArkAddress, ArkSeed, RecoveredKeys};
 pub use autonomi::{Client as AutonomiClient, Wallet as EvmWallet};
 pub use autonomi_config::ClientConfig as AutonomiClientConfig;
+pub use bip39::Language as MnemonicLanguage;
 pub use bridge_key::{BridgeAddress, BridgeKey};
+pub use budget::{Budget, BudgetExceeded};
 pub use chrono::{DateTime, Utc};
 pub use data_key::{DataKey, SealKey};
+pub use error::{Error, KeyKind};
+pub use explorer::{DerivedRecord, RecordState};
 pub use helm_key::{HelmKey, PublicHelmKey};
-pub use manifest::Manifest;
-pub use objects::ObjectType;
+pub use journal::{Journal, JournalEntry, JournalOperation, JournalStep};
+pub use keystore::{Keystore, KeystoreEntry, KeystoreKey};
+pub use ledger::{Ledger, LedgerEntry, SpendSummary};
+pub use manifest::{LabelValue, Manifest, ManifestConflict};
+pub use objects::{
+    Email, FileSystem, FileSystemConfig, ImapConfig, ObjectStorage, ObjectType, S3Config,
+};
+pub use paper_backup::PaperBackup;
 pub use progress::{Progress, Report as ProgressReport, Status as ProgressStatus};
-pub use vault::{VaultAddress, VaultConfig, VaultCreationSettings};
+pub use secret_provider::SecretProvider;
+pub use seed_share::{MAX_SEED_SHARES, SEED_SHARE_WORDS, SeedShare};
+pub use storage::{
+    CacheKey, CachingStorage, DirectoryStorage, MemoryStorage, Storage, StorageBackend,
+};
+pub use vault::{
+    LegalHold, LegalHoldRelease, VaultAddress, VaultConfig, VaultCreationSettings, VaultState,
+    VaultStatus,
+};
+pub use verify::{CheckOutcome, CheckResult, Repair, VerificationReport};
 pub use worker_key::{EitherWorkerKey, PublicWorkerKey, RetiredWorkerKey, WorkerKey};
 
 use crate::crypto::{TypedChunk, TypedChunkAddress};
-use anyhow::bail;
+use crate::journal::JournalHandle;
+use crate::progress::Task;
+use crate::storage::QuotingStorage;
+use anyhow::{anyhow, bail};
 use autonomi::client::payment::PaymentOption;
 use autonomi::register::{RegisterAddress, RegisterValue};
 use autonomi::{AttoTokens, Pointer, PointerAddress, Scratchpad, ScratchpadAddress};
 use bon::bon;
 use bytes::Bytes;
 use moka::future::Cache;
+use serde::{Deserialize, Serialize};
 use std::fmt::{Debug, Display, Formatter};
 use std::marker::PhantomData;
-use std::ops::AddAssign;
+use std::ops::{AddAssign, Deref};
 use std::time::Duration;
 use uuid::Uuid;
 use zeroize::{Zeroize, ZeroizeOnDrop};
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RecordKind {
+    Chunk,
+    Register,
+    Pointer,
+    Scratchpad,
+}
+
+impl Display for RecordKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::Chunk => "chunk",
+            Self::Register => "register",
+            Self::Pointer => "pointer",
+            Self::Scratchpad => "scratchpad",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// A single payment made for storing a record.
+#[derive(Debug, Clone)]
 pub struct LineItem {
     cost: AttoTokens,
     timestamp: DateTime<Utc>,
+    operation: String,
+    kind: RecordKind,
+    address: String,
+    network: String,
+    ark: ArkAddress,
+    vault: Option<VaultAddress>,
 }
+
+impl LineItem {
+    pub fn cost(&self) -> AttoTokens {
+        self.cost
+    }
+
+    pub fn timestamp(&self) -> &DateTime<Utc> {
+        &self.timestamp
+    }
+
+    /// Label of the operation this payment was made for, e.g. `Vault Creation`.
+    pub fn operation(&self) -> &str {
+        self.operation.as_str()
+    }
+
+    pub fn kind(&self) -> RecordKind {
+        self.kind
+    }
+
+    /// Hex encoded network address of the stored record.
+    pub fn address(&self) -> &str {
+        self.address.as_str()
+    }
+
+    pub fn network(&self) -> &str {
+        self.network.as_str()
+    }
+
+    pub fn ark(&self) -> &ArkAddress {
+        &self.ark
+    }
+
+    /// The vault the operation was about, if any.
+    pub fn vault(&self) -> Option<&VaultAddress> {
+        self.vault.as_ref()
+    }
+}
+
 pub struct Receipt {
+    operation: String,
+    vault: Option<VaultAddress>,
     items: Vec<LineItem>,
+    /// Spending of the 24 hours before the operation, read once by its first budget check
+    spent_before: Option<AttoTokens>,
 }
 
 impl AddAssign for Receipt {
@@ -56,11 +161,19 @@ impl AddAssign for Receipt {
 }
 
 impl Receipt {
-    fn new() -> Self {
+    fn new(operation: impl Into<String>) -> Self {
         Self {
+            operation: operation.into(),
+            vault: None,
             items: Vec::default(),
+            spent_before: None,
         }
     }
+
+    pub fn operation(&self) -> &str {
+        self.operation.as_str()
+    }
+
     pub fn len(&self) -> usize {
         self.items.len()
     }
@@ -81,28 +194,202 @@ impl Receipt {
         attos
     }
 
-    pub(crate) fn add(&mut self, cost: AttoTokens) {
+    /// Attributes all subsequently added line items to the given vault.
+    pub(crate) fn set_vault(&mut self, vault: &VaultAddress) {
+        self.vault = Some(vault.clone());
+    }
+
+    pub(crate) fn add(
+        &mut self,
+        cost: AttoTokens,
+        kind: RecordKind,
+        address: String,
+        network: &str,
+        ark: &ArkAddress,
+    ) {
         self.items.push(LineItem {
             cost,
             timestamp: Utc::now(),
+            operation: self.operation.clone(),
+            kind,
+            address,
+            network: network.to_string(),
+            ark: ark.clone(),
+            vault: self.vault.clone(),
         })
     }
 }
 
 pub type CostlyResult<T, E> = core::result::Result<(T, Receipt), (E, Receipt)>;
-pub type Result<T> = CostlyResult<T, anyhow::Error>;
+pub type Result<T> = CostlyResult<T, Error>;
 
-async fn with_receipt<T>(f: impl AsyncFnOnce(&mut Receipt) -> anyhow::Result<T>) -> Result<T> {
-    let mut receipt = Receipt::new();
+async fn with_receipt<T>(
+    operation: &str,
+    f: impl AsyncFnOnce(&mut Receipt) -> anyhow::Result<T>,
+) -> Result<T> {
+    let mut receipt = Receipt::new(operation);
     match f(&mut receipt).await {
         Ok(ok) => Ok((ok, receipt)),
         Err(err) => Err((err.into(), receipt)),
     }
 }
 
+/// Like `with_receipt`, but the resulting `Receipt` is only an estimate.
+///
+/// `f` is expected to run against storage that quotes instead of paying.
+async fn with_quote<T>(
+    operation: &str,
+    f: impl AsyncFnOnce(&mut Receipt) -> anyhow::Result<T>,
+) -> anyhow::Result<Receipt> {
+    let mut receipt = Receipt::new(operation);
+    f(&mut receipt).await?;
+    Ok(receipt)
+}
+
+/// Full access to an Ark, including everything that has to be paid for.
+///
+/// Dereferences to [`ReadOnlyCore`], so all reads are available as well.
 pub struct Core {
-    client: AutonomiClient,
-    wallet: EvmWallet,
+    reader: ReadOnlyCore,
+    payment: Payment,
+    budget: Option<Budget>,
+    journal: Option<Journal>,
+}
+
+/// How the writes of a `Core` are paid for.
+enum Payment {
+    Wallet(EvmWallet),
+    /// Writes are only quoted, see [`ReadOnlyCore::quoting`]
+    Quote,
+}
+
+impl Deref for Core {
+    type Target = ReadOnlyCore;
+
+    fn deref(&self) -> &Self::Target {
+        &self.reader
+    }
+}
+
+#[bon]
+impl Core {
+    #[builder]
+    pub fn new(
+        #[builder(into)] storage: Storage,
+        wallet: EvmWallet,
+        ark_address: ArkAddress,
+        budget: Option<Budget>,
+        journal: Option<Journal>,
+        #[builder(default = Duration::from_secs(3600))] cache_ttl: Duration,
+        #[builder(default = Duration::from_secs(900))] cache_tti: Duration,
+        #[builder(default = 1000)] register_cache_capacity: u64,
+        #[builder(default = 200)] register_history_cache_capacity: u64,
+        #[builder(default = 1000)] pointer_cache_capacity: u64,
+        #[builder(default = 1024 * 1024 * 8)] scratchpad_cache_capacity: u64,
+    ) -> Self {
+        Self {
+            reader: ReadOnlyCore::builder()
+                .storage(storage)
+                .ark_address(ark_address)
+                .cache_ttl(cache_ttl)
+                .cache_tti(cache_tti)
+                .register_cache_capacity(register_cache_capacity)
+                .register_history_cache_capacity(register_history_cache_capacity)
+                .pointer_cache_capacity(pointer_cache_capacity)
+                .scratchpad_cache_capacity(scratchpad_cache_capacity)
+                .build(),
+            payment: Payment::Wallet(wallet),
+            budget,
+            journal,
+        }
+    }
+
+    async fn put_chunk<T>(
+        &self,
+        chunk: &TypedChunk<T>,
+        receipt: &mut Receipt,
+    ) -> anyhow::Result<()> {
+        let payment = self
+            .authorize_payment(receipt, self.storage.chunk_cost(chunk.as_ref()))
+            .await?;
+        let (attos, address) = self.storage.chunk_put(chunk.as_ref(), payment).await?;
+        self.record_payment(receipt, RecordKind::Chunk, address.to_hex(), attos);
+        if chunk.address().as_ref() != &address {
+            bail!("incorrect chunk address returned");
+        }
+        Ok(())
+    }
+
+    /// Starts tracking `operation` in the journal (if any),
+    /// or picks up where an interrupted attempt left off.
+    fn journal(&self, operation: JournalOperation) -> anyhow::Result<JournalHandle> {
+        JournalHandle::new(self.journal.as_ref(), &self.ark_address, operation)
+    }
+
+    /// Quotes the complete `operation` and checks its total against the budget (if any).
+    ///
+    /// Called by operations consisting of several writes before their first payment,
+    /// so they are not aborted halfway for exceeding the budget.
+    async fn check_budget<T>(
+        &self,
+        receipt: &mut Receipt,
+        task: &mut Task,
+        operation: impl AsyncFnOnce(&Core, &mut Receipt, Task) -> anyhow::Result<T>,
+    ) -> anyhow::Result<()> {
+        let Some(budget) = &self.budget else {
+            return Ok(());
+        };
+        let mut check = task.child(1, "Check Budget".to_string());
+        check.start();
+        let mut quote = Receipt::new("Budget Check");
+        operation(&self.quoting(), &mut quote, check.child(1, None)).await?;
+        budget.check(receipt, quote.total_cost())?;
+        check.complete();
+        Ok(())
+    }
+
+    /// Checks the quoted `cost` against the budget (if any) right before it is paid.
+    ///
+    /// The quote is only requested when a budget is set.
+    async fn authorize_payment(
+        &self,
+        receipt: &mut Receipt,
+        cost: impl Future<Output = anyhow::Result<AttoTokens>>,
+    ) -> anyhow::Result<PaymentOption> {
+        if let Some(budget) = &self.budget {
+            budget.check(receipt, cost.await?)?;
+        }
+        Ok(match &self.payment {
+            Payment::Wallet(wallet) => PaymentOption::Wallet(wallet.clone()),
+            // never passed on by the quoting storage
+            Payment::Quote => PaymentOption::Receipt(Default::default()),
+        })
+    }
+
+    fn record_payment(
+        &self,
+        receipt: &mut Receipt,
+        kind: RecordKind,
+        address: String,
+        cost: AttoTokens,
+    ) {
+        receipt.add(
+            cost,
+            kind,
+            address,
+            self.storage.network(),
+            &self.ark_address,
+        );
+    }
+}
+
+/// Read-only access to an Ark.
+///
+/// Does not require a wallet and is unable to pay for anything, making it
+/// suitable for inspection, auditing and recovery drills on machines that
+/// never hold any funds. Costly operations can still be quoted.
+pub struct ReadOnlyCore {
+    storage: Storage,
     ark_address: ArkAddress,
     register_cache: Cache<RegisterAddress, Option<RegisterValue>>,
     register_history_cache: Cache<RegisterAddress, Vec<RegisterValue>>,
@@ -111,11 +398,10 @@ pub struct Core {
 }
 
 #[bon]
-impl Core {
+impl ReadOnlyCore {
     #[builder]
     pub fn new(
-        client: AutonomiClient,
-        wallet: EvmWallet,
+        #[builder(into)] storage: Storage,
         ark_address: ArkAddress,
         #[builder(default = Duration::from_secs(3600))] cache_ttl: Duration,
         #[builder(default = Duration::from_secs(900))] cache_tti: Duration,
@@ -125,8 +411,7 @@ impl Core {
         #[builder(default = 1024 * 1024 * 8)] scratchpad_cache_capacity: u64,
     ) -> Self {
         Self {
-            client,
-            wallet,
+            storage,
             ark_address,
             register_cache: Cache::builder()
                 .name("register_cache")
@@ -158,20 +443,26 @@ impl Core {
         }
     }
 
-    async fn put_chunk<T>(
-        &self,
-        chunk: &TypedChunk<T>,
-        receipt: &mut Receipt,
-    ) -> anyhow::Result<()> {
-        let (attos, address) = self
-            .client
-            .chunk_put(chunk.as_ref(), self.payment())
-            .await?;
-        receipt.add(attos);
-        if chunk.address().as_ref() != &address {
-            bail!("incorrect chunk address returned");
+    pub fn ark_address(&self) -> &ArkAddress {
+        &self.ark_address
+    }
+
+    /// Returns a `Core` for the same Ark whose writes are quoted & staged instead of paid for.
+    ///
+    /// Quoting does not require a wallet, nothing ever reaches the underlying storage.
+    fn quoting(&self) -> Core {
+        Core {
+            reader: ReadOnlyCore::builder()
+                .storage(
+                    Storage::from(QuotingStorage::new(self.storage.clone()))
+                        .with_network(self.storage.network()),
+                )
+                .ark_address(self.ark_address.clone())
+                .build(),
+            payment: Payment::Quote,
+            budget: None,
+            journal: None,
         }
-        Ok(())
     }
 
     async fn get_chunk<T: TryFrom<Bytes>>(
@@ -181,13 +472,17 @@ impl Core {
     where
         <T as TryFrom<Bytes>>::Error: Display,
     {
-        let chunk = TypedChunk::from_chunk(self.client.chunk_get(address.as_ref()).await?);
+        let chunk = self
+            .storage
+            .chunk_get(address.as_ref())
+            .await?
+            .ok_or(Error::NotFound {
+                kind: RecordKind::Chunk,
+                address: address.as_ref().to_hex(),
+            })?;
+        let chunk = TypedChunk::from_chunk(chunk);
         chunk.try_into_inner()
     }
-
-    fn payment(&self) -> PaymentOption {
-        PaymentOption::Wallet(self.wallet.clone())
-    }
 }
 
 #[derive(Debug, Clone, Copy, Eq, Hash, Ord, PartialEq, PartialOrd)]
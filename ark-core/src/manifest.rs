@@ -7,26 +7,41 @@ use crate::crypto::{
 };
 use crate::helm_key::HelmKind;
 use crate::protos::{deserialize_with_header, serialize_with_header};
-use crate::vault::{VaultConfig, VaultCreationSettings};
+use crate::vault::{
+    LegalHold, LegalHoldRelease, VaultConfig, VaultCreationSettings, VaultState, VaultStatus,
+};
 use crate::{
-    ArkAccessor, ArkAddress, ArkSeed, Core, DataKey, HelmKey, PublicHelmKey, PublicWorkerKey,
-    Receipt, RetiredWorkerKey, SealKey, VaultAddress, WorkerKey, decryptor, encryptor,
-    impl_decryptor_for,
+    ArkAccessor, ArkAddress, ArkSeed, Core, DataKey, Error, HelmKey, PublicHelmKey,
+    PublicWorkerKey, ReadOnlyCore, Receipt, RecordKind, RetiredWorkerKey, SealKey, VaultAddress,
+    WorkerKey, decryptor, encryptor, impl_decryptor_for,
 };
 use anyhow::{anyhow, bail};
 use bytes::Bytes;
 use chrono::{DateTime, Utc};
 use once_cell::sync::Lazy;
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{Display, Formatter};
 use std::ops::Deref;
+use std::str::FromStr;
+use thiserror::Error;
 
 const MAGIC_NUMBER: &'static [u8; 16] = &[
+    0x61, 0x72, 0x6B, 0x5F, 0x6D, 0x61, 0x6E, 0x69, 0x66, 0x65, 0x73, 0x74, 0x5F, 0x76, 0x30, 0x31,
+];
+
+/// Format written before labels, vault states & legal holds existed.
+///
+/// Still readable, but never written: clients only knowing this format would silently
+/// drop the newer fields when rewriting the manifest, so they have to refuse it instead.
+const MAGIC_NUMBER_V00: &'static [u8; 16] = &[
     0x61, 0x72, 0x6B, 0x5F, 0x6D, 0x61, 0x6E, 0x69, 0x66, 0x65, 0x73, 0x74, 0x5F, 0x76, 0x30, 0x30,
 ];
 
 const MANIFEST_SCRATCHPAD_ENCODING: u64 = 344850175421548714;
 
-const MANIFEST_NAME: &str = "/ark/v0/manifest/scratchpad";
+const MANIFEST_UPDATE_ATTEMPTS: usize = 5;
+
+pub(crate) const MANIFEST_NAME: &str = "/ark/v0/manifest/scratchpad";
 static MANIFEST_DERIVATOR: Lazy<ManifestDerivator> =
     Lazy::new(|| ManifestDerivator::from_name(MANIFEST_NAME));
 
@@ -41,9 +56,75 @@ pub struct Manifest {
     pub description: Option<String>,
     pub authorized_worker: PublicWorkerKey,
     pub retired_workers: BTreeSet<RetiredWorkerKey>,
+    /// Free-form metadata, such as owner, department or retention class
+    pub labels: BTreeMap<String, LabelValue>,
     pub vaults: Vec<VaultConfig>,
 }
 
+/// Typed value of a manifest label.
+///
+/// Parsed from `<type>:<value>`, with `text`, `int`, `bool` and `time` (RFC 3339) as types.
+/// Anything without a known type prefix is text.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum LabelValue {
+    Text(String),
+    Integer(i64),
+    Boolean(bool),
+    Timestamp(DateTime<Utc>),
+}
+
+impl FromStr for LabelValue {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (kind, value) = match s.split_once(':') {
+            Some((kind, value)) => (kind.trim(), value.trim()),
+            None => return Ok(Self::Text(s.trim().to_string())),
+        };
+        Ok(match kind {
+            "text" => Self::Text(value.to_string()),
+            "int" => Self::Integer(
+                value
+                    .parse()
+                    .map_err(|_| anyhow!("invalid integer label value [{}]", value))?,
+            ),
+            "bool" => Self::Boolean(
+                value
+                    .parse()
+                    .map_err(|_| anyhow!("invalid boolean label value [{}]", value))?,
+            ),
+            "time" => Self::Timestamp(
+                DateTime::parse_from_rfc3339(value)
+                    .map_err(|_| anyhow!("invalid timestamp label value [{}]", value))?
+                    .to_utc(),
+            ),
+            _ => Self::Text(s.trim().to_string()),
+        })
+    }
+}
+
+impl Display for LabelValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Text(value) => write!(f, "{}", value),
+            Self::Integer(value) => write!(f, "{}", value),
+            Self::Boolean(value) => write!(f, "{}", value),
+            Self::Timestamp(value) => write!(f, "{}", value.to_rfc3339()),
+        }
+    }
+}
+
+/// The manifest was modified by someone else since it was read.
+#[derive(Error, Debug, Clone)]
+#[error(
+    "manifest of ark [{ark_address}] was modified concurrently: expected counter [{expected}] but found [{actual}]"
+)]
+pub struct ManifestConflict {
+    pub ark_address: ArkAddress,
+    pub expected: u64,
+    pub actual: u64,
+}
+
 impl Manifest {
     pub fn vault(&self, vault_address: &VaultAddress) -> Option<&VaultConfig> {
         self.vaults.iter().find(|v| &v.address == vault_address)
@@ -113,7 +194,15 @@ impl From<VaultCreationSettings> for VaultConfig {
             last_modified: Utc::now(),
             name: value.name,
             description: value.description,
-            active: value.active,
+            status: VaultStatus::new(
+                match value.active {
+                    true => VaultState::Active,
+                    false => VaultState::Paused,
+                },
+                None,
+            ),
+            status_history: vec![],
+            legal_holds: vec![],
             bridge: value.bridge,
             object_type: value.object_type,
         }
@@ -135,6 +224,7 @@ impl Manifest {
             vaults: Default::default(),
             authorized_worker,
             retired_workers: Default::default(),
+            labels: Default::default(),
         }
     }
 
@@ -148,7 +238,12 @@ impl Manifest {
     }
 
     pub(super) fn deserialize(data: impl AsRef<[u8]>) -> anyhow::Result<Self> {
-        let proto: protos::Manifest = deserialize_with_header(data, MAGIC_NUMBER)?;
+        let data = data.as_ref();
+        let proto: protos::Manifest = if data.starts_with(MAGIC_NUMBER_V00) {
+            deserialize_with_header(data, MAGIC_NUMBER_V00)?
+        } else {
+            deserialize_with_header(data, MAGIC_NUMBER)?
+        };
         proto.try_into()
     }
 
@@ -180,19 +275,25 @@ impl TryFrom<Bytes> for Manifest {
     }
 }
 
-impl Core {
-    pub(crate) async fn create_manifest(
+impl ReadOnlyCore {
+    /// Retrieves the latest manifest, bypassing the cache, along with its counter.
+    ///
+    /// The counter has to be handed to [`Core::update_manifest`] to detect concurrent changes.
+    pub(super) async fn get_manifest_for_update<D: ManifestDecryptor>(
         &self,
-        manifest: &Manifest,
-        helm_key: &HelmKey,
-        manifest_encryptor: &ManifestEncryptor,
-        receipt: &mut Receipt,
-    ) -> anyhow::Result<ManifestAddress> {
-        self.create_encrypted_scratchpad(
-            helm_key.manifest(manifest_encryptor.encrypt_manifest(&manifest)?),
-            receipt,
-        )
-        .await
+        decryptor: &D,
+    ) -> anyhow::Result<(Manifest, u64)> {
+        let address = self.public_helm_key().await?.manifest();
+        self.invalidate_scratchpad(&address).await?;
+        let pad = self
+            .get_scratchpad(&address)
+            .await?
+            .ok_or(Error::NotFound {
+                kind: RecordKind::Scratchpad,
+                address: address.as_ref().to_hex(),
+            })?;
+        let counter = pad.counter();
+        Ok((decryptor.decrypt_manifest(&pad.try_into_inner()?)?, counter))
     }
 
     pub(super) async fn get_manifest<D: ManifestDecryptor>(
@@ -209,9 +310,7 @@ impl Core {
         decryptor: &D,
         public_helm_key: &PublicHelmKey,
     ) -> anyhow::Result<Manifest> {
-        let encrypted_manifest = self
-            .read_scratchpad(&public_helm_key.manifest())
-            .await?;
+        let encrypted_manifest = self.read_scratchpad(&public_helm_key.manifest()).await?;
         decryptor.decrypt_manifest(&encrypted_manifest)
     }
 
@@ -226,10 +325,32 @@ impl Core {
             self.seal_key().await?,
         ))
     }
+}
+
+impl Core {
+    pub(crate) async fn create_manifest(
+        &self,
+        manifest: &Manifest,
+        helm_key: &HelmKey,
+        manifest_encryptor: &ManifestEncryptor,
+        receipt: &mut Receipt,
+    ) -> anyhow::Result<ManifestAddress> {
+        self.create_encrypted_scratchpad(
+            helm_key.manifest(manifest_encryptor.encrypt_manifest(&manifest)?),
+            receipt,
+        )
+        .await
+    }
 
+    /// Writes `manifest`, unless it was changed since it was read at `expected_counter`.
+    ///
+    /// Fails with [`ManifestConflict`] in that case. The new version is written with
+    /// exactly `expected_counter + 1`, so a concurrent update that gets there first
+    /// makes the network reject this one, which is reported as a conflict as well.
     pub(super) async fn update_manifest(
         &self,
         manifest: &Manifest,
+        expected_counter: u64,
         helm_key: &HelmKey,
         receipt: &mut Receipt,
     ) -> anyhow::Result<u64> {
@@ -237,15 +358,75 @@ impl Core {
             bail!("manifest ark address does not match given ark address");
         }
         self.verify_helm_key(helm_key).await?;
-        self.update_scratchpad(
-            helm_key.manifest(
-                self.manifest_encryptor(helm_key)
-                    .await?
-                    .encrypt_manifest(&manifest)?,
-            ),
-            receipt,
-        )
-        .await
+        let address = helm_key.public_key().manifest();
+        let actual = self
+            .scratchpad_counter(&address)
+            .await?
+            .ok_or(Error::NotFound {
+                kind: RecordKind::Scratchpad,
+                address: address.as_ref().to_hex(),
+            })?;
+        if actual != expected_counter {
+            return Err(ManifestConflict {
+                ark_address: self.ark_address.clone(),
+                expected: expected_counter,
+                actual,
+            }
+            .into());
+        }
+        let res = self
+            .update_scratchpad_after(
+                helm_key.manifest(
+                    self.manifest_encryptor(helm_key)
+                        .await?
+                        .encrypt_manifest(&manifest)?,
+                ),
+                expected_counter,
+                receipt,
+            )
+            .await;
+        if res.is_err() {
+            if let Ok(Some(actual)) = self.scratchpad_counter(&address).await {
+                if actual != expected_counter {
+                    return Err(ManifestConflict {
+                        ark_address: self.ark_address.clone(),
+                        expected: expected_counter,
+                        actual,
+                    }
+                    .into());
+                }
+            }
+        }
+        res
+    }
+
+    /// Applies `modify` to the latest manifest and writes it back.
+    ///
+    /// Should the manifest change concurrently, `modify` is applied again on top of the
+    /// new version. Gives up with [`ManifestConflict`] after `MANIFEST_UPDATE_ATTEMPTS`.
+    pub(super) async fn modify_manifest(
+        &self,
+        helm_key: &HelmKey,
+        receipt: &mut Receipt,
+        mut modify: impl FnMut(&mut Manifest) -> anyhow::Result<()> + Send,
+    ) -> anyhow::Result<u64> {
+        let mut attempt = 1;
+        loop {
+            let (mut manifest, counter) = self.get_manifest_for_update(helm_key).await?;
+            modify(&mut manifest)?;
+            match self
+                .update_manifest(&manifest, counter, helm_key, receipt)
+                .await
+            {
+                Err(err)
+                    if attempt < MANIFEST_UPDATE_ATTEMPTS
+                        && err.downcast_ref::<ManifestConflict>().is_some() =>
+                {
+                    attempt += 1;
+                }
+                res => return res,
+            }
+        }
     }
 
     pub(super) async fn retire_manifest(
@@ -260,10 +441,14 @@ impl Core {
             )
         };
 
+        let address = helm_key.public_key().manifest();
         self.danger_retire_scratchpad(
-            self.get_scratchpad(&helm_key.public_key().manifest())
+            self.get_scratchpad(&address)
                 .await?
-                .ok_or(anyhow!("manifest not found"))?
+                .ok_or(Error::NotFound {
+                    kind: RecordKind::Scratchpad,
+                    address: address.as_ref().to_hex(),
+                })?
                 .try_into_owned(&helm_key.derive_manifest_key())?,
             receipt,
         )
@@ -273,8 +458,9 @@ impl Core {
 }
 
 mod protos {
-    use anyhow::anyhow;
-    use std::collections::BTreeSet;
+    use anyhow::{anyhow, bail};
+    use chrono::{DateTime, Utc};
+    use std::collections::{BTreeMap, BTreeSet};
 
     include!(concat!(env!("OUT_DIR"), "/protos/manifest.rs"));
 
@@ -292,11 +478,43 @@ mod protos {
                     .into_iter()
                     .map(|w| w.into())
                     .collect::<Vec<_>>(),
+                labels: value
+                    .labels
+                    .into_iter()
+                    .map(|(key, value)| Label {
+                        key,
+                        value: Some(value.into()),
+                    })
+                    .collect(),
                 vaults: value.vaults.into_iter().map(|v| v.into()).collect(),
             }
         }
     }
 
+    impl From<super::LabelValue> for label::Value {
+        fn from(value: super::LabelValue) -> Self {
+            match value {
+                super::LabelValue::Text(value) => Self::Text(value),
+                super::LabelValue::Integer(value) => Self::Integer(value),
+                super::LabelValue::Boolean(value) => Self::Boolean(value),
+                super::LabelValue::Timestamp(value) => Self::Timestamp(value.into()),
+            }
+        }
+    }
+
+    impl TryFrom<label::Value> for super::LabelValue {
+        type Error = anyhow::Error;
+
+        fn try_from(value: label::Value) -> Result<Self, Self::Error> {
+            Ok(match value {
+                label::Value::Text(value) => Self::Text(value),
+                label::Value::Integer(value) => Self::Integer(value),
+                label::Value::Boolean(value) => Self::Boolean(value),
+                label::Value::Timestamp(value) => Self::Timestamp(value.try_into()?),
+            })
+        }
+    }
+
     impl TryFrom<Manifest> for super::Manifest {
         type Error = anyhow::Error;
 
@@ -325,6 +543,18 @@ mod protos {
                     .into_iter()
                     .map(|r| r.try_into())
                     .collect::<anyhow::Result<BTreeSet<super::RetiredWorkerKey>>>()?,
+                labels: value
+                    .labels
+                    .into_iter()
+                    .map(|l| {
+                        Ok((
+                            l.key,
+                            l.value
+                                .ok_or(anyhow!("label value is missing"))?
+                                .try_into()?,
+                        ))
+                    })
+                    .collect::<anyhow::Result<BTreeMap<String, super::LabelValue>>>()?,
                 vaults: value
                     .vaults
                     .into_iter()
@@ -342,17 +572,120 @@ mod protos {
                 last_modified: Some(value.last_modified.into()),
                 name: value.name,
                 description: value.description,
-                active: value.active,
+                active: value.status.state == super::VaultState::Active,
                 bridge: value.bridge.map(|b| b.into()),
                 object_type: Some(value.object_type.into()),
+                status: Some(value.status.into()),
+                status_history: value.status_history.into_iter().map(|s| s.into()).collect(),
+                legal_holds: value.legal_holds.into_iter().map(|h| h.into()).collect(),
+            }
+        }
+    }
+
+    impl From<super::LegalHold> for LegalHold {
+        fn from(value: super::LegalHold) -> Self {
+            Self {
+                placed: Some(value.placed.into()),
+                reason: value.reason,
+                released: value.released.map(|r| r.into()),
+            }
+        }
+    }
+
+    impl TryFrom<LegalHold> for super::LegalHold {
+        type Error = anyhow::Error;
+
+        fn try_from(value: LegalHold) -> Result<Self, Self::Error> {
+            Ok(Self {
+                placed: value
+                    .placed
+                    .ok_or(anyhow!("placed is missing"))?
+                    .try_into()?,
+                reason: value.reason,
+                released: value.released.map(|r| r.try_into()).transpose()?,
+            })
+        }
+    }
+
+    impl From<super::LegalHoldRelease> for LegalHoldRelease {
+        fn from(value: super::LegalHoldRelease) -> Self {
+            Self {
+                at: Some(value.at.into()),
+                reason: value.reason,
+                signature: value.signature,
+            }
+        }
+    }
+
+    impl TryFrom<LegalHoldRelease> for super::LegalHoldRelease {
+        type Error = anyhow::Error;
+
+        fn try_from(value: LegalHoldRelease) -> Result<Self, Self::Error> {
+            Ok(Self {
+                at: value.at.ok_or(anyhow!("at is missing"))?.try_into()?,
+                reason: value.reason,
+                signature: value.signature,
+            })
+        }
+    }
+
+    impl From<super::VaultStatus> for VaultStatus {
+        fn from(value: super::VaultStatus) -> Self {
+            Self {
+                state: VaultState::from(value.state).into(),
+                since: Some(value.since.into()),
+                reason: value.reason,
+            }
+        }
+    }
+
+    impl TryFrom<VaultStatus> for super::VaultStatus {
+        type Error = anyhow::Error;
+
+        fn try_from(value: VaultStatus) -> Result<Self, Self::Error> {
+            Ok(Self {
+                state: VaultState::try_from(value.state)
+                    .map_err(|e| anyhow!("invalid vault state: {}", e))?
+                    .try_into()?,
+                since: value.since.ok_or(anyhow!("since is missing"))?.try_into()?,
+                reason: value.reason,
+            })
+        }
+    }
+
+    impl From<super::VaultState> for VaultState {
+        fn from(value: super::VaultState) -> Self {
+            match value {
+                super::VaultState::Active => Self::Active,
+                super::VaultState::Paused => Self::Paused,
+                super::VaultState::Archived => Self::Archived,
+                super::VaultState::Decommissioned => Self::Decommissioned,
             }
         }
     }
 
+    impl TryFrom<VaultState> for super::VaultState {
+        type Error = anyhow::Error;
+
+        fn try_from(value: VaultState) -> Result<Self, Self::Error> {
+            Ok(match value {
+                VaultState::Active => Self::Active,
+                VaultState::Paused => Self::Paused,
+                VaultState::Archived => Self::Archived,
+                VaultState::Decommissioned => Self::Decommissioned,
+                VaultState::Unspecified => bail!("vault state is unspecified"),
+            })
+        }
+    }
+
     impl TryFrom<Vault> for super::VaultConfig {
         type Error = anyhow::Error;
 
         fn try_from(value: Vault) -> Result<Self, Self::Error> {
+            let last_modified: DateTime<Utc> = value
+                .last_modified
+                .ok_or(anyhow!("last_modified is missing"))?
+                .try_into()?;
             Ok(Self {
                 address: value
                     .address
@@ -362,13 +695,31 @@ mod protos {
                     .created
                     .ok_or(anyhow!("created is missing"))?
                     .try_into()?,
-                last_modified: value
-                    .last_modified
-                    .ok_or(anyhow!("last_modified is missing"))?
-                    .try_into()?,
+                last_modified,
+                // vaults written before lifecycle states existed only carry the active flag
+                status: match value.status {
+                    Some(status) => status.try_into()?,
+                    None => super::VaultStatus {
+                        state: match value.active {
+                            true => super::VaultState::Active,
+                            false => super::VaultState::Paused,
+                        },
+                        since: last_modified,
+                        reason: None,
+                    },
+                },
+                status_history: value
+                    .status_history
+                    .into_iter()
+                    .map(|s| s.try_into())
+                    .collect::<anyhow::Result<Vec<_>>>()?,
+                legal_holds: value
+                    .legal_holds
+                    .into_iter()
+                    .map(|h| h.try_into())
+                    .collect::<anyhow::Result<Vec<_>>>()?,
                 name: value.name,
                 description: value.description,
-                active: value.active,
                 bridge: value.bridge.map(|a| a.try_into()).transpose()?,
                 object_type: value
                     .object_type
@@ -378,3 +729,118 @@ mod protos {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EvmWallet, MemoryStorage, Storage};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tokio::runtime::Handle;
+
+    fn client(storage: &Storage, ark_seed: &ArkSeed) -> Core {
+        Core::builder()
+            .storage(storage.clone())
+            .wallet(EvmWallet::new_with_random_wallet(
+                autonomi::Network::ArbitrumSepoliaTest,
+            ))
+            .ark_address(ark_seed.address().clone())
+            .build()
+    }
+
+    async fn create_ark(storage: &Storage) -> (ArkSeed, HelmKey) {
+        let (ark_seed, _) = ArkSeed::random(None);
+        let settings = ArkCreationSettings::builder().name("Test Ark").build();
+        let wallet = EvmWallet::new_with_random_wallet(autonomi::Network::ArbitrumSepoliaTest);
+        let Ok((details, _)) = Core::create_ark(settings, &ark_seed, storage, &wallet, None)
+            .1
+            .await
+        else {
+            panic!("ark creation failed");
+        };
+        (ark_seed, details.helm_key.clone())
+    }
+
+    #[tokio::test]
+    async fn refuse_stale_manifest_update() {
+        let storage: Storage = MemoryStorage::new().into();
+        let (ark_seed, helm_key) = create_ark(&storage).await;
+        let core = client(&storage, &ark_seed);
+        let (mut manifest, counter) = core.get_manifest_for_update(&helm_key).await.unwrap();
+
+        manifest.name = "First".to_string();
+        let written = core
+            .update_manifest(&manifest, counter, &helm_key, &mut Receipt::new("First"))
+            .await
+            .unwrap();
+        assert_eq!(written, counter + 1);
+
+        manifest.name = "Second".to_string();
+        let err = core
+            .update_manifest(&manifest, counter, &helm_key, &mut Receipt::new("Second"))
+            .await
+            .unwrap_err();
+        let conflict = err
+            .downcast_ref::<ManifestConflict>()
+            .expect("a manifest conflict");
+        assert_eq!(conflict.expected, counter);
+        assert_eq!(conflict.actual, counter + 1);
+        assert_eq!(core.get_manifest(&helm_key).await.unwrap().name, "First");
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn retry_manifest_modification_after_conflict() {
+        let storage: Storage = MemoryStorage::new().into();
+        let (ark_seed, helm_key) = create_ark(&storage).await;
+        let core = client(&storage, &ark_seed);
+        // a second client, sharing nothing but the storage
+        let other = client(&storage, &ark_seed);
+        let attempts = AtomicUsize::new(0);
+
+        core.modify_manifest(&helm_key, &mut Receipt::new("Modify"), |manifest| {
+            if attempts.fetch_add(1, Ordering::SeqCst) == 0 {
+                // the other client gets there first
+                tokio::task::block_in_place(|| {
+                    Handle::current().block_on(other.modify_manifest(
+                        &helm_key,
+                        &mut Receipt::new("Concurrent"),
+                        |manifest| {
+                            manifest.description = Some("concurrent".to_string());
+                            Ok(())
+                        },
+                    ))
+                })?;
+            }
+            manifest.name = "Retried".to_string();
+            Ok(())
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(attempts.load(Ordering::SeqCst), 2);
+        let manifest = core.get_manifest(&helm_key).await.unwrap();
+        assert_eq!(manifest.description.as_deref(), Some("concurrent"));
+        assert_eq!(manifest.name, "Retried");
+    }
+
+    #[test]
+    fn parse_typed_label_values() {
+        let parse = |s: &str| LabelValue::from_str(s).unwrap();
+        assert_eq!(parse("int: 42"), LabelValue::Integer(42));
+        assert_eq!(parse("bool:true"), LabelValue::Boolean(true));
+        assert_eq!(
+            parse("time:2025-01-31T12:00:00+01:00"),
+            LabelValue::Timestamp("2025-01-31T11:00:00Z".parse().unwrap())
+        );
+        assert_eq!(parse("text:int:42"), LabelValue::Text("int:42".to_string()));
+        // anything without a known type prefix is text
+        assert_eq!(parse(" finance "), LabelValue::Text("finance".to_string()));
+        assert_eq!(
+            parse("https://example.com"),
+            LabelValue::Text("https://example.com".to_string())
+        );
+
+        assert!(LabelValue::from_str("int:many").is_err());
+        assert!(LabelValue::from_str("bool:yes").is_err());
+        assert!(LabelValue::from_str("time:yesterday").is_err());
+    }
+}
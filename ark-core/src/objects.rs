@@ -1,6 +1,8 @@
 use anyhow::anyhow;
 use std::fmt::{Display, Formatter};
+use std::mem::discriminant;
 use std::str::FromStr;
+use url::Url;
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub enum ObjectType {
@@ -9,25 +11,43 @@ pub enum ObjectType {
     ObjectStorage(ObjectStorage),
 }
 
+impl ObjectType {
+    /// Whether both refer to the same kind of source, regardless of their configuration.
+    pub fn is_same_kind(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::FileSystem(a), Self::FileSystem(b)) => discriminant(a) == discriminant(b),
+            (Self::Email(a), Self::Email(b)) => discriminant(a) == discriminant(b),
+            (Self::ObjectStorage(a), Self::ObjectStorage(b)) => discriminant(a) == discriminant(b),
+            _ => false,
+        }
+    }
+}
+
 impl FromStr for ObjectType {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let s = s.trim().to_lowercase();
         if s.contains("posix") {
-            return Ok(ObjectType::FileSystem(FileSystem::Posix));
+            return Ok(ObjectType::FileSystem(FileSystem::Posix(
+                FileSystemConfig::default(),
+            )));
         }
         if s.contains("win") {
-            return Ok(ObjectType::FileSystem(FileSystem::Windows));
+            return Ok(ObjectType::FileSystem(FileSystem::Windows(
+                FileSystemConfig::default(),
+            )));
         }
         if s.contains("imap") {
-            return Ok(ObjectType::Email(Email::IMAP));
+            return Ok(ObjectType::Email(Email::IMAP(ImapConfig::default())));
         }
         if s.contains("gmail") {
             return Ok(ObjectType::Email(Email::GMAIL));
         }
         if s.contains("s3") {
-            return Ok(ObjectType::ObjectStorage(ObjectStorage::S3));
+            return Ok(ObjectType::ObjectStorage(ObjectStorage::S3(
+                S3Config::default(),
+            )));
         }
         Err(anyhow!("unknown or unsupported object type [{}]", s))
     }
@@ -39,11 +59,11 @@ impl Display for ObjectType {
             f,
             "{}",
             match &self {
-                Self::FileSystem(FileSystem::Posix) => "File System (Posix)",
-                Self::FileSystem(FileSystem::Windows) => "File System (Windows)",
-                Self::Email(Email::IMAP) => "Email (IMAP)",
+                Self::FileSystem(FileSystem::Posix(_)) => "File System (Posix)",
+                Self::FileSystem(FileSystem::Windows(_)) => "File System (Windows)",
+                Self::Email(Email::IMAP(_)) => "Email (IMAP)",
                 Self::Email(Email::GMAIL) => "Email (Gmail)",
-                Self::ObjectStorage(ObjectStorage::S3) => "Object Storage (S3)",
+                Self::ObjectStorage(ObjectStorage::S3(_)) => "Object Storage (S3)",
             }
         )
     }
@@ -51,19 +71,49 @@ impl Display for ObjectType {
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub enum FileSystem {
-    Posix,
-    Windows,
+    Posix(FileSystemConfig),
+    Windows(FileSystemConfig),
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub enum Email {
-    IMAP,
+    IMAP(ImapConfig),
     GMAIL,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub enum ObjectStorage {
-    S3,
+    S3(S3Config),
+}
+
+/// Which part of a file system a vault covers.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct FileSystemConfig {
+    /// Path on the source machine, kept as given
+    pub root: Option<String>,
+    /// Glob patterns relative to `root`. Everything is included if empty.
+    pub include: Vec<String>,
+    /// Glob patterns relative to `root`, applied after `include`
+    pub exclude: Vec<String>,
+}
+
+/// The mailbox a vault covers. Credentials are never part of the manifest.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ImapConfig {
+    /// e.g. `imap.example.com:993`
+    pub server: Option<String>,
+    pub account: Option<String>,
+    /// All folders are covered if empty
+    pub folders: Vec<String>,
+}
+
+/// The bucket a vault covers. Credentials are never part of the manifest.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct S3Config {
+    pub bucket: Option<String>,
+    pub prefix: Option<String>,
+    /// Custom endpoint for S3 compatible services, AWS if `None`
+    pub endpoint: Option<Url>,
 }
 
 pub mod protos {
@@ -77,6 +127,7 @@ pub mod protos {
     use crate::objects::protos::object_storage::StorageType;
     use crate::objects::protos::object_type::Type;
     use anyhow::anyhow;
+    use url::Url;
 
     include!(concat!(env!("OUT_DIR"), "/protos/objects.rs"));
 
@@ -126,7 +177,7 @@ pub mod protos {
         fn from(value: super::ObjectStorage) -> Self {
             Self {
                 storage_type: match value {
-                    super::ObjectStorage::S3 => Some(StorageType::S3(ProtoS3::default())),
+                    super::ObjectStorage::S3(config) => Some(StorageType::S3(config.into())),
                 },
             }
         }
@@ -136,12 +187,37 @@ pub mod protos {
         type Error = anyhow::Error;
 
         fn try_from(value: ObjectStorage) -> Result<Self, Self::Error> {
-            value
+            match value
                 .storage_type
-                .map(|s| match s {
-                    StorageType::S3(_) => super::ObjectStorage::S3,
-                })
-                .ok_or(anyhow!("invalid object_storage"))
+                .ok_or(anyhow!("invalid object_storage"))?
+            {
+                StorageType::S3(s3) => Ok(super::ObjectStorage::S3(s3.try_into()?)),
+            }
+        }
+    }
+
+    impl From<super::S3Config> for ProtoS3 {
+        fn from(value: super::S3Config) -> Self {
+            Self {
+                bucket: value.bucket,
+                prefix: value.prefix,
+                endpoint: value.endpoint.map(|url| url.to_string()),
+            }
+        }
+    }
+
+    impl TryFrom<ProtoS3> for super::S3Config {
+        type Error = anyhow::Error;
+
+        fn try_from(value: ProtoS3) -> Result<Self, Self::Error> {
+            Ok(Self {
+                bucket: value.bucket,
+                prefix: value.prefix,
+                endpoint: value
+                    .endpoint
+                    .map(|url| Url::parse(url.as_str()))
+                    .transpose()?,
+            })
         }
     }
 
@@ -149,7 +225,7 @@ pub mod protos {
         fn from(value: super::Email) -> Self {
             Self {
                 email_type: match value {
-                    super::Email::IMAP => Some(EmailType::Imap(ProtoImap::default())),
+                    super::Email::IMAP(config) => Some(EmailType::Imap(config.into())),
                     super::Email::GMAIL => Some(EmailType::Gmail(ProtoGmail::default())),
                 },
             }
@@ -163,20 +239,48 @@ pub mod protos {
             value
                 .email_type
                 .map(|s| match s {
-                    EmailType::Imap(_) => super::Email::IMAP,
+                    EmailType::Imap(imap) => super::Email::IMAP(imap.into()),
                     EmailType::Gmail(_) => super::Email::GMAIL,
                 })
                 .ok_or(anyhow!("invalid email_type"))
         }
     }
 
+    impl From<super::ImapConfig> for ProtoImap {
+        fn from(value: super::ImapConfig) -> Self {
+            Self {
+                server: value.server,
+                account: value.account,
+                folders: value.folders,
+            }
+        }
+    }
+
+    impl From<ProtoImap> for super::ImapConfig {
+        fn from(value: ProtoImap) -> Self {
+            Self {
+                server: value.server,
+                account: value.account,
+                folders: value.folders,
+            }
+        }
+    }
+
     impl From<super::FileSystem> for Filesystem {
         fn from(value: super::FileSystem) -> Self {
             Self {
                 filesystem_type: match value {
-                    super::FileSystem::Posix => Some(FilesystemType::Posix(ProtoPosix::default())),
-                    super::FileSystem::Windows => {
-                        Some(FilesystemType::Windows(ProtoWindows::default()))
+                    super::FileSystem::Posix(config) => Some(FilesystemType::Posix(ProtoPosix {
+                        root: config.root,
+                        include: config.include,
+                        exclude: config.exclude,
+                    })),
+                    super::FileSystem::Windows(config) => {
+                        Some(FilesystemType::Windows(ProtoWindows {
+                            root: config.root,
+                            include: config.include,
+                            exclude: config.exclude,
+                        }))
                     }
                 },
             }
@@ -190,8 +294,20 @@ pub mod protos {
             value
                 .filesystem_type
                 .map(|s| match s {
-                    FilesystemType::Posix(_) => super::FileSystem::Posix,
-                    FilesystemType::Windows(_) => super::FileSystem::Windows,
+                    FilesystemType::Posix(posix) => {
+                        super::FileSystem::Posix(super::FileSystemConfig {
+                            root: posix.root,
+                            include: posix.include,
+                            exclude: posix.exclude,
+                        })
+                    }
+                    FilesystemType::Windows(windows) => {
+                        super::FileSystem::Windows(super::FileSystemConfig {
+                            root: windows.root,
+                            include: windows.include,
+                            exclude: windows.exclude,
+                        })
+                    }
                 })
                 .ok_or(anyhow!("invalid filesystem_type"))
         }
@@ -0,0 +1,279 @@
+use crate::{ArkCreationDetails, ConfidentialString, EitherWorkerKey};
+use bon::Builder;
+use qrcode::{Color, EcLevel, QrCode};
+use std::fmt::Write;
+use zeroize::Zeroize;
+
+const PAGE_WIDTH: f32 = 210.0;
+const PAGE_HEIGHT: f32 = 297.0;
+const MARGIN: f32 = 15.0;
+const QR_SIZE: f32 = 52.0;
+const QR_QUIET_ZONE: usize = 4;
+const KEY_CHARS_PER_LINE: usize = 26;
+
+/// Printable backup of a newly created Ark, rendered as a single A4 page SVG.
+///
+/// Contains the Ark's address & metadata, the mnemonic of the Ark Seed (if given)
+/// and all secret keys, both as text and as QR codes.
+///
+/// QR codes contain the bech32 encoded key in upper case, which QR readers encode
+/// more compactly. Scanned payloads can be read back with `ArkAccessor::from_str`,
+/// or the `FromStr` implementation of the respective key type.
+#[derive(Builder)]
+pub struct PaperBackup<'a> {
+    details: &'a ArkCreationDetails,
+    mnemonic: Option<&'a ConfidentialString>,
+}
+
+impl PaperBackup<'_> {
+    pub fn to_svg(&self) -> anyhow::Result<ConfidentialString> {
+        let mut svg = String::new();
+        self.render(&mut svg)?;
+        let res = ConfidentialString::from(svg.clone());
+        svg.zeroize();
+        Ok(res)
+    }
+
+    fn render(&self, svg: &mut String) -> anyhow::Result<()> {
+        let manifest = &self.details.manifest;
+
+        writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{PAGE_WIDTH}mm" height="{PAGE_HEIGHT}mm" viewBox="0 0 {PAGE_WIDTH} {PAGE_HEIGHT}" font-family="sans-serif">"#
+        )?;
+        writeln!(
+            svg,
+            r#"<rect width="{PAGE_WIDTH}" height="{PAGE_HEIGHT}" fill="white"/>"#
+        )?;
+
+        let mut y = MARGIN + 6.0;
+        text(svg, MARGIN, y, 7.0, "bold", "Ark Paper Backup")?;
+        y += 6.0;
+        text(
+            svg,
+            MARGIN,
+            y,
+            3.0,
+            "normal",
+            "Keep this page secret & offline. Anyone holding it has full control over the Ark.",
+        )?;
+
+        y += 10.0;
+        y = field(svg, y, "Name", &[manifest.name.as_str()])?;
+        if let Some(description) = &manifest.description {
+            y = field(svg, y, "Description", &[description.as_str()])?;
+        }
+        let created = manifest.created.to_rfc3339();
+        y = field(svg, y, "Created", &[created.as_str()])?;
+        let address = self.details.address.to_string();
+        y = field(svg, y, "Ark Address", &split_lines(&address, 64))?;
+
+        if let Some(mnemonic) = self.mnemonic {
+            y += 4.0;
+            text(svg, MARGIN, y, 4.0, "bold", "Ark Seed")?;
+            y += 6.0;
+            let column_width = (PAGE_WIDTH - 2.0 * MARGIN) / 4.0;
+            for (i, word) in mnemonic.as_ref().split_whitespace().enumerate() {
+                let x = MARGIN + (i % 4) as f32 * column_width;
+                let row_y = y + (i / 4) as f32 * 6.0;
+                text(
+                    svg,
+                    x,
+                    row_y,
+                    4.0,
+                    "normal",
+                    format!("{:>2}. {}", i + 1, word).as_str(),
+                )?;
+            }
+            y += mnemonic.as_ref().split_whitespace().count().div_ceil(4) as f32 * 6.0;
+        }
+
+        y += 6.0;
+        let mut keys = vec![
+            ("Helm Key", self.details.helm_key.danger_to_string()),
+            ("Data Key", self.details.data_key.danger_to_string()),
+        ];
+        if let EitherWorkerKey::Secret(worker_key) = &self.details.worker_key {
+            keys.push(("Worker Key", worker_key.danger_to_string()));
+        }
+        let column_width = (PAGE_WIDTH - 2.0 * MARGIN) / 3.0;
+        for (i, (label, key)) in keys.iter().enumerate() {
+            let x = MARGIN + i as f32 * column_width;
+            text(svg, x, y, 4.0, "bold", label)?;
+            let mut payload = key.to_uppercase();
+            let res = qr_code(svg, x, y + 2.0, payload.as_str());
+            payload.zeroize();
+            res?;
+            for (line_no, line) in split_lines(key, KEY_CHARS_PER_LINE).iter().enumerate() {
+                monospace(svg, x, y + QR_SIZE + 6.0 + line_no as f32 * 3.5, line)?;
+            }
+        }
+        for (_, key) in keys.iter_mut() {
+            key.zeroize();
+        }
+
+        text(
+            svg,
+            MARGIN,
+            PAGE_HEIGHT - MARGIN,
+            2.5,
+            "normal",
+            "All keys can be regenerated from the Ark Seed. A passphrase, if one was chosen, is NOT part of this backup.",
+        )?;
+        writeln!(svg, "</svg>")?;
+        Ok(())
+    }
+}
+
+fn field(svg: &mut String, y: f32, label: &str, lines: &[&str]) -> anyhow::Result<f32> {
+    text(svg, MARGIN, y, 3.0, "bold", label)?;
+    let mut y = y + 5.0;
+    for line in lines {
+        monospace(svg, MARGIN, y, line)?;
+        y += 4.5;
+    }
+    Ok(y + 2.0)
+}
+
+fn text(
+    svg: &mut String,
+    x: f32,
+    y: f32,
+    size: f32,
+    weight: &str,
+    content: &str,
+) -> anyhow::Result<()> {
+    writeln!(
+        svg,
+        r#"<text x="{x}" y="{y}" font-size="{size}" font-weight="{weight}">{}</text>"#,
+        escape(content)
+    )?;
+    Ok(())
+}
+
+fn monospace(svg: &mut String, x: f32, y: f32, content: &str) -> anyhow::Result<()> {
+    writeln!(
+        svg,
+        r#"<text x="{x}" y="{y}" font-size="3.2" font-family="monospace">{}</text>"#,
+        escape(content)
+    )?;
+    Ok(())
+}
+
+fn qr_code(svg: &mut String, x: f32, y: f32, payload: &str) -> anyhow::Result<()> {
+    let code = QrCode::with_error_correction_level(payload, EcLevel::M)?;
+    let modules = code.width() + 2 * QR_QUIET_ZONE;
+    let scale = QR_SIZE / modules as f32;
+    write!(
+        svg,
+        r#"<g transform="translate({x} {y}) scale({scale})"><path fill="black" shape-rendering="crispEdges" d=""#
+    )?;
+    for (i, color) in code.to_colors().into_iter().enumerate() {
+        if color == Color::Dark {
+            let col = i % code.width() + QR_QUIET_ZONE;
+            let row = i / code.width() + QR_QUIET_ZONE;
+            write!(svg, "M{col},{row}h1v1h-1z")?;
+        }
+    }
+    writeln!(svg, r#""/></g>"#)?;
+    Ok(())
+}
+
+fn split_lines(s: &str, chars_per_line: usize) -> Vec<&str> {
+    let mut lines = vec![];
+    let mut rest = s;
+    while let Some((idx, _)) = rest.char_indices().nth(chars_per_line) {
+        let (line, remainder) = rest.split_at(idx);
+        lines.push(line);
+        rest = remainder;
+    }
+    lines.push(rest);
+    lines
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data_key::DataKeySeed;
+    use crate::helm_key::HelmKeySeed;
+    use crate::{
+        ArkAccessor, ArkCreationSettings, ArkSeed, Core, DataKey, EvmWallet, HelmKey,
+        MemoryStorage, Storage, WorkerKey,
+    };
+    use std::str::FromStr;
+
+    /// Scanned QR payloads are the upper case form of the key.
+    fn scanned(key: &str) -> String {
+        format!("{}\n", key.to_uppercase())
+    }
+
+    #[test]
+    fn read_back_scanned_keys() {
+        let (ark_seed, _) = ArkSeed::random(None);
+
+        let helm_key = ark_seed.helm_key(&HelmKeySeed::random());
+        let payload = scanned(&helm_key.danger_to_string());
+        assert_eq!(HelmKey::from_str(payload.trim()).unwrap(), helm_key);
+        assert!(matches!(
+            ArkAccessor::from_str(&payload).unwrap(),
+            ArkAccessor::HelmKey(key) if key == helm_key
+        ));
+
+        let data_key = ark_seed.data_key(&DataKeySeed::random());
+        let payload = scanned(&data_key.danger_to_string());
+        assert_eq!(DataKey::from_str(payload.trim()).unwrap(), data_key);
+        assert!(matches!(
+            ArkAccessor::from_str(&payload).unwrap(),
+            ArkAccessor::DataKey(key) if key == data_key
+        ));
+
+        let worker_key = WorkerKey::random();
+        let payload = scanned(&worker_key.danger_to_string());
+        assert_eq!(WorkerKey::from_str(payload.trim()).unwrap(), worker_key);
+        assert!(matches!(
+            ArkAccessor::from_str(&payload).unwrap(),
+            ArkAccessor::WorkerKey(key) if key == worker_key
+        ));
+    }
+
+    #[tokio::test]
+    async fn render_svg() {
+        let storage: Storage = MemoryStorage::new().into();
+        let wallet = EvmWallet::new_with_random_wallet(autonomi::Network::ArbitrumSepoliaTest);
+        let (ark_seed, mnemonic) = ArkSeed::random(None);
+        let settings = ArkCreationSettings::builder()
+            .name("Backup <Test> & Co")
+            .build();
+        let (details, _) = Core::create_ark(settings, &ark_seed, &storage, &wallet, None)
+            .1
+            .await
+            .map_err(|(err, _)| err)
+            .unwrap();
+
+        let svg = PaperBackup::builder()
+            .details(&details)
+            .mnemonic(&mnemonic)
+            .build()
+            .to_svg()
+            .unwrap();
+        let svg = svg.as_ref();
+
+        assert!(svg.starts_with("<svg "));
+        assert!(svg.trim_end().ends_with("</svg>"));
+        assert!(svg.contains("Backup &lt;Test&gt; &amp; Co"));
+        // helm, data & worker key
+        assert_eq!(svg.matches("<path ").count(), 3);
+        for word in mnemonic.as_ref().split_whitespace() {
+            assert!(svg.contains(word));
+        }
+        let helm_key = details.helm_key.danger_to_string();
+        assert!(svg.contains(&helm_key[..KEY_CHARS_PER_LINE]));
+    }
+}
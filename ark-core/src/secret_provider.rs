@@ -0,0 +1,272 @@
+use crate::{ArkSeed, ConfidentialString};
+use anyhow::{Context, anyhow, bail, ensure};
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
+use std::str::FromStr;
+use zeroize::Zeroize;
+
+/// Non-interactive source of a secret, such as a key, the Ark Seed or a wallet secret.
+///
+/// Written as `<kind>:<location>`:
+///
+/// - `file:/path/to/secret` reads the file
+/// - `fd:3` reads an inherited file descriptor until EOF (unix only, stdio is refused)
+/// - `env:VAR_NAME` reads an environment variable
+/// - `cmd:pass show ark/helm` runs a shell command and reads its output
+/// - `secret-service:attr=value,...` looks up an item in the freedesktop Secret Service
+///
+/// Surrounding whitespace, e.g. a trailing newline, is removed from the secret.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SecretProvider {
+    File(PathBuf),
+    Fd(i32),
+    Env(String),
+    Command(String),
+    SecretService(BTreeMap<String, String>),
+}
+
+impl SecretProvider {
+    pub async fn resolve(&self) -> anyhow::Result<ConfidentialString> {
+        let mut raw = match self {
+            Self::File(path) => tokio::fs::read(path)
+                .await
+                .with_context(|| format!("unable to read secret from [{}]", path.display()))?,
+            Self::Fd(fd) => read_fd(*fd).await?,
+            Self::Env(name) => std::env::var(name)
+                .with_context(|| format!("environment variable [{}] not set", name))?
+                .into_bytes(),
+            Self::Command(cmd) => run_command(cmd).await?,
+            Self::SecretService(attributes) => secret_service_lookup(attributes).await?,
+        };
+        let res = std::str::from_utf8(&raw).map(|s| s.trim().to_string());
+        raw.zeroize();
+        let secret = res.context("secret is not valid utf-8")?;
+        ensure!(!secret.is_empty(), "secret from [{}] is empty", self);
+        Ok(secret.into())
+    }
+
+    /// Resolves and parses a bech32 encoded key, e.g. a `HelmKey` or `WorkerKey`.
+    pub async fn resolve_key<K: FromStr<Err = anyhow::Error>>(&self) -> anyhow::Result<K> {
+        K::from_str(self.resolve().await?.as_ref())
+            .with_context(|| format!("invalid key from [{}]", self))
+    }
+
+    /// Resolves the mnemonic of an Ark Seed.
+    pub async fn resolve_ark_seed(
+        &self,
+        passphrase: Option<&ConfidentialString>,
+    ) -> anyhow::Result<ArkSeed> {
+        let mnemonic = self.resolve().await?;
+        ArkSeed::try_from_mnemonic(mnemonic.as_ref().to_string(), passphrase)
+            .with_context(|| format!("invalid ark seed from [{}]", self))
+    }
+}
+
+impl FromStr for SecretProvider {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (kind, location) = s.split_once(':').ok_or(anyhow!(
+            "invalid secret provider [{}], expected <kind>:<location>",
+            s
+        ))?;
+        ensure!(
+            !location.is_empty(),
+            "secret provider [{}] is incomplete",
+            s
+        );
+        Ok(match kind {
+            "file" => Self::File(PathBuf::from(location)),
+            "fd" => Self::Fd(location.parse().context("invalid file descriptor")?),
+            "env" => Self::Env(location.to_string()),
+            "cmd" => Self::Command(location.to_string()),
+            "secret-service" => Self::SecretService(
+                location
+                    .split(',')
+                    .map(|pair| {
+                        pair.split_once('=')
+                            .map(|(k, v)| (k.trim().to_string(), v.trim().to_string()))
+                            .ok_or(anyhow!("invalid secret service attribute [{}]", pair))
+                    })
+                    .collect::<anyhow::Result<_>>()?,
+            ),
+            other => bail!("unknown secret provider kind [{}]", other),
+        })
+    }
+}
+
+impl Display for SecretProvider {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::File(path) => write!(f, "file:{}", path.display()),
+            Self::Fd(fd) => write!(f, "fd:{}", fd),
+            Self::Env(name) => write!(f, "env:{}", name),
+            Self::Command(cmd) => write!(f, "cmd:{}", cmd),
+            Self::SecretService(attributes) => {
+                write!(f, "secret-service:")?;
+                for (i, (k, v)) in attributes.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{}={}", k, v)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(unix)]
+async fn read_fd(fd: i32) -> anyhow::Result<Vec<u8>> {
+    use std::io::Read;
+    use std::os::fd::BorrowedFd;
+
+    ensure!(
+        fd > 2,
+        "refusing to read a secret from standard stream fd [{}]",
+        fd
+    );
+    tokio::task::spawn_blocking(move || {
+        // SAFETY: the descriptor is inherited from the parent process and only borrowed
+        // long enough to duplicate it, the original stays open and owned by the process
+        let mut file = std::fs::File::from(
+            unsafe { BorrowedFd::borrow_raw(fd) }
+                .try_clone_to_owned()
+                .with_context(|| format!("unable to open secret fd [{}]", fd))?,
+        );
+        let mut buf = Vec::new();
+        file.read_to_end(&mut buf)
+            .with_context(|| format!("unable to read secret from fd [{}]", fd))?;
+        Ok(buf)
+    })
+    .await?
+}
+
+#[cfg(not(unix))]
+async fn read_fd(_fd: i32) -> anyhow::Result<Vec<u8>> {
+    bail!("reading secrets from file descriptors is only supported on unix")
+}
+
+async fn run_command(cmd: &str) -> anyhow::Result<Vec<u8>> {
+    #[cfg(unix)]
+    let mut command = {
+        let mut command = tokio::process::Command::new("sh");
+        command.arg("-c").arg(cmd);
+        command
+    };
+    #[cfg(not(unix))]
+    let mut command = {
+        let mut command = tokio::process::Command::new("cmd");
+        command.arg("/C").arg(cmd);
+        command
+    };
+    let mut output = command
+        .stdin(std::process::Stdio::null())
+        .stderr(std::process::Stdio::inherit())
+        .output()
+        .await
+        .with_context(|| format!("unable to run secret command [{}]", cmd))?;
+    if !output.status.success() {
+        output.stdout.zeroize();
+        bail!("secret command [{}] failed: {}", cmd, output.status);
+    }
+    Ok(output.stdout)
+}
+
+async fn secret_service_lookup(attributes: &BTreeMap<String, String>) -> anyhow::Result<Vec<u8>> {
+    use secret_service::{EncryptionType, SecretService};
+
+    let ss = SecretService::connect(EncryptionType::Dh)
+        .await
+        .context("unable to connect to the secret service")?;
+    let search = ss
+        .search_items(
+            attributes
+                .iter()
+                .map(|(k, v)| (k.as_str(), v.as_str()))
+                .collect(),
+        )
+        .await?;
+    let item = match search.unlocked.into_iter().next() {
+        Some(item) => item,
+        None => {
+            let item = search
+                .locked
+                .into_iter()
+                .next()
+                .ok_or(anyhow!("no matching secret service item found"))?;
+            item.unlock().await?;
+            item
+        }
+    };
+    Ok(item.get_secret().await?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::WorkerKey;
+
+    #[test]
+    fn parse_and_display() {
+        for s in [
+            "file:/run/secrets/helm key",
+            "fd:3",
+            "env:ARK_HELM_KEY",
+            "cmd:pass show ark/helm",
+            "secret-service:application=ark,name=helm",
+        ] {
+            assert_eq!(SecretProvider::from_str(s).unwrap().to_string(), s);
+        }
+        for s in ["ARK_HELM_KEY", "env:", "fd:three", "vault:ark/helm"] {
+            assert!(SecretProvider::from_str(s).is_err(), "{}", s);
+        }
+    }
+
+    #[tokio::test]
+    async fn resolve_trimmed_secret_from_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("worker");
+        let worker_key = WorkerKey::random();
+        std::fs::write(&path, format!("{}\n", worker_key.danger_to_string())).unwrap();
+
+        let provider = SecretProvider::File(path.clone());
+        assert_eq!(
+            provider.resolve_key::<WorkerKey>().await.unwrap(),
+            worker_key
+        );
+
+        std::fs::write(&path, " \n").unwrap();
+        assert!(provider.resolve().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn resolve_secret_from_env() {
+        // SAFETY: the variable is unique to this test
+        unsafe { std::env::set_var("ARK_TEST_SECRET_PROVIDER", "s3cret\n") };
+        let provider = SecretProvider::from_str("env:ARK_TEST_SECRET_PROVIDER").unwrap();
+        assert_eq!(provider.resolve().await.unwrap().as_ref(), "s3cret");
+
+        let provider = SecretProvider::from_str("env:ARK_TEST_SECRET_PROVIDER_UNSET").unwrap();
+        assert!(provider.resolve().await.is_err());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn resolve_secret_from_command() {
+        let provider = SecretProvider::from_str("cmd:echo s3cret").unwrap();
+        assert_eq!(provider.resolve().await.unwrap().as_ref(), "s3cret");
+
+        let provider = SecretProvider::from_str("cmd:exit 1").unwrap();
+        assert!(provider.resolve().await.is_err());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn refuse_standard_streams() {
+        for fd in 0..=2 {
+            assert!(SecretProvider::Fd(fd).resolve().await.is_err());
+        }
+    }
+}
@@ -0,0 +1,341 @@
+use crate::{ArkSeed, ConfidentialString};
+use anyhow::{anyhow, bail, ensure};
+use bip39::Language;
+use blsttc::SecretKey;
+use rand::Rng;
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use std::fmt::{Debug, Formatter};
+use std::str::FromStr;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+/// Upper bound for the number of shares, limited by the 4 bits used for the index.
+pub const MAX_SEED_SHARES: u8 = 16;
+/// Number of words every share consists of.
+pub const SEED_SHARE_WORDS: usize = SHARE_BITS / WORD_BITS;
+
+const SET_ID_BITS: usize = 15;
+const NIBBLE_BITS: usize = 4;
+const VALUE_BITS: usize = 256;
+const CHECKSUM_BITS: usize = 29;
+const WORD_BITS: usize = 11;
+const SHARE_BITS: usize = SET_ID_BITS + 2 * NIBBLE_BITS + VALUE_BITS + CHECKSUM_BITS;
+const CHECKSUM_DOMAIN: &[u8] = b"ark-seed-share-v1";
+
+/// One of the shares an [`ArkSeed`] was split into using Shamir's Secret Sharing.
+///
+/// Any `threshold` shares of the same set recover the seed, fewer reveal nothing about it.
+/// Shares are written down as 28 words from the English BIP39 word list. The words
+/// include the set, the threshold, the share's index and a checksum, so mistyped words
+/// and shares from different sets are detected.
+///
+/// Note: the seed is split after the BIP39 passphrase (if any) has been applied.
+/// Recovering from shares does not require the passphrase.
+#[derive(Zeroize, ZeroizeOnDrop, Clone, PartialEq, Eq)]
+pub struct SeedShare {
+    set_id: u16,
+    threshold: u8,
+    index: u8,
+    value: [u8; 32],
+}
+
+impl Debug for SeedShare {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "SeedShare {{ set_id: {}, threshold: {}, index: {}, value: <redacted> }}",
+            self.set_id, self.threshold, self.index
+        )
+    }
+}
+
+impl SeedShare {
+    /// Identifies the set this share belongs to, shares of different sets can not be combined.
+    pub fn set_id(&self) -> u16 {
+        self.set_id
+    }
+
+    /// Number of shares required to recover the seed.
+    pub fn threshold(&self) -> u8 {
+        self.threshold
+    }
+
+    /// Position of this share within its set, starting at 1.
+    pub fn index(&self) -> u8 {
+        self.index
+    }
+
+    pub fn to_words(&self) -> ConfidentialString {
+        let mut bits = self.payload_bits();
+        bits.extend(checksum_bits(&bits));
+        let word_list = Language::English.word_list();
+        let words = bits
+            .chunks(WORD_BITS)
+            .map(|chunk| word_list[read_bits(chunk) as usize])
+            .collect::<Vec<_>>()
+            .join(" ");
+        bits.zeroize();
+        words.into()
+    }
+
+    fn payload_bits(&self) -> Vec<bool> {
+        let mut bits = Vec::with_capacity(SHARE_BITS);
+        write_bits(&mut bits, self.set_id as u32, SET_ID_BITS);
+        write_bits(&mut bits, (self.threshold - 1) as u32, NIBBLE_BITS);
+        write_bits(&mut bits, (self.index - 1) as u32, NIBBLE_BITS);
+        for byte in self.value {
+            write_bits(&mut bits, byte as u32, 8);
+        }
+        bits
+    }
+}
+
+impl FromStr for SeedShare {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut bits = Vec::with_capacity(SHARE_BITS);
+        let mut count = 0;
+        for word in s.split_whitespace() {
+            let idx = Language::English
+                .find_word(word.to_lowercase().as_str())
+                .ok_or(anyhow!("unknown word [{}] in seed share", word))?;
+            write_bits(&mut bits, idx as u32, WORD_BITS);
+            count += 1;
+        }
+        if count != SEED_SHARE_WORDS {
+            bits.zeroize();
+            bail!(
+                "invalid seed share, exactly {} words are expected",
+                SEED_SHARE_WORDS
+            );
+        }
+
+        let (payload, checksum) = bits.split_at(SHARE_BITS - CHECKSUM_BITS);
+        let valid = checksum_bits(payload).as_slice() == checksum;
+        let mut value = [0u8; 32];
+        let offset = SET_ID_BITS + 2 * NIBBLE_BITS;
+        for (i, byte) in value.iter_mut().enumerate() {
+            *byte = read_bits(&payload[offset + i * 8..offset + (i + 1) * 8]) as u8;
+        }
+        let share = Self {
+            set_id: read_bits(&payload[..SET_ID_BITS]) as u16,
+            threshold: read_bits(&payload[SET_ID_BITS..SET_ID_BITS + NIBBLE_BITS]) as u8 + 1,
+            index: read_bits(&payload[SET_ID_BITS + NIBBLE_BITS..offset]) as u8 + 1,
+            value,
+        };
+        value.zeroize();
+        bits.zeroize();
+        ensure!(valid, "invalid seed share, checksum mismatch");
+        Ok(share)
+    }
+}
+
+impl ArkSeed {
+    /// Splits the seed into `shares` shares, any `threshold` of which recover it.
+    pub fn split(&self, threshold: u8, shares: u8) -> anyhow::Result<Vec<SeedShare>> {
+        ensure!(
+            (2..=shares).contains(&threshold),
+            "threshold needs to be at least 2 and must not exceed the number of shares"
+        );
+        ensure!(
+            shares <= MAX_SEED_SHARES,
+            "at most {} shares are supported",
+            MAX_SEED_SHARES
+        );
+
+        let mut rng = rand::rng();
+        let set_id = rng.random_range(0..(1 << SET_ID_BITS)) as u16;
+        let mut secret = self.as_ref().to_bytes();
+        let mut shares = (1..=shares)
+            .map(|index| SeedShare {
+                set_id,
+                threshold,
+                index,
+                value: [0u8; 32],
+            })
+            .collect::<Vec<_>>();
+
+        // every byte of the secret is the constant term of its own random polynomial
+        let mut coefficients = vec![0u8; threshold as usize];
+        for (pos, byte) in secret.iter().enumerate() {
+            coefficients[0] = *byte;
+            rng.fill(&mut coefficients[1..]);
+            for share in shares.iter_mut() {
+                share.value[pos] = gf256::evaluate(&coefficients, share.index);
+            }
+        }
+        coefficients.zeroize();
+        secret.zeroize();
+        Ok(shares)
+    }
+
+    /// Recovers the seed from at least `threshold` shares of the same set.
+    ///
+    /// There is no way to tell whether the result is the expected seed, compare
+    /// its [`ArkSeed::address`] with the expected one.
+    pub fn combine(shares: &[SeedShare]) -> anyhow::Result<Self> {
+        let first = shares.first().ok_or(anyhow!("no seed shares given"))?;
+        let mut indices = HashSet::new();
+        for share in shares {
+            ensure!(
+                share.set_id == first.set_id && share.threshold == first.threshold,
+                "seed shares belong to different sets"
+            );
+            ensure!(
+                indices.insert(share.index),
+                "seed share #{} given more than once",
+                share.index
+            );
+        }
+        ensure!(
+            shares.len() >= first.threshold as usize,
+            "{} seed shares required, only {} given",
+            first.threshold,
+            shares.len()
+        );
+
+        let shares = &shares[..first.threshold as usize];
+        let mut secret = [0u8; 32];
+        for (pos, byte) in secret.iter_mut().enumerate() {
+            *byte = gf256::interpolate_at_zero(shares.iter().map(|s| (s.index, s.value[pos])));
+        }
+        let secret_key = SecretKey::from_bytes(secret);
+        secret.zeroize();
+        Ok(Self::new(secret_key.map_err(|_| {
+            anyhow!("seed shares do not combine into a valid seed")
+        })?))
+    }
+}
+
+fn checksum_bits(payload: &[bool]) -> Vec<bool> {
+    let mut bytes = payload
+        .chunks(8)
+        .map(|chunk| {
+            // the last chunk is padded with zeros
+            (read_bits(chunk) << (8 - chunk.len())) as u8
+        })
+        .collect::<Vec<_>>();
+    let digest = Sha256::new()
+        .chain_update(CHECKSUM_DOMAIN)
+        .chain_update(&bytes)
+        .finalize();
+    bytes.zeroize();
+    let mut bits = Vec::with_capacity(32);
+    for byte in &digest[..4] {
+        write_bits(&mut bits, *byte as u32, 8);
+    }
+    bits.truncate(CHECKSUM_BITS);
+    bits
+}
+
+fn write_bits(bits: &mut Vec<bool>, value: u32, len: usize) {
+    bits.extend((0..len).rev().map(|i| (value >> i) & 1 == 1));
+}
+
+fn read_bits(bits: &[bool]) -> u32 {
+    bits.iter().fold(0, |acc, bit| (acc << 1) | *bit as u32)
+}
+
+/// Arithmetic in GF(2^8), using the AES polynomial.
+mod gf256 {
+    /// Constant-time: always runs all 8 rounds and masks instead of branching on secret bits.
+    fn mul(mut a: u8, mut b: u8) -> u8 {
+        let mut product = 0;
+        for _ in 0..8 {
+            product ^= a & (b & 1).wrapping_neg();
+            let carry = (a >> 7).wrapping_neg();
+            a = (a << 1) ^ (0x1b & carry);
+            b >>= 1;
+        }
+        product
+    }
+
+    fn inverse(a: u8) -> u8 {
+        // a^254 == a^-1
+        let mut result = 1;
+        for _ in 0..254 {
+            result = mul(result, a);
+        }
+        result
+    }
+
+    pub(super) fn evaluate(coefficients: &[u8], x: u8) -> u8 {
+        coefficients
+            .iter()
+            .rev()
+            .fold(0, |acc, coefficient| mul(acc, x) ^ coefficient)
+    }
+
+    pub(super) fn interpolate_at_zero(points: impl Iterator<Item = (u8, u8)> + Clone) -> u8 {
+        let mut result = 0;
+        for (i, (x_i, y_i)) in points.clone().enumerate() {
+            let mut basis = 1;
+            for (j, (x_j, _)) in points.clone().enumerate() {
+                if i != j {
+                    basis = mul(basis, mul(x_j, inverse(x_j ^ x_i)));
+                }
+            }
+            result ^= mul(y_i, basis);
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn combine_any_threshold_shares() {
+        let (ark_seed, _) = ArkSeed::random(None);
+        let shares = ark_seed.split(3, 5).unwrap();
+        assert_eq!(shares.len(), 5);
+
+        for selection in [[0, 1, 2], [4, 2, 0], [1, 3, 4]] {
+            let selected = selection.map(|i| shares[i].clone());
+            let combined = ArkSeed::combine(&selected).unwrap();
+            assert_eq!(combined.address(), ark_seed.address());
+        }
+
+        let err = ArkSeed::combine(&shares[..2]).unwrap_err();
+        assert!(err.to_string().contains("3 seed shares required"));
+    }
+
+    #[test]
+    fn refuse_shares_of_different_sets() {
+        let (ark_seed, _) = ArkSeed::random(None);
+        let mut shares = ark_seed.split(2, 3).unwrap();
+        shares.truncate(1);
+        shares.push(ark_seed.split(2, 3).unwrap().remove(1));
+        // an accidental set id collision is possible, if unlikely
+        if shares[0].set_id() != shares[1].set_id() {
+            assert!(ArkSeed::combine(&shares).is_err());
+        }
+    }
+
+    #[test]
+    fn words_round_trip() {
+        let (ark_seed, _) = ArkSeed::random(None);
+        for share in ark_seed.split(2, MAX_SEED_SHARES).unwrap() {
+            let words = share.to_words();
+            assert_eq!(words.as_ref().split_whitespace().count(), SEED_SHARE_WORDS);
+            let parsed = SeedShare::from_str(words.as_ref()).unwrap();
+            assert_eq!(parsed, share);
+        }
+    }
+
+    #[test]
+    fn detect_mistyped_word() {
+        let (ark_seed, _) = ArkSeed::random(None);
+        let share = ark_seed.split(2, 2).unwrap().remove(0);
+        let words = share.to_words();
+        let mut words = words.as_ref().split_whitespace().collect::<Vec<_>>();
+        words[5] = if words[5] == "abandon" {
+            "ability"
+        } else {
+            "abandon"
+        };
+        assert!(SeedShare::from_str(words.join(" ").as_str()).is_err());
+    }
+}
@@ -0,0 +1,610 @@
+use crate::crypto::{AgeSingleKeyEncryptionScheme, EncryptionScheme};
+use crate::storage::{Storage, StorageBackend};
+use anyhow::anyhow;
+use async_trait::async_trait;
+use autonomi::client::payment::PaymentOption;
+use autonomi::register::{RegisterAddress, RegisterValue};
+use autonomi::{
+    AttoTokens, Chunk, ChunkAddress, Pointer, PointerAddress, Scratchpad, ScratchpadAddress,
+};
+use blsttc::{PublicKey, SecretKey};
+use bon::bon;
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use uuid::Uuid;
+use zeroize::Zeroize;
+
+const CHUNKS: &str = "chunks";
+const REGISTERS: &str = "registers";
+const REGISTER_HISTORIES: &str = "register_histories";
+const POINTERS: &str = "pointers";
+const SCRATCHPADS: &str = "scratchpads";
+
+/// Secret used to encrypt all records in a [`CachingStorage`].
+pub struct CacheKey(SecretKey);
+
+impl CacheKey {
+    pub fn random() -> Self {
+        Self(SecretKey::random())
+    }
+
+    /// Reads the key from `path`, creating a new random one if the file does not exist yet.
+    ///
+    /// New key files are only readable by the current user (on Unix).
+    pub fn load_or_create(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        match std::fs::read_to_string(path) {
+            Ok(mut hex) => {
+                let key = SecretKey::from_hex(hex.trim())
+                    .map_err(|e| anyhow!("invalid cache key at [{}]: {}", path.display(), e));
+                hex.zeroize();
+                Ok(Self(key?))
+            }
+            Err(err) if err.kind() == ErrorKind::NotFound => {
+                let key = Self::random();
+                if let Some(parent) = path.parent() {
+                    std::fs::create_dir_all(parent)?;
+                }
+                let mut options = std::fs::OpenOptions::new();
+                options.write(true).create_new(true);
+                #[cfg(unix)]
+                std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+                let mut hex = key.0.to_hex();
+                let res = std::io::Write::write_all(&mut options.open(path)?, hex.as_bytes());
+                hex.zeroize();
+                res?;
+                Ok(key)
+            }
+            Err(err) => Err(err.into()),
+        }
+    }
+}
+
+/// Keeps a local, encrypted copy of every record read from or written to the
+/// underlying storage.
+///
+/// - Chunks are immutable and served from the cache indefinitely.
+/// - Registers, pointers & scratchpads are served from the cache for `ttl`.
+///   For another `max_stale` after that, the cached copy is still returned
+///   immediately while a fresh one is fetched in the background.
+/// - If fetching fails, e.g. because the network is unavailable, the last known
+///   copy is returned regardless of its age.
+/// - Records explicitly invalidated, such as the key registers before a key is
+///   verified or rotated, are read from the underlying storage and never served
+///   from the cache, neither fresh nor stale.
+///
+/// Without underlying storage, the cache operates offline: only records cached
+/// earlier can be read and all writes fail.
+///
+/// Record contents are encrypted with the [`CacheKey`], their addresses are not.
+#[derive(Clone)]
+pub struct CachingStorage {
+    inner: Option<Storage>,
+    cache: DiskCache,
+    ttl: Duration,
+    max_stale: Duration,
+    invalidated: Arc<Mutex<HashSet<PathBuf>>>,
+}
+
+#[bon]
+impl CachingStorage {
+    #[builder]
+    pub fn new(
+        /// Storage to cache, `None` to operate offline
+        inner: Option<Storage>,
+        dir: impl AsRef<Path>,
+        key: CacheKey,
+        #[builder(default = Duration::from_secs(300))] ttl: Duration,
+        #[builder(default = Duration::from_secs(86400))] max_stale: Duration,
+    ) -> anyhow::Result<Self> {
+        let dir = dir.as_ref().to_path_buf();
+        for kind in [CHUNKS, REGISTERS, REGISTER_HISTORIES, POINTERS, SCRATCHPADS] {
+            std::fs::create_dir_all(dir.join(kind))?;
+        }
+        Ok(Self {
+            inner,
+            cache: DiskCache {
+                dir: Arc::new(dir),
+                key: Arc::new(key),
+            },
+            ttl,
+            max_stale,
+            invalidated: Arc::new(Mutex::new(HashSet::new())),
+        })
+    }
+
+    pub fn is_offline(&self) -> bool {
+        self.inner.is_none()
+    }
+
+    fn inner(&self) -> anyhow::Result<&Storage> {
+        self.inner
+            .as_ref()
+            .ok_or(anyhow!("offline, only cached records are available"))
+    }
+
+    async fn get<T, F, Fut>(
+        &self,
+        kind: &str,
+        address: String,
+        fetch: F,
+    ) -> anyhow::Result<Option<T>>
+    where
+        T: Serialize + DeserializeOwned + Send + Sync + 'static,
+        F: FnOnce(Storage) -> Fut,
+        Fut: Future<Output = anyhow::Result<Option<T>>> + Send + 'static,
+    {
+        let path = self.cache.path(kind, address);
+        let cached = self.cache.read::<T>(&path).await;
+        let invalidated = self
+            .invalidated
+            .lock()
+            .expect("lock not to be poisoned")
+            .remove(&path);
+
+        let inner = match &self.inner {
+            Some(inner) => inner.clone(),
+            None => return Ok(cached.map(|e| e.value)),
+        };
+
+        let cached = match cached {
+            Some(entry) if !invalidated => {
+                let age = (Utc::now() - entry.fetched).to_std().unwrap_or_default();
+                if age < self.ttl {
+                    return Ok(Some(entry.value));
+                }
+                if age < self.ttl + self.max_stale {
+                    let cache = self.cache.clone();
+                    let fetch = fetch(inner);
+                    tokio::spawn(async move {
+                        match fetch.await {
+                            Ok(Some(value)) => cache.write(&path, &value).await,
+                            Ok(None) => {}
+                            Err(err) => tracing::debug!(
+                                error = %err,
+                                path = %path.display(),
+                                "revalidation failed"
+                            ),
+                        }
+                    });
+                    return Ok(Some(entry.value));
+                }
+                Some(entry)
+            }
+            cached => cached,
+        };
+
+        match fetch(inner).await {
+            Ok(Some(value)) => {
+                self.cache.write(&path, &value).await;
+                Ok(Some(value))
+            }
+            Ok(None) => Ok(None),
+            Err(err) => match cached {
+                Some(entry) if !invalidated => {
+                    tracing::warn!(
+                        error = %err,
+                        fetched = %entry.fetched,
+                        "unable to fetch record, returning cached copy"
+                    );
+                    Ok(Some(entry.value))
+                }
+                _ => {
+                    if invalidated {
+                        // the next read still has to reach the underlying storage
+                        self.invalidated
+                            .lock()
+                            .expect("lock not to be poisoned")
+                            .insert(path);
+                    }
+                    Err(err)
+                }
+            },
+        }
+    }
+}
+
+#[async_trait]
+impl StorageBackend for CachingStorage {
+    async fn chunk_get(&self, address: &ChunkAddress) -> anyhow::Result<Option<Chunk>> {
+        let path = self.cache.path(CHUNKS, address.to_hex());
+        if let Some(entry) = self.cache.read(&path).await {
+            return Ok(Some(entry.value));
+        }
+        let chunk = self.inner()?.chunk_get(address).await?;
+        if let Some(chunk) = &chunk {
+            self.cache.write(&path, chunk).await;
+        }
+        Ok(chunk)
+    }
+
+    async fn chunk_put(
+        &self,
+        chunk: &Chunk,
+        payment: PaymentOption,
+    ) -> anyhow::Result<(AttoTokens, ChunkAddress)> {
+        let (cost, address) = self.inner()?.chunk_put(chunk, payment).await?;
+        self.cache
+            .write(&self.cache.path(CHUNKS, address.to_hex()), chunk)
+            .await;
+        Ok((cost, address))
+    }
+
+    async fn chunk_cost(&self, chunk: &Chunk) -> anyhow::Result<AttoTokens> {
+        self.inner()?.chunk_cost(chunk).await
+    }
+
+    async fn register_get(
+        &self,
+        address: &RegisterAddress,
+    ) -> anyhow::Result<Option<RegisterValue>> {
+        let address = address.clone();
+        self.get(REGISTERS, address.to_hex(), move |storage| async move {
+            storage.register_get(&address).await
+        })
+        .await
+    }
+
+    async fn register_history(
+        &self,
+        address: &RegisterAddress,
+    ) -> anyhow::Result<Vec<RegisterValue>> {
+        let address = address.clone();
+        Ok(self
+            .get(
+                REGISTER_HISTORIES,
+                address.to_hex(),
+                move |storage| async move {
+                    let history = storage.register_history(&address).await?;
+                    Ok((!history.is_empty()).then_some(history))
+                },
+            )
+            .await?
+            .unwrap_or_default())
+    }
+
+    async fn register_create(
+        &self,
+        owner: &SecretKey,
+        value: RegisterValue,
+        payment: PaymentOption,
+    ) -> anyhow::Result<(AttoTokens, RegisterAddress)> {
+        let (cost, address) = self.inner()?.register_create(owner, value, payment).await?;
+        self.cache.updated_register(&address, value).await;
+        Ok((cost, address))
+    }
+
+    async fn register_update(
+        &self,
+        owner: &SecretKey,
+        value: RegisterValue,
+        payment: PaymentOption,
+    ) -> anyhow::Result<AttoTokens> {
+        let cost = self.inner()?.register_update(owner, value, payment).await?;
+        self.cache
+            .updated_register(&RegisterAddress::new(owner.public_key()), value)
+            .await;
+        Ok(cost)
+    }
+
+    async fn register_create_cost(&self, owner: &PublicKey) -> anyhow::Result<AttoTokens> {
+        self.inner()?.register_create_cost(owner).await
+    }
+
+    async fn register_update_cost(&self, owner: &PublicKey) -> anyhow::Result<AttoTokens> {
+        self.inner()?.register_update_cost(owner).await
+    }
+
+    async fn register_invalidate(&self, address: &RegisterAddress) -> anyhow::Result<()> {
+        self.invalidated
+            .lock()
+            .expect("lock not to be poisoned")
+            .extend(
+                [REGISTERS, REGISTER_HISTORIES].map(|kind| self.cache.path(kind, address.to_hex())),
+            );
+        if let Some(inner) = &self.inner {
+            inner.register_invalidate(address).await?;
+        }
+        Ok(())
+    }
+
+    async fn pointer_get(&self, address: &PointerAddress) -> anyhow::Result<Option<Pointer>> {
+        let address = address.clone();
+        self.get(POINTERS, address.to_hex(), move |storage| async move {
+            storage.pointer_get(&address).await
+        })
+        .await
+    }
+
+    async fn pointer_put(
+        &self,
+        pointer: Pointer,
+        payment: PaymentOption,
+    ) -> anyhow::Result<(AttoTokens, PointerAddress)> {
+        let (cost, address) = self.inner()?.pointer_put(pointer.clone(), payment).await?;
+        self.cache
+            .write(&self.cache.path(POINTERS, address.to_hex()), &pointer)
+            .await;
+        Ok((cost, address))
+    }
+
+    async fn pointer_exists(&self, address: &PointerAddress) -> anyhow::Result<bool> {
+        Ok(self.pointer_get(address).await?.is_some())
+    }
+
+    async fn pointer_cost(&self, pointer: &Pointer) -> anyhow::Result<AttoTokens> {
+        self.inner()?.pointer_cost(pointer).await
+    }
+
+    async fn scratchpad_get(
+        &self,
+        address: &ScratchpadAddress,
+    ) -> anyhow::Result<Option<Scratchpad>> {
+        let address = address.clone();
+        self.get(SCRATCHPADS, address.to_hex(), move |storage| async move {
+            storage.scratchpad_get(&address).await
+        })
+        .await
+    }
+
+    async fn scratchpad_put(
+        &self,
+        scratchpad: Scratchpad,
+        payment: PaymentOption,
+    ) -> anyhow::Result<(AttoTokens, ScratchpadAddress)> {
+        let (cost, address) = self
+            .inner()?
+            .scratchpad_put(scratchpad.clone(), payment)
+            .await?;
+        self.cache
+            .write(&self.cache.path(SCRATCHPADS, address.to_hex()), &scratchpad)
+            .await;
+        Ok((cost, address))
+    }
+
+    async fn scratchpad_exists(&self, address: &ScratchpadAddress) -> anyhow::Result<bool> {
+        Ok(self.scratchpad_get(address).await?.is_some())
+    }
+
+    async fn scratchpad_cost(&self, scratchpad: &Scratchpad) -> anyhow::Result<AttoTokens> {
+        self.inner()?.scratchpad_cost(scratchpad).await
+    }
+
+    async fn scratchpad_invalidate(&self, address: &ScratchpadAddress) -> anyhow::Result<()> {
+        self.invalidated
+            .lock()
+            .expect("lock not to be poisoned")
+            .insert(self.cache.path(SCRATCHPADS, address.to_hex()));
+        if let Some(inner) = &self.inner {
+            inner.scratchpad_invalidate(address).await?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct Entry<T> {
+    fetched: DateTime<Utc>,
+    value: T,
+}
+
+#[derive(Clone)]
+struct DiskCache {
+    dir: Arc<PathBuf>,
+    key: Arc<CacheKey>,
+}
+
+impl DiskCache {
+    fn path(&self, kind: &str, address: String) -> PathBuf {
+        self.dir.join(kind).join(address)
+    }
+
+    /// Unreadable entries are treated as missing, the cache is never authoritative.
+    async fn read<T: DeserializeOwned>(&self, path: &Path) -> Option<Entry<T>> {
+        match self._read(path).await {
+            Ok(entry) => entry,
+            Err(err) => {
+                tracing::warn!(
+                    error = %err,
+                    path = %path.display(),
+                    "ignoring unreadable cache entry"
+                );
+                None
+            }
+        }
+    }
+
+    async fn _read<T: DeserializeOwned>(&self, path: &Path) -> anyhow::Result<Option<Entry<T>>> {
+        let ciphertext = match tokio::fs::read(path).await {
+            Ok(bytes) => Bytes::from(bytes),
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err.into()),
+        };
+        let plaintext = AgeSingleKeyEncryptionScheme::decrypt(&ciphertext, &self.key.0)?;
+        Ok(Some(rmp_serde::from_slice(&plaintext)?))
+    }
+
+    /// Failing to update the cache is logged, never fatal.
+    async fn write<T: Serialize>(&self, path: &Path, value: &T) {
+        if let Err(err) = self._write(path, value).await {
+            tracing::warn!(
+                error = %err,
+                path = %path.display(),
+                "unable to update cache entry"
+            );
+        }
+    }
+
+    async fn _write<T: Serialize>(&self, path: &Path, value: &T) -> anyhow::Result<()> {
+        let plaintext = rmp_serde::to_vec(&Entry {
+            fetched: Utc::now(),
+            value,
+        })?;
+        let ciphertext =
+            AgeSingleKeyEncryptionScheme::encrypt(plaintext, &self.key.0.public_key())?;
+        let tmp = path.with_extension(format!("{}.tmp", Uuid::now_v7()));
+        tokio::fs::write(&tmp, ciphertext).await?;
+        if let Err(err) = tokio::fs::rename(&tmp, path).await {
+            let _ = tokio::fs::remove_file(&tmp).await;
+            return Err(err.into());
+        }
+        Ok(())
+    }
+
+    async fn remove(&self, path: &Path) {
+        match tokio::fs::remove_file(path).await {
+            Ok(()) => {}
+            Err(err) if err.kind() == ErrorKind::NotFound => {}
+            Err(err) => tracing::warn!(
+                error = %err,
+                path = %path.display(),
+                "unable to remove cache entry"
+            ),
+        }
+    }
+
+    /// The cached history is dropped as its new length is unknown.
+    async fn updated_register(&self, address: &RegisterAddress, value: RegisterValue) {
+        self.write(&self.path(REGISTERS, address.to_hex()), &value)
+            .await;
+        self.remove(&self.path(REGISTER_HISTORIES, address.to_hex()))
+            .await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MemoryStorage;
+    use tempfile::TempDir;
+
+    fn caching(dir: &TempDir, inner: Option<Storage>, ttl: u64, max_stale: u64) -> CachingStorage {
+        CachingStorage::builder()
+            .maybe_inner(inner)
+            .dir(dir.path())
+            .key(CacheKey::load_or_create(dir.path().join("key")).unwrap())
+            .ttl(Duration::from_secs(ttl))
+            .max_stale(Duration::from_secs(max_stale))
+            .build()
+            .unwrap()
+    }
+
+    async fn get(cache: &CachingStorage, fetched: anyhow::Result<&str>) -> anyhow::Result<String> {
+        let fetched = fetched.map(|v| Some(v.to_string()));
+        Ok(cache
+            .get(
+                POINTERS,
+                "record".to_string(),
+                move |_| async move { fetched },
+            )
+            .await?
+            .expect("record to exist"))
+    }
+
+    #[tokio::test]
+    async fn serve_from_cache_within_ttl() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = caching(&dir, Some(MemoryStorage::new().into()), 300, 0);
+
+        assert_eq!(get(&cache, Ok("v1")).await.unwrap(), "v1");
+        assert_eq!(get(&cache, Ok("v2")).await.unwrap(), "v1");
+        assert_eq!(
+            get(&cache, Err(anyhow!("unreachable"))).await.unwrap(),
+            "v1"
+        );
+    }
+
+    #[tokio::test]
+    async fn revalidate_stale_record_in_background() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = caching(&dir, Some(MemoryStorage::new().into()), 0, 300);
+
+        assert_eq!(get(&cache, Ok("v1")).await.unwrap(), "v1");
+        assert_eq!(get(&cache, Ok("v2")).await.unwrap(), "v1");
+
+        let path = cache.cache.path(POINTERS, "record".to_string());
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while cache.cache.read::<String>(&path).await.unwrap().value != "v2" {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("background revalidation to update the cache");
+        assert_eq!(
+            get(&cache, Err(anyhow!("unreachable"))).await.unwrap(),
+            "v2"
+        );
+    }
+
+    #[tokio::test]
+    async fn fall_back_to_expired_record_if_unreachable() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = caching(&dir, Some(MemoryStorage::new().into()), 0, 0);
+
+        assert_eq!(get(&cache, Ok("v1")).await.unwrap(), "v1");
+        assert_eq!(get(&cache, Ok("v2")).await.unwrap(), "v2");
+        assert_eq!(
+            get(&cache, Err(anyhow!("unreachable"))).await.unwrap(),
+            "v2"
+        );
+
+        let offline = caching(&dir, None, 0, 0);
+        assert!(offline.is_offline());
+        assert_eq!(get(&offline, Ok("v3")).await.unwrap(), "v2");
+    }
+
+    #[tokio::test]
+    async fn never_serve_invalidated_record_from_cache() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = caching(&dir, Some(MemoryStorage::new().into()), 300, 300);
+        let path = cache.cache.path(POINTERS, "record".to_string());
+
+        assert_eq!(get(&cache, Ok("v1")).await.unwrap(), "v1");
+        cache.invalidated.lock().unwrap().insert(path.clone());
+        assert_eq!(get(&cache, Ok("v2")).await.unwrap(), "v2");
+
+        cache.invalidated.lock().unwrap().insert(path.clone());
+        assert!(get(&cache, Err(anyhow!("unreachable"))).await.is_err());
+        // still invalidated, as the storage was never reached
+        assert!(get(&cache, Err(anyhow!("unreachable"))).await.is_err());
+        assert_eq!(get(&cache, Ok("v3")).await.unwrap(), "v3");
+        assert_eq!(
+            get(&cache, Err(anyhow!("unreachable"))).await.unwrap(),
+            "v3"
+        );
+    }
+
+    #[tokio::test]
+    async fn invalidate_register_and_its_history() {
+        let dir = tempfile::tempdir().unwrap();
+        let memory = MemoryStorage::new();
+        let cache = caching(&dir, Some(memory.clone().into()), 300, 300);
+        let owner = SecretKey::random();
+        let payment = || PaymentOption::Receipt(Default::default());
+
+        let (_, address) = cache
+            .register_create(&owner, [1; 32], payment())
+            .await
+            .unwrap();
+        assert_eq!(cache.register_history(&address).await.unwrap().len(), 1);
+
+        // updated elsewhere, e.g. by another machine
+        memory
+            .register_update(&owner, [2; 32], payment())
+            .await
+            .unwrap();
+        assert_eq!(cache.register_get(&address).await.unwrap(), Some([1; 32]));
+        assert_eq!(cache.register_history(&address).await.unwrap().len(), 1);
+
+        cache.register_invalidate(&address).await.unwrap();
+        assert_eq!(cache.register_get(&address).await.unwrap(), Some([2; 32]));
+        assert_eq!(cache.register_history(&address).await.unwrap().len(), 2);
+    }
+}
@@ -0,0 +1,238 @@
+use crate::storage::{StorageBackend, check_pointer_put, check_scratchpad_put};
+use anyhow::{anyhow, bail};
+use async_trait::async_trait;
+use autonomi::client::payment::PaymentOption;
+use autonomi::register::{RegisterAddress, RegisterValue};
+use autonomi::{
+    AttoTokens, Chunk, ChunkAddress, Pointer, PointerAddress, Scratchpad, ScratchpadAddress,
+};
+use blsttc::{PublicKey, SecretKey};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+const CHUNKS: &str = "chunks";
+const REGISTERS: &str = "registers";
+const POINTERS: &str = "pointers";
+const SCRATCHPADS: &str = "scratchpads";
+
+/// Persists all records as files under a local directory.
+///
+/// Follows the same rules as the Autonomi Network but never pays for anything.
+/// Every record lives in its own file, named after its hex encoded address:
+///
+/// ```text
+/// <root>/chunks/<address>
+/// <root>/registers/<address>
+/// <root>/pointers/<address>
+/// <root>/scratchpads/<address>
+/// ```
+///
+/// Records are written atomically. Multiple handles within the same process
+/// are fine, concurrent writers from multiple processes are not supported.
+#[derive(Clone, Debug)]
+pub struct DirectoryStorage {
+    root: Arc<PathBuf>,
+    write_lock: Arc<Mutex<()>>,
+}
+
+impl DirectoryStorage {
+    pub fn open(root: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let root = root.as_ref().to_path_buf();
+        for dir in [CHUNKS, REGISTERS, POINTERS, SCRATCHPADS] {
+            std::fs::create_dir_all(root.join(dir))?;
+        }
+        Ok(Self {
+            root: Arc::new(root),
+            write_lock: Arc::new(Mutex::new(())),
+        })
+    }
+
+    pub fn root(&self) -> &Path {
+        self.root.as_path()
+    }
+
+    fn path(&self, kind: &str, address: String) -> PathBuf {
+        self.root.join(kind).join(address)
+    }
+
+    async fn read<T: DeserializeOwned>(&self, path: &Path) -> anyhow::Result<Option<T>> {
+        match tokio::fs::read(path).await {
+            Ok(bytes) => Ok(Some(rmp_serde::from_slice(&bytes).map_err(|e| {
+                anyhow!("corrupt record at [{}]: {}", path.display(), e)
+            })?)),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    async fn write<T: Serialize>(&self, path: &Path, value: &T) -> anyhow::Result<()> {
+        let bytes = rmp_serde::to_vec(value)?;
+        let tmp = path.with_extension(format!("{}.tmp", Uuid::now_v7()));
+        tokio::fs::write(&tmp, bytes).await?;
+        if let Err(err) = tokio::fs::rename(&tmp, path).await {
+            let _ = tokio::fs::remove_file(&tmp).await;
+            return Err(err.into());
+        }
+        Ok(())
+    }
+
+    async fn register_write(
+        &self,
+        owner: &SecretKey,
+        value: RegisterValue,
+        create: bool,
+    ) -> anyhow::Result<RegisterAddress> {
+        let address = RegisterAddress::new(owner.public_key());
+        let path = self.path(REGISTERS, address.to_hex());
+        let _guard = self.write_lock.lock().await;
+        let history = match (self.read::<Vec<RegisterValue>>(&path).await?, create) {
+            (Some(_), true) => bail!("register already exists"),
+            (None, false) => bail!("register does not exist"),
+            (Some(mut history), false) => {
+                history.push(value);
+                history
+            }
+            (None, true) => vec![value],
+        };
+        self.write(&path, &history).await?;
+        Ok(address)
+    }
+}
+
+#[async_trait]
+impl StorageBackend for DirectoryStorage {
+    async fn chunk_get(&self, address: &ChunkAddress) -> anyhow::Result<Option<Chunk>> {
+        self.read(&self.path(CHUNKS, address.to_hex())).await
+    }
+
+    async fn chunk_put(
+        &self,
+        chunk: &Chunk,
+        _payment: PaymentOption,
+    ) -> anyhow::Result<(AttoTokens, ChunkAddress)> {
+        let address = chunk.address.clone();
+        let path = self.path(CHUNKS, address.to_hex());
+        let _guard = self.write_lock.lock().await;
+        if !tokio::fs::try_exists(&path).await? {
+            self.write(&path, chunk).await?;
+        }
+        Ok((AttoTokens::zero(), address))
+    }
+
+    async fn chunk_cost(&self, _chunk: &Chunk) -> anyhow::Result<AttoTokens> {
+        Ok(AttoTokens::zero())
+    }
+
+    async fn chunk_exists(&self, address: &ChunkAddress) -> anyhow::Result<bool> {
+        Ok(tokio::fs::try_exists(self.path(CHUNKS, address.to_hex())).await?)
+    }
+
+    async fn register_get(
+        &self,
+        address: &RegisterAddress,
+    ) -> anyhow::Result<Option<RegisterValue>> {
+        Ok(self.register_history(address).await?.pop())
+    }
+
+    async fn register_history(
+        &self,
+        address: &RegisterAddress,
+    ) -> anyhow::Result<Vec<RegisterValue>> {
+        Ok(self
+            .read(&self.path(REGISTERS, address.to_hex()))
+            .await?
+            .unwrap_or_default())
+    }
+
+    async fn register_create(
+        &self,
+        owner: &SecretKey,
+        value: RegisterValue,
+        _payment: PaymentOption,
+    ) -> anyhow::Result<(AttoTokens, RegisterAddress)> {
+        let address = self.register_write(owner, value, true).await?;
+        Ok((AttoTokens::zero(), address))
+    }
+
+    async fn register_update(
+        &self,
+        owner: &SecretKey,
+        value: RegisterValue,
+        _payment: PaymentOption,
+    ) -> anyhow::Result<AttoTokens> {
+        self.register_write(owner, value, false).await?;
+        Ok(AttoTokens::zero())
+    }
+
+    async fn register_create_cost(&self, _owner: &PublicKey) -> anyhow::Result<AttoTokens> {
+        Ok(AttoTokens::zero())
+    }
+
+    async fn register_update_cost(&self, _owner: &PublicKey) -> anyhow::Result<AttoTokens> {
+        Ok(AttoTokens::zero())
+    }
+
+    async fn register_exists(&self, address: &RegisterAddress) -> anyhow::Result<bool> {
+        Ok(tokio::fs::try_exists(self.path(REGISTERS, address.to_hex())).await?)
+    }
+
+    async fn pointer_get(&self, address: &PointerAddress) -> anyhow::Result<Option<Pointer>> {
+        self.read(&self.path(POINTERS, address.to_hex())).await
+    }
+
+    async fn pointer_put(
+        &self,
+        pointer: Pointer,
+        _payment: PaymentOption,
+    ) -> anyhow::Result<(AttoTokens, PointerAddress)> {
+        let address = pointer.address();
+        let path = self.path(POINTERS, address.to_hex());
+        let _guard = self.write_lock.lock().await;
+        let existing = self.read::<Pointer>(&path).await?;
+        check_pointer_put(existing.as_ref(), &pointer)?;
+        self.write(&path, &pointer).await?;
+        Ok((AttoTokens::zero(), address))
+    }
+
+    async fn pointer_exists(&self, address: &PointerAddress) -> anyhow::Result<bool> {
+        Ok(tokio::fs::try_exists(self.path(POINTERS, address.to_hex())).await?)
+    }
+
+    async fn pointer_cost(&self, _pointer: &Pointer) -> anyhow::Result<AttoTokens> {
+        Ok(AttoTokens::zero())
+    }
+
+    async fn scratchpad_get(
+        &self,
+        address: &ScratchpadAddress,
+    ) -> anyhow::Result<Option<Scratchpad>> {
+        self.read(&self.path(SCRATCHPADS, address.to_hex())).await
+    }
+
+    async fn scratchpad_put(
+        &self,
+        scratchpad: Scratchpad,
+        _payment: PaymentOption,
+    ) -> anyhow::Result<(AttoTokens, ScratchpadAddress)> {
+        let address = scratchpad.address().clone();
+        let path = self.path(SCRATCHPADS, address.to_hex());
+        let _guard = self.write_lock.lock().await;
+        let existing = self.read::<Scratchpad>(&path).await?;
+        check_scratchpad_put(existing.as_ref(), &scratchpad)?;
+        self.write(&path, &scratchpad).await?;
+        Ok((AttoTokens::zero(), address))
+    }
+
+    async fn scratchpad_exists(&self, address: &ScratchpadAddress) -> anyhow::Result<bool> {
+        Ok(tokio::fs::try_exists(self.path(SCRATCHPADS, address.to_hex())).await?)
+    }
+
+    async fn scratchpad_cost(&self, _scratchpad: &Scratchpad) -> anyhow::Result<AttoTokens> {
+        Ok(AttoTokens::zero())
+    }
+}
@@ -0,0 +1,224 @@
+use crate::storage::{StorageBackend, check_pointer_put, check_scratchpad_put};
+use anyhow::{anyhow, bail};
+use async_trait::async_trait;
+use autonomi::client::payment::PaymentOption;
+use autonomi::register::{RegisterAddress, RegisterValue};
+use autonomi::{
+    AttoTokens, Chunk, ChunkAddress, Pointer, PointerAddress, Scratchpad, ScratchpadAddress,
+};
+use blsttc::{PublicKey, SecretKey};
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+/// Keeps all records in memory.
+///
+/// Follows the same rules as the Autonomi Network but never pays for anything
+/// and never leaves the process. Useful for testing & offline experiments.
+/// Clones share the same underlying records.
+#[derive(Clone, Default)]
+pub struct MemoryStorage {
+    state: Arc<RwLock<State>>,
+    price: AttoTokens,
+}
+
+#[derive(Default)]
+struct State {
+    chunks: HashMap<ChunkAddress, Chunk>,
+    registers: HashMap<RegisterAddress, Vec<RegisterValue>>,
+    pointers: HashMap<PointerAddress, Pointer>,
+    scratchpads: HashMap<ScratchpadAddress, Scratchpad>,
+}
+
+impl MemoryStorage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Simulated cost charged for every record written.
+    pub fn with_price(mut self, price: AttoTokens) -> Self {
+        self.price = price;
+        self
+    }
+
+    /// Inserts records as-is, bypassing all checks.
+    pub(super) fn import_register(
+        &self,
+        address: RegisterAddress,
+        history: Vec<RegisterValue>,
+    ) -> anyhow::Result<()> {
+        self.write(|s| {
+            s.registers.insert(address, history);
+            Ok(())
+        })
+    }
+
+    pub(super) fn import_pointer(&self, pointer: Pointer) -> anyhow::Result<()> {
+        self.write(|s| {
+            s.pointers.insert(pointer.address(), pointer);
+            Ok(())
+        })
+    }
+
+    pub(super) fn import_scratchpad(&self, scratchpad: Scratchpad) -> anyhow::Result<()> {
+        self.write(|s| {
+            s.scratchpads
+                .insert(scratchpad.address().clone(), scratchpad);
+            Ok(())
+        })
+    }
+
+    fn read<T>(&self, f: impl FnOnce(&State) -> T) -> anyhow::Result<T> {
+        let state = self
+            .state
+            .read()
+            .map_err(|_| anyhow!("memory storage lock poisoned"))?;
+        Ok(f(&state))
+    }
+
+    fn write<T>(&self, f: impl FnOnce(&mut State) -> anyhow::Result<T>) -> anyhow::Result<T> {
+        let mut state = self
+            .state
+            .write()
+            .map_err(|_| anyhow!("memory storage lock poisoned"))?;
+        f(&mut state)
+    }
+}
+
+#[async_trait]
+impl StorageBackend for MemoryStorage {
+    async fn chunk_get(&self, address: &ChunkAddress) -> anyhow::Result<Option<Chunk>> {
+        self.read(|s| s.chunks.get(address).cloned())
+    }
+
+    async fn chunk_put(
+        &self,
+        chunk: &Chunk,
+        _payment: PaymentOption,
+    ) -> anyhow::Result<(AttoTokens, ChunkAddress)> {
+        let address = chunk.address.clone();
+        self.write(|s| {
+            s.chunks.entry(address.clone()).or_insert(chunk.clone());
+            Ok(())
+        })?;
+        Ok((self.price, address))
+    }
+
+    async fn chunk_cost(&self, _chunk: &Chunk) -> anyhow::Result<AttoTokens> {
+        Ok(self.price)
+    }
+
+    async fn register_get(
+        &self,
+        address: &RegisterAddress,
+    ) -> anyhow::Result<Option<RegisterValue>> {
+        self.read(|s| {
+            s.registers
+                .get(address)
+                .and_then(|history| history.last().cloned())
+        })
+    }
+
+    async fn register_history(
+        &self,
+        address: &RegisterAddress,
+    ) -> anyhow::Result<Vec<RegisterValue>> {
+        self.read(|s| s.registers.get(address).cloned().unwrap_or_default())
+    }
+
+    async fn register_create(
+        &self,
+        owner: &SecretKey,
+        value: RegisterValue,
+        _payment: PaymentOption,
+    ) -> anyhow::Result<(AttoTokens, RegisterAddress)> {
+        let address = RegisterAddress::new(owner.public_key());
+        self.write(|s| {
+            if s.registers.contains_key(&address) {
+                bail!("register already exists");
+            }
+            s.registers.insert(address.clone(), vec![value]);
+            Ok(())
+        })?;
+        Ok((self.price, address))
+    }
+
+    async fn register_update(
+        &self,
+        owner: &SecretKey,
+        value: RegisterValue,
+        _payment: PaymentOption,
+    ) -> anyhow::Result<AttoTokens> {
+        let address = RegisterAddress::new(owner.public_key());
+        self.write(|s| {
+            s.registers
+                .get_mut(&address)
+                .ok_or(anyhow!("register does not exist"))?
+                .push(value);
+            Ok(())
+        })?;
+        Ok(self.price)
+    }
+
+    async fn register_create_cost(&self, _owner: &PublicKey) -> anyhow::Result<AttoTokens> {
+        Ok(self.price)
+    }
+
+    async fn register_update_cost(&self, _owner: &PublicKey) -> anyhow::Result<AttoTokens> {
+        Ok(self.price)
+    }
+
+    async fn pointer_get(&self, address: &PointerAddress) -> anyhow::Result<Option<Pointer>> {
+        self.read(|s| s.pointers.get(address).cloned())
+    }
+
+    async fn pointer_put(
+        &self,
+        pointer: Pointer,
+        _payment: PaymentOption,
+    ) -> anyhow::Result<(AttoTokens, PointerAddress)> {
+        let address = pointer.address();
+        self.write(|s| {
+            check_pointer_put(s.pointers.get(&address), &pointer)?;
+            s.pointers.insert(address.clone(), pointer);
+            Ok(())
+        })?;
+        Ok((self.price, address))
+    }
+
+    async fn pointer_exists(&self, address: &PointerAddress) -> anyhow::Result<bool> {
+        self.read(|s| s.pointers.contains_key(address))
+    }
+
+    async fn pointer_cost(&self, _pointer: &Pointer) -> anyhow::Result<AttoTokens> {
+        Ok(self.price)
+    }
+
+    async fn scratchpad_get(
+        &self,
+        address: &ScratchpadAddress,
+    ) -> anyhow::Result<Option<Scratchpad>> {
+        self.read(|s| s.scratchpads.get(address).cloned())
+    }
+
+    async fn scratchpad_put(
+        &self,
+        scratchpad: Scratchpad,
+        _payment: PaymentOption,
+    ) -> anyhow::Result<(AttoTokens, ScratchpadAddress)> {
+        let address = scratchpad.address().clone();
+        self.write(|s| {
+            check_scratchpad_put(s.scratchpads.get(&address), &scratchpad)?;
+            s.scratchpads.insert(address.clone(), scratchpad);
+            Ok(())
+        })?;
+        Ok((self.price, address))
+    }
+
+    async fn scratchpad_exists(&self, address: &ScratchpadAddress) -> anyhow::Result<bool> {
+        self.read(|s| s.scratchpads.contains_key(address))
+    }
+
+    async fn scratchpad_cost(&self, _scratchpad: &Scratchpad) -> anyhow::Result<AttoTokens> {
+        Ok(self.price)
+    }
+}
@@ -0,0 +1,205 @@
+mod cache;
+mod directory;
+mod memory;
+mod network;
+mod quote;
+
+pub use cache::{CacheKey, CachingStorage};
+pub use directory::DirectoryStorage;
+pub use memory::MemoryStorage;
+pub(crate) use quote::QuotingStorage;
+
+use anyhow::bail;
+use async_trait::async_trait;
+use autonomi::client::payment::PaymentOption;
+use autonomi::register::{RegisterAddress, RegisterValue};
+use autonomi::{
+    AttoTokens, Chunk, ChunkAddress, Client, Pointer, PointerAddress, Scratchpad, ScratchpadAddress,
+};
+use blsttc::{PublicKey, SecretKey};
+use std::ops::Deref;
+use std::sync::Arc;
+
+/// Abstraction over the record storage `Core` operates on.
+///
+/// Implementations are expected to follow the semantics of the Autonomi Network:
+/// chunks are content-addressed & immutable, registers keep their full history,
+/// pointers and scratchpads are signed by their owner and only accept updates with
+/// a higher counter. A pointer with a counter of `u32::MAX` is immutable.
+///
+/// Getters return `Ok(None)` if the record does not exist.
+/// The `*_cost` methods return a price quote for the corresponding write without paying.
+#[async_trait]
+pub trait StorageBackend: Send + Sync {
+    async fn chunk_get(&self, address: &ChunkAddress) -> anyhow::Result<Option<Chunk>>;
+
+    async fn chunk_put(
+        &self,
+        chunk: &Chunk,
+        payment: PaymentOption,
+    ) -> anyhow::Result<(AttoTokens, ChunkAddress)>;
+
+    async fn chunk_exists(&self, address: &ChunkAddress) -> anyhow::Result<bool> {
+        Ok(self.chunk_get(address).await?.is_some())
+    }
+
+    async fn chunk_cost(&self, chunk: &Chunk) -> anyhow::Result<AttoTokens>;
+
+    async fn register_get(
+        &self,
+        address: &RegisterAddress,
+    ) -> anyhow::Result<Option<RegisterValue>>;
+
+    /// Returns all values the register ever had, oldest first.
+    async fn register_history(
+        &self,
+        address: &RegisterAddress,
+    ) -> anyhow::Result<Vec<RegisterValue>>;
+
+    async fn register_create(
+        &self,
+        owner: &SecretKey,
+        value: RegisterValue,
+        payment: PaymentOption,
+    ) -> anyhow::Result<(AttoTokens, RegisterAddress)>;
+
+    async fn register_update(
+        &self,
+        owner: &SecretKey,
+        value: RegisterValue,
+        payment: PaymentOption,
+    ) -> anyhow::Result<AttoTokens>;
+
+    async fn register_exists(&self, address: &RegisterAddress) -> anyhow::Result<bool> {
+        Ok(self.register_get(address).await?.is_some())
+    }
+
+    async fn register_create_cost(&self, owner: &PublicKey) -> anyhow::Result<AttoTokens>;
+
+    async fn register_update_cost(&self, owner: &PublicKey) -> anyhow::Result<AttoTokens>;
+
+    /// Makes sure the next read of the register, including its history, is not served
+    /// from a local copy.
+    ///
+    /// Only relevant for backends that cache, such as [`CachingStorage`].
+    async fn register_invalidate(&self, _address: &RegisterAddress) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    async fn pointer_get(&self, address: &PointerAddress) -> anyhow::Result<Option<Pointer>>;
+
+    async fn pointer_put(
+        &self,
+        pointer: Pointer,
+        payment: PaymentOption,
+    ) -> anyhow::Result<(AttoTokens, PointerAddress)>;
+
+    async fn pointer_exists(&self, address: &PointerAddress) -> anyhow::Result<bool>;
+
+    async fn pointer_cost(&self, pointer: &Pointer) -> anyhow::Result<AttoTokens>;
+
+    async fn scratchpad_get(
+        &self,
+        address: &ScratchpadAddress,
+    ) -> anyhow::Result<Option<Scratchpad>>;
+
+    async fn scratchpad_put(
+        &self,
+        scratchpad: Scratchpad,
+        payment: PaymentOption,
+    ) -> anyhow::Result<(AttoTokens, ScratchpadAddress)>;
+
+    async fn scratchpad_exists(&self, address: &ScratchpadAddress) -> anyhow::Result<bool>;
+
+    async fn scratchpad_cost(&self, scratchpad: &Scratchpad) -> anyhow::Result<AttoTokens>;
+
+    /// Makes sure the next read of the scratchpad is not served from a local copy.
+    ///
+    /// Only relevant for backends that cache, such as [`CachingStorage`].
+    async fn scratchpad_invalidate(&self, _address: &ScratchpadAddress) -> anyhow::Result<()> {
+        Ok(())
+    }
+}
+
+/// Cheaply cloneable handle to a [`StorageBackend`].
+///
+/// Carries the name of the network the backend belongs to, which ends up
+/// in the line items of every `Receipt`.
+#[derive(Clone)]
+pub struct Storage {
+    backend: Arc<dyn StorageBackend>,
+    network: Arc<str>,
+}
+
+impl Storage {
+    pub fn new(backend: impl StorageBackend + 'static) -> Self {
+        Self {
+            backend: Arc::new(backend),
+            network: Arc::from("unknown"),
+        }
+    }
+
+    pub fn with_network(mut self, network: impl AsRef<str>) -> Self {
+        self.network = Arc::from(network.as_ref());
+        self
+    }
+
+    pub fn network(&self) -> &str {
+        self.network.as_ref()
+    }
+}
+
+impl<B: StorageBackend + 'static> From<B> for Storage {
+    fn from(value: B) -> Self {
+        Self::new(value)
+    }
+}
+
+impl Deref for Storage {
+    type Target = dyn StorageBackend;
+
+    fn deref(&self) -> &Self::Target {
+        self.backend.as_ref()
+    }
+}
+
+/// Applies the network's acceptance rules for a pointer update.
+fn check_pointer_put(existing: Option<&Pointer>, pointer: &Pointer) -> anyhow::Result<()> {
+    if !pointer.verify_signature() {
+        bail!("invalid pointer signature");
+    }
+    if let Some(existing) = existing {
+        if existing.counter() == u32::MAX {
+            bail!("pointer is immutable");
+        }
+        if existing.counter() >= pointer.counter() {
+            bail!(
+                "pointer counter [{}] not newer than existing [{}]",
+                pointer.counter(),
+                existing.counter()
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Applies the network's acceptance rules for a scratchpad update.
+fn check_scratchpad_put(
+    existing: Option<&Scratchpad>,
+    scratchpad: &Scratchpad,
+) -> anyhow::Result<()> {
+    Client::scratchpad_verify(scratchpad)?;
+    if let Some(existing) = existing {
+        if existing.counter() == u64::MAX {
+            bail!("scratchpad is immutable");
+        }
+        if existing.counter() >= scratchpad.counter() {
+            bail!(
+                "scratchpad counter [{}] not newer than existing [{}]",
+                scratchpad.counter(),
+                existing.counter()
+            );
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,250 @@
+use crate::storage::StorageBackend;
+use crate::{AutonomiClient, Error};
+use ant_networking::{GetRecordError, NetworkError};
+use async_trait::async_trait;
+use autonomi::client::GetError;
+use autonomi::client::payment::PaymentOption;
+use autonomi::pointer::PointerError;
+use autonomi::register::{RegisterAddress, RegisterError, RegisterValue};
+use autonomi::{
+    AttoTokens, Chunk, ChunkAddress, Pointer, PointerAddress, Scratchpad, ScratchpadAddress,
+};
+use blsttc::{PublicKey, SecretKey};
+use evmlib::wallet::Error as EvmWalletError;
+use tokio::time::error::Elapsed;
+
+/// The live Autonomi Network.
+#[async_trait]
+impl StorageBackend for AutonomiClient {
+    async fn chunk_get(&self, address: &ChunkAddress) -> anyhow::Result<Option<Chunk>> {
+        match AutonomiClient::chunk_get(self, address).await {
+            Ok(chunk) => Ok(Some(chunk)),
+            Err(GetError::Network(NetworkError::GetRecordError(
+                GetRecordError::RecordNotFound,
+            ))) => Ok(None),
+            Err(err) => Err(classify(err)),
+        }
+    }
+
+    async fn chunk_put(
+        &self,
+        chunk: &Chunk,
+        payment: PaymentOption,
+    ) -> anyhow::Result<(AttoTokens, ChunkAddress)> {
+        AutonomiClient::chunk_put(self, chunk, payment)
+            .await
+            .map_err(classify)
+    }
+
+    async fn chunk_cost(&self, chunk: &Chunk) -> anyhow::Result<AttoTokens> {
+        AutonomiClient::chunk_cost(self, chunk.address())
+            .await
+            .map_err(classify)
+    }
+
+    async fn register_get(
+        &self,
+        address: &RegisterAddress,
+    ) -> anyhow::Result<Option<RegisterValue>> {
+        match AutonomiClient::register_get(self, address).await {
+            Ok(reg) => Ok(Some(reg)),
+            Err(RegisterError::PointerError(PointerError::Network(
+                NetworkError::GetRecordError(GetRecordError::RecordNotFound),
+            ))) => {
+                // if there is a better way to check for a register's existence, please update!
+                Ok(None)
+            }
+            Err(err) => Err(classify(err)),
+        }
+    }
+
+    async fn register_history(
+        &self,
+        address: &RegisterAddress,
+    ) -> anyhow::Result<Vec<RegisterValue>> {
+        AutonomiClient::register_history(self, address)
+            .collect()
+            .await
+            .map_err(classify)
+    }
+
+    async fn register_create(
+        &self,
+        owner: &SecretKey,
+        value: RegisterValue,
+        payment: PaymentOption,
+    ) -> anyhow::Result<(AttoTokens, RegisterAddress)> {
+        AutonomiClient::register_create(self, owner, value, payment)
+            .await
+            .map_err(classify)
+    }
+
+    async fn register_update(
+        &self,
+        owner: &SecretKey,
+        value: RegisterValue,
+        payment: PaymentOption,
+    ) -> anyhow::Result<AttoTokens> {
+        AutonomiClient::register_update(self, owner, value, payment)
+            .await
+            .map_err(classify)
+    }
+
+    async fn register_create_cost(&self, owner: &PublicKey) -> anyhow::Result<AttoTokens> {
+        AutonomiClient::register_cost(self, owner)
+            .await
+            .map_err(classify)
+    }
+
+    async fn register_update_cost(&self, owner: &PublicKey) -> anyhow::Result<AttoTokens> {
+        // an update adds a new graph entry, moving the register's head pointer is free
+        self.graph_entry_cost(owner).await.map_err(classify)
+    }
+
+    async fn pointer_get(&self, address: &PointerAddress) -> anyhow::Result<Option<Pointer>> {
+        if !self
+            .pointer_check_existance(address)
+            .await
+            .map_err(classify)?
+        {
+            return Ok(None);
+        }
+        Ok(Some(
+            AutonomiClient::pointer_get(self, address)
+                .await
+                .map_err(classify)?,
+        ))
+    }
+
+    async fn pointer_put(
+        &self,
+        pointer: Pointer,
+        payment: PaymentOption,
+    ) -> anyhow::Result<(AttoTokens, PointerAddress)> {
+        AutonomiClient::pointer_put(self, pointer, payment)
+            .await
+            .map_err(classify)
+    }
+
+    async fn pointer_exists(&self, address: &PointerAddress) -> anyhow::Result<bool> {
+        self.pointer_check_existance(address)
+            .await
+            .map_err(classify)
+    }
+
+    async fn pointer_cost(&self, pointer: &Pointer) -> anyhow::Result<AttoTokens> {
+        AutonomiClient::pointer_cost(self, pointer.owner())
+            .await
+            .map_err(classify)
+    }
+
+    async fn scratchpad_get(
+        &self,
+        address: &ScratchpadAddress,
+    ) -> anyhow::Result<Option<Scratchpad>> {
+        if !self
+            .scratchpad_check_existance(address)
+            .await
+            .map_err(classify)?
+        {
+            return Ok(None);
+        }
+        Ok(Some(
+            self.scratchpad_get_from_public_key(address.owner())
+                .await
+                .map_err(classify)?,
+        ))
+    }
+
+    async fn scratchpad_put(
+        &self,
+        scratchpad: Scratchpad,
+        payment: PaymentOption,
+    ) -> anyhow::Result<(AttoTokens, ScratchpadAddress)> {
+        AutonomiClient::scratchpad_put(self, scratchpad, payment)
+            .await
+            .map_err(classify)
+    }
+
+    async fn scratchpad_exists(&self, address: &ScratchpadAddress) -> anyhow::Result<bool> {
+        self.scratchpad_check_existance(address)
+            .await
+            .map_err(classify)
+    }
+
+    async fn scratchpad_cost(&self, scratchpad: &Scratchpad) -> anyhow::Result<AttoTokens> {
+        AutonomiClient::scratchpad_cost(self, scratchpad.owner())
+            .await
+            .map_err(classify)
+    }
+}
+
+/// Surfaces conditions callers commonly act upon as typed [`Error`]s.
+///
+/// The client wraps them in layers of error types that differ per operation,
+/// so the whole chain of causes is searched for the underlying ones.
+/// Anything else is passed on as is and ends up as [`Error::Other`].
+fn classify(err: impl Into<anyhow::Error>) -> anyhow::Error {
+    let err = err.into();
+    if err.chain().any(is_insufficient_funds) {
+        Error::InsufficientFunds(format!("{:#}", err)).into()
+    } else if err.chain().any(is_timeout) {
+        Error::Timeout(format!("{:#}", err)).into()
+    } else {
+        err
+    }
+}
+
+fn is_insufficient_funds(cause: &(dyn std::error::Error + 'static)) -> bool {
+    matches!(
+        cause.downcast_ref::<EvmWalletError>(),
+        Some(EvmWalletError::InsufficientTokensForQuotes(..))
+    )
+}
+
+fn is_timeout(cause: &(dyn std::error::Error + 'static)) -> bool {
+    cause.is::<Elapsed>()
+        || matches!(
+            cause.downcast_ref::<GetRecordError>(),
+            Some(GetRecordError::QueryTimeout)
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Context;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn classify_timeouts() {
+        let err = GetError::Network(NetworkError::GetRecordError(GetRecordError::QueryTimeout));
+        assert!(matches!(Error::from(classify(err)), Error::Timeout(_)));
+
+        let elapsed = tokio::time::timeout(Duration::ZERO, std::future::pending::<()>())
+            .await
+            .unwrap_err();
+        let err = anyhow::Error::from(elapsed).context("reading register");
+        assert!(matches!(Error::from(classify(err)), Error::Timeout(_)));
+    }
+
+    #[test]
+    fn classify_insufficient_funds() {
+        let err = Err::<(), _>(EvmWalletError::InsufficientTokensForQuotes(
+            Default::default(),
+            Default::default(),
+        ))
+        .context("paying for chunk")
+        .unwrap_err();
+        assert!(matches!(
+            Error::from(classify(err)),
+            Error::InsufficientFunds(_)
+        ));
+    }
+
+    #[test]
+    fn pass_on_anything_else() {
+        let err = GetError::Network(NetworkError::GetRecordError(GetRecordError::RecordNotFound));
+        assert!(matches!(Error::from(classify(err)), Error::Other(_)));
+    }
+}
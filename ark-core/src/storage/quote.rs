@@ -0,0 +1,194 @@
+use crate::storage::{MemoryStorage, Storage, StorageBackend};
+use anyhow::bail;
+use async_trait::async_trait;
+use autonomi::client::payment::PaymentOption;
+use autonomi::register::{RegisterAddress, RegisterValue};
+use autonomi::{
+    AttoTokens, Chunk, ChunkAddress, Pointer, PointerAddress, Scratchpad, ScratchpadAddress,
+};
+use blsttc::{PublicKey, SecretKey};
+
+/// Collects price quotes instead of paying.
+///
+/// Writes never reach the underlying storage. Instead, they are quoted by it
+/// and staged in memory, so subsequent reads see them just as they would after
+/// a real write. This allows costly operations to run all their steps for
+/// a cost estimate without spending anything.
+pub(crate) struct QuotingStorage {
+    inner: Storage,
+    staged: MemoryStorage,
+}
+
+impl QuotingStorage {
+    pub(crate) fn new(inner: Storage) -> Self {
+        Self {
+            inner,
+            staged: MemoryStorage::new(),
+        }
+    }
+}
+
+#[async_trait]
+impl StorageBackend for QuotingStorage {
+    async fn chunk_get(&self, address: &ChunkAddress) -> anyhow::Result<Option<Chunk>> {
+        match self.staged.chunk_get(address).await? {
+            Some(chunk) => Ok(Some(chunk)),
+            None => self.inner.chunk_get(address).await,
+        }
+    }
+
+    async fn chunk_put(
+        &self,
+        chunk: &Chunk,
+        payment: PaymentOption,
+    ) -> anyhow::Result<(AttoTokens, ChunkAddress)> {
+        let cost = self.inner.chunk_cost(chunk).await?;
+        let (_, address) = self.staged.chunk_put(chunk, payment).await?;
+        Ok((cost, address))
+    }
+
+    async fn chunk_cost(&self, chunk: &Chunk) -> anyhow::Result<AttoTokens> {
+        self.inner.chunk_cost(chunk).await
+    }
+
+    async fn register_get(
+        &self,
+        address: &RegisterAddress,
+    ) -> anyhow::Result<Option<RegisterValue>> {
+        match self.staged.register_get(address).await? {
+            Some(value) => Ok(Some(value)),
+            None => self.inner.register_get(address).await,
+        }
+    }
+
+    async fn register_history(
+        &self,
+        address: &RegisterAddress,
+    ) -> anyhow::Result<Vec<RegisterValue>> {
+        // staged registers always contain the full history
+        let history = self.staged.register_history(address).await?;
+        if !history.is_empty() {
+            return Ok(history);
+        }
+        self.inner.register_history(address).await
+    }
+
+    async fn register_create(
+        &self,
+        owner: &SecretKey,
+        value: RegisterValue,
+        payment: PaymentOption,
+    ) -> anyhow::Result<(AttoTokens, RegisterAddress)> {
+        let address = RegisterAddress::new(owner.public_key());
+        if self.inner.register_exists(&address).await? {
+            bail!("register already exists");
+        }
+        let cost = self.inner.register_create_cost(&owner.public_key()).await?;
+        self.staged.register_create(owner, value, payment).await?;
+        Ok((cost, address))
+    }
+
+    async fn register_update(
+        &self,
+        owner: &SecretKey,
+        value: RegisterValue,
+        payment: PaymentOption,
+    ) -> anyhow::Result<AttoTokens> {
+        let address = RegisterAddress::new(owner.public_key());
+        if !self.staged.register_exists(&address).await? {
+            let history = self.inner.register_history(&address).await?;
+            if history.is_empty() {
+                bail!("register does not exist");
+            }
+            self.staged.import_register(address, history)?;
+        }
+        let cost = self.inner.register_update_cost(&owner.public_key()).await?;
+        self.staged.register_update(owner, value, payment).await?;
+        Ok(cost)
+    }
+
+    async fn register_create_cost(&self, owner: &PublicKey) -> anyhow::Result<AttoTokens> {
+        self.inner.register_create_cost(owner).await
+    }
+
+    async fn register_update_cost(&self, owner: &PublicKey) -> anyhow::Result<AttoTokens> {
+        self.inner.register_update_cost(owner).await
+    }
+
+    async fn pointer_get(&self, address: &PointerAddress) -> anyhow::Result<Option<Pointer>> {
+        match self.staged.pointer_get(address).await? {
+            Some(pointer) => Ok(Some(pointer)),
+            None => self.inner.pointer_get(address).await,
+        }
+    }
+
+    async fn pointer_put(
+        &self,
+        pointer: Pointer,
+        payment: PaymentOption,
+    ) -> anyhow::Result<(AttoTokens, PointerAddress)> {
+        let address = pointer.address();
+        if !self.staged.pointer_exists(&address).await? {
+            if let Some(existing) = self.inner.pointer_get(&address).await? {
+                self.staged.import_pointer(existing)?;
+            }
+        }
+        let cost = self.inner.pointer_cost(&pointer).await?;
+        self.staged.pointer_put(pointer, payment).await?;
+        Ok((cost, address))
+    }
+
+    async fn pointer_exists(&self, address: &PointerAddress) -> anyhow::Result<bool> {
+        Ok(
+            self.staged.pointer_exists(address).await?
+                || self.inner.pointer_exists(address).await?,
+        )
+    }
+
+    async fn pointer_cost(&self, pointer: &Pointer) -> anyhow::Result<AttoTokens> {
+        self.inner.pointer_cost(pointer).await
+    }
+
+    async fn scratchpad_get(
+        &self,
+        address: &ScratchpadAddress,
+    ) -> anyhow::Result<Option<Scratchpad>> {
+        match self.staged.scratchpad_get(address).await? {
+            Some(scratchpad) => Ok(Some(scratchpad)),
+            None => self.inner.scratchpad_get(address).await,
+        }
+    }
+
+    async fn scratchpad_put(
+        &self,
+        scratchpad: Scratchpad,
+        payment: PaymentOption,
+    ) -> anyhow::Result<(AttoTokens, ScratchpadAddress)> {
+        let address = scratchpad.address().clone();
+        if !self.staged.scratchpad_exists(&address).await? {
+            if let Some(existing) = self.inner.scratchpad_get(&address).await? {
+                self.staged.import_scratchpad(existing)?;
+            }
+        }
+        let cost = self.inner.scratchpad_cost(&scratchpad).await?;
+        self.staged.scratchpad_put(scratchpad, payment).await?;
+        Ok((cost, address))
+    }
+
+    async fn scratchpad_exists(&self, address: &ScratchpadAddress) -> anyhow::Result<bool> {
+        Ok(self.staged.scratchpad_exists(address).await?
+            || self.inner.scratchpad_exists(address).await?)
+    }
+
+    async fn scratchpad_cost(&self, scratchpad: &Scratchpad) -> anyhow::Result<AttoTokens> {
+        self.inner.scratchpad_cost(scratchpad).await
+    }
+
+    async fn register_invalidate(&self, address: &RegisterAddress) -> anyhow::Result<()> {
+        self.inner.register_invalidate(address).await
+    }
+
+    async fn scratchpad_invalidate(&self, address: &ScratchpadAddress) -> anyhow::Result<()> {
+        self.inner.scratchpad_invalidate(address).await
+    }
+}
@@ -1,24 +1,43 @@
+use crate::ark_seed::ArkRoot;
 use crate::crypto::{
     AllowDerivation, AllowRandom, Bech32Public, Derived, Finalizeable, TypedDerivationIndex,
     TypedOwnedPointer, TypedPointerAddress, TypedPublicKey, TypedSecretKey,
 };
 use crate::objects::ObjectType;
 use crate::progress::Task;
-use crate::{ArkAddress, AutonomiClient, BridgeAddress, HelmKey, Progress};
-use crate::{Core, Receipt, Result, with_receipt};
-use anyhow::{anyhow, bail};
+use crate::{ArkAccessor, ArkAddress, ArkSeed, BridgeAddress, HelmKey, Progress, Storage};
+use crate::{Core, Error, ReadOnlyCore, Receipt, Result, with_quote, with_receipt};
+use anyhow::bail;
 use autonomi::PointerAddress;
+use blsttc::Signature;
 use bon::Builder;
 use chrono::{DateTime, Utc};
 use once_cell::sync::Lazy;
+use std::fmt::{Display, Formatter};
 use std::ops::Deref;
 
-const ARK_POINTER_NAME: &str = "/ark/v0/vault/ark/pointer";
+pub(crate) const ARK_POINTER_NAME: &str = "/ark/v0/vault/ark/pointer";
 static ARK_POINTER_DERIVATOR: Lazy<ArkPointerDerivator> =
     Lazy::new(|| ArkPointerDerivator::from_name(ARK_POINTER_NAME));
 
 type ArkPointerDerivator = TypedDerivationIndex<ArkAddress>;
 
+pub(crate) const LEGAL_HOLD_KEY_NAME: &str = "/ark/v0/vault/legal-hold/key";
+static LEGAL_HOLD_KEY_DERIVATOR: Lazy<LegalHoldKeyDerivator> =
+    Lazy::new(|| LegalHoldKeyDerivator::from_name(LEGAL_HOLD_KEY_NAME));
+
+type LegalHoldKeyDerivator = TypedDerivationIndex<LegalHoldKeyKind>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LegalHoldKeyKind;
+
+impl AllowDerivation<ArkRoot, LegalHoldKeyKind> for ArkRoot {
+    type Derivator = LegalHoldKeyDerivator;
+}
+
+/// Signs legal hold releases, derived from the Ark Seed.
+type LegalHoldKey = TypedSecretKey<Derived<LegalHoldKeyKind, ArkRoot>>;
+
 #[derive(Debug, Clone, Copy, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub struct VaultKind;
 
@@ -38,7 +57,7 @@ impl Finalizeable for ArkAddress {}
 
 type ArkPointerKind = Derived<ArkAddress, VaultKind>;
 
-type ArkPointerAddress = TypedPointerAddress<ArkPointerKind, ArkAddress>;
+pub(crate) type ArkPointerAddress = TypedPointerAddress<ArkPointerKind, ArkAddress>;
 
 impl From<VaultAddress> for ArkPointerAddress {
     fn from(value: VaultAddress) -> Self {
@@ -68,9 +87,9 @@ async fn create(
 ) -> anyhow::Result<VaultConfig> {
     let mut verify_helm = task.child(1, "Verify Helm Key".to_string());
     let mut vault_pointer = task.child(1, "Create Vault Address".to_string());
-    let mut read_manifest = task.child(1, "Retrieve Current Manifest".to_string());
     let mut update_manifest = task.child(1, "Updating Manifest".to_string());
     task.start();
+    receipt.set_vault(settings.vault_key.public_key());
 
     verify_helm.start();
     core.verify_helm_key(helm_key).await?;
@@ -85,16 +104,15 @@ async fn create(
     .await?;
     vault_pointer.complete();
 
-    read_manifest.start();
-    let mut manifest = core.get_manifest(helm_key).await?;
-    read_manifest.complete();
-
     let vault_config = VaultConfig::from(settings);
-    manifest.vaults.push(vault_config.clone());
-    manifest.last_modified = Utc::now();
 
     update_manifest.start();
-    core.update_manifest(&manifest, helm_key, receipt).await?;
+    core.modify_manifest(helm_key, receipt, |manifest| {
+        manifest.vaults.push(vault_config.clone());
+        manifest.last_modified = Utc::now();
+        Ok(())
+    })
+    .await?;
     update_manifest.complete();
 
     task.complete();
@@ -143,31 +161,354 @@ pub struct VaultConfig {
     pub last_modified: DateTime<Utc>,
     pub name: String,
     pub description: Option<String>,
-    pub active: bool,
+    pub status: VaultStatus,
+    /// All previous statuses, oldest first
+    pub status_history: Vec<VaultStatus>,
     pub bridge: Option<BridgeAddress>,
     pub object_type: ObjectType,
+    /// Every legal hold ever placed on the vault, oldest first
+    pub legal_holds: Vec<LegalHold>,
 }
 
 impl VaultConfig {
-    fn apply(&mut self, req: &ModificationRequest) {
+    pub fn is_active(&self) -> bool {
+        self.status.state == VaultState::Active
+    }
+
+    /// The first legal hold still in effect, if any.
+    ///
+    /// A hold stays in effect unless its release carries a valid signature made with
+    /// the Ark Seed of `ark_address`.
+    pub fn legal_hold(&self, ark_address: &ArkAddress) -> Option<&LegalHold> {
+        self.legal_holds
+            .iter()
+            .find(|hold| !hold.is_released(&self.address, ark_address))
+    }
+
+    /// Applies the request, provided it does not violate the vault's policies.
+    ///
+    /// Nothing is changed if the request is refused.
+    fn apply(&mut self, req: &ModificationRequest, ark_address: &ArkAddress) -> anyhow::Result<()> {
+        self.check(req, ark_address)?;
+        if let Some(change) = &req.legal_hold {
+            match change {
+                LegalHoldChange::Place(reason) => self.legal_holds.push(LegalHold {
+                    placed: Utc::now(),
+                    reason: reason.clone(),
+                    released: None,
+                }),
+                LegalHoldChange::Release { reason, key } => {
+                    let vault_address = self.address.clone();
+                    if let Some(hold) = self
+                        .legal_holds
+                        .iter_mut()
+                        .find(|hold| !hold.is_released(&vault_address, ark_address))
+                    {
+                        let at = Utc::now();
+                        let signature =
+                            key.as_ref()
+                                .sign(release_message(&vault_address, &hold.placed, &at));
+                        hold.released = Some(LegalHoldRelease {
+                            at,
+                            reason: reason.clone(),
+                            signature: signature.to_bytes().to_vec(),
+                        });
+                    }
+                }
+            }
+        }
         if let Some(name) = &req.name {
             self.name = name.clone();
         }
         if let Some(description) = &req.description {
             self.description = description.clone();
         }
-        if let Some(active) = req.active {
-            self.active = active;
+        if let Some(status) = &req.status {
+            let previous = std::mem::replace(&mut self.status, status.clone());
+            self.status_history.push(previous);
         }
         if let Some(bridge) = &req.bridge {
             self.bridge = bridge.clone();
         }
+        if let Some(object_type) = &req.object_type {
+            self.object_type = object_type.clone();
+        }
+        self.last_modified = Utc::now();
+        Ok(())
+    }
+
+    /// Policy checks, evaluated against the vault as it is before the request is applied.
+    fn check(&self, req: &ModificationRequest, ark_address: &ArkAddress) -> anyhow::Result<()> {
+        if let Some(object_type) = &req.object_type {
+            if !object_type.is_same_kind(&self.object_type) {
+                bail!(
+                    "object type of vault [{}] cannot be changed from [{}] to [{}]",
+                    self.address,
+                    self.object_type,
+                    object_type
+                );
+            }
+        }
+        if let Some(status) = &req.status {
+            if status.state == self.status.state && status.reason == self.status.reason {
+                bail!("vault [{}] is already {}", self.address, self.status.state);
+            }
+        }
+        let legal_hold = self.legal_hold(ark_address);
+        match (&req.legal_hold, legal_hold) {
+            (Some(LegalHoldChange::Place(_)), Some(_)) => {
+                bail!("vault [{}] is already under legal hold", self.address)
+            }
+            (Some(LegalHoldChange::Release { .. }), None) => {
+                bail!("vault [{}] is not under legal hold", self.address)
+            }
+            (Some(LegalHoldChange::Release { key, .. }), Some(_))
+                if key.public_key() != &legal_hold_public_key(ark_address) =>
+            {
+                bail!("releasing a legal hold requires the Ark Seed")
+            }
+            _ => {}
+        }
+        if legal_hold.is_none() || req.legal_hold.is_some() {
+            return Ok(());
+        }
+        let deactivates = req
+            .status
+            .as_ref()
+            .is_some_and(|status| !status.state.accepts_uploads());
+        let rebinds = req
+            .bridge
+            .as_ref()
+            .is_some_and(|bridge| bridge != &self.bridge);
+        let retypes = req
+            .object_type
+            .as_ref()
+            .is_some_and(|object_type| object_type != &self.object_type);
+        if deactivates || rebinds || retypes {
+            return Err(Error::VaultUnderLegalHold {
+                vault_address: self.address.clone(),
+            }
+            .into());
+        }
+        Ok(())
+    }
+}
+
+/// A legal hold keeps a vault write-once: while in effect, the vault cannot be
+/// deactivated, bound to a different bridge, changed to a different object type or removed
+/// from the manifest.
+///
+/// Placing a hold requires the Helm Key, releasing it the Ark Seed. The release is signed
+/// with a key derived from the Ark Seed, a release written with the Helm Key alone is
+/// ignored. The Helm Key can still rewrite the manifest as a whole, removing the hold
+/// record entirely is not prevented.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LegalHold {
+    pub placed: DateTime<Utc>,
+    pub reason: Option<String>,
+    pub released: Option<LegalHoldRelease>,
+}
+
+impl LegalHold {
+    /// Whether the hold has been released with a valid signature of the Ark Seed.
+    pub fn is_released(&self, vault_address: &VaultAddress, ark_address: &ArkAddress) -> bool {
+        let Some(release) = &self.released else {
+            return false;
+        };
+        let Some(signature) = <[u8; 96]>::try_from(release.signature.as_slice())
+            .ok()
+            .and_then(|bytes| Signature::from_bytes(bytes).ok())
+        else {
+            return false;
+        };
+        legal_hold_public_key(ark_address).as_ref().verify(
+            &signature,
+            release_message(vault_address, &self.placed, &release.at),
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LegalHoldRelease {
+    pub at: DateTime<Utc>,
+    pub reason: Option<String>,
+    /// Signature over the vault address, the time the hold was placed & `at`
+    pub(crate) signature: Vec<u8>,
+}
+
+fn legal_hold_public_key(
+    ark_address: &ArkAddress,
+) -> TypedPublicKey<Derived<LegalHoldKeyKind, ArkRoot>> {
+    ark_address.derive_child::<LegalHoldKeyKind>(LEGAL_HOLD_KEY_DERIVATOR.deref())
+}
+
+fn release_message(
+    vault_address: &VaultAddress,
+    placed: &DateTime<Utc>,
+    at: &DateTime<Utc>,
+) -> Vec<u8> {
+    let mut msg = LEGAL_HOLD_KEY_NAME.as_bytes().to_vec();
+    msg.extend_from_slice(&vault_address.as_ref().to_bytes());
+    for ts in [placed, at] {
+        msg.extend_from_slice(&ts.timestamp().to_be_bytes());
+        msg.extend_from_slice(&ts.timestamp_subsec_nanos().to_be_bytes());
+    }
+    msg
+}
+
+/// Lifecycle state of a vault.
+///
+/// Only active vaults accept new uploads. Vaults in every other state remain in the
+/// manifest and can be listed, read & restored by switching back to `Active`.
+///
+/// The engine checks the state via [`ReadOnlyCore::writable_vault`] before every upload.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum VaultState {
+    Active,
+    /// Temporarily not synchronized
+    Paused,
+    /// Read-only, kept for reference
+    Archived,
+    /// No longer in use, kept for restore
+    Decommissioned,
+}
+
+impl VaultState {
+    pub fn accepts_uploads(&self) -> bool {
+        matches!(self, Self::Active)
+    }
+}
+
+impl Display for VaultState {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::Active => "active",
+            Self::Paused => "paused",
+            Self::Archived => "archived",
+            Self::Decommissioned => "decommissioned",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct VaultStatus {
+    pub state: VaultState,
+    pub since: DateTime<Utc>,
+    pub reason: Option<String>,
+}
+
+impl VaultStatus {
+    pub(crate) fn new(state: VaultState, reason: Option<String>) -> Self {
+        Self {
+            state,
+            since: Utc::now(),
+            reason,
+        }
+    }
+}
+
+impl ReadOnlyCore {
+    /// Lists all vaults of the Ark, active or not.
+    pub fn vaults<'a>(
+        &'a self,
+        ark_accessor: &'a ArkAccessor,
+    ) -> (
+        Progress,
+        impl Future<Output = Result<Vec<VaultConfig>>> + Send + 'a,
+    ) {
+        let (progress, mut task) = Progress::new(1, "Retrieve Vaults".to_string());
+
+        let fut = with_receipt("List Vaults", async move |_| {
+            task.start();
+            let vaults = self.get_manifest(ark_accessor).await?.vaults;
+            task.complete();
+            Ok(vaults)
+        });
+
+        (progress, fut)
+    }
+
+    pub fn vault_details<'a>(
+        &'a self,
+        vault_address: &'a VaultAddress,
+        ark_accessor: &'a ArkAccessor,
+    ) -> (
+        Progress,
+        impl Future<Output = Result<VaultConfig>> + Send + 'a,
+    ) {
+        let (progress, mut task) = Progress::new(1, "Retrieve Vault Details".to_string());
+
+        let fut = with_receipt("Vault Details", async move |_| {
+            task.start();
+            let vault = self
+                .get_manifest(ark_accessor)
+                .await?
+                .vault(vault_address)
+                .cloned()
+                .ok_or(Error::VaultNotFound {
+                    vault_address: vault_address.clone(),
+                })?;
+            task.complete();
+            Ok(vault)
+        });
+
+        (progress, fut)
+    }
+
+    /// Returns the vault's config, provided it currently accepts new uploads.
+    ///
+    /// Fails with [`Error::VaultNotWritable`] otherwise.
+    ///
+    /// Checked by the engine before every upload, always against the latest manifest.
+    pub async fn writable_vault(
+        &self,
+        vault_address: &VaultAddress,
+        ark_accessor: &ArkAccessor,
+    ) -> Result<VaultConfig> {
+        with_receipt("Check Vault", async move |_| {
+            let vault = self
+                .get_manifest(ark_accessor)
+                .await?
+                .vault(vault_address)
+                .cloned()
+                .ok_or(Error::VaultNotFound {
+                    vault_address: vault_address.clone(),
+                })?;
+            if !vault.status.state.accepts_uploads() {
+                return Err(Error::VaultNotWritable {
+                    vault_address: vault_address.clone(),
+                    state: vault.status.state,
+                }
+                .into());
+            }
+            Ok(vault)
+        })
+        .await
+    }
+
+    /// Estimates the cost of [`Core::create_vault`] without spending anything.
+    pub fn quote_create_vault(
+        &self,
+        settings: VaultCreationSettings,
+        helm_key: &HelmKey,
+    ) -> (
+        Progress,
+        impl Future<Output = anyhow::Result<Receipt>> + Send,
+    ) {
+        let (progress, task) = Progress::new(1, "Vault Creation Quote".to_string());
+
+        let fut = with_quote("Vault Creation", async move |receipt| {
+            create(settings, helm_key, &self.quoting(), receipt, task).await
+        });
+
+        (progress, fut)
     }
 }
 
 impl Core {
     pub fn ark_from_vault_address(
-        client: &AutonomiClient,
+        storage: &Storage,
         vault_address: &VaultAddress,
     ) -> (
         Progress,
@@ -175,18 +516,18 @@ impl Core {
     ) {
         let (progress, task) = Progress::new(1, "Check Vault Address".to_string());
 
-        let fut = Self::_ark_from_source(client, vault_address.clone(), task);
+        let fut = Self::_ark_from_source(storage, vault_address.clone(), task);
 
         (progress, fut)
     }
 
     async fn _ark_from_source<S: Into<ArkPointerAddress>>(
-        client: &AutonomiClient,
+        storage: &Storage,
         source: S,
         mut task: Task,
     ) -> anyhow::Result<Option<ArkAddress>> {
         task.start();
-        let pointer = match Self::read_pointer_directly(client, &(source.into())).await? {
+        let pointer = match ReadOnlyCore::read_pointer_directly(storage, &(source.into())).await? {
             Some(p) => p,
             None => return Ok(None),
         };
@@ -203,9 +544,13 @@ impl Core {
         settings: VaultCreationSettings,
         helm_key: &HelmKey,
     ) -> (Progress, impl Future<Output = Result<VaultConfig>> + Send) {
-        let (progress, task) = Progress::new(1, "Vault Creation".to_string());
+        let (progress, mut task) = Progress::new(1, "Vault Creation".to_string());
 
-        let fut = with_receipt(async move |receipt| {
+        let fut = with_receipt("Vault Creation", async move |receipt| {
+            self.check_budget(receipt, &mut task, async |core, receipt, task| {
+                create(settings.clone(), helm_key, core, receipt, task).await
+            })
+            .await?;
             create(settings, helm_key, &self, receipt, task).await
         });
 
@@ -219,11 +564,13 @@ impl Core {
     ) -> (Progress, impl Future<Output = Result<()>> + Send) {
         let (progress, task) = Progress::new(1, "Activate Vault".to_string());
 
-        let fut = with_receipt(async move |receipt| {
+        let fut = with_receipt("Activate Vault", async move |receipt| {
             self._modify_vault(
                 vault_address,
                 helm_key,
-                &ModificationRequest::builder().active(true).build(),
+                &ModificationRequest::builder()
+                    .status(VaultStatus::new(VaultState::Active, None))
+                    .build(),
                 receipt,
                 task,
             )
@@ -240,11 +587,219 @@ impl Core {
     ) -> (Progress, impl Future<Output = Result<()>> + Send) {
         let (progress, task) = Progress::new(1, "Deactivate Vault".to_string());
 
-        let fut = with_receipt(async move |receipt| {
+        let fut = with_receipt("Deactivate Vault", async move |receipt| {
+            self._modify_vault(
+                vault_address,
+                helm_key,
+                &ModificationRequest::builder()
+                    .status(VaultStatus::new(VaultState::Paused, None))
+                    .build(),
+                receipt,
+                task,
+            )
+            .await
+        });
+
+        (progress, fut)
+    }
+
+    /// Moves the vault into `state`, recording the `reason` (if any) in its status history.
+    ///
+    /// Staying in the current state with a different `reason` is recorded as a status change
+    /// of its own. A request that changes neither is refused.
+    ///
+    /// Uploads already in progress are not interrupted, the engine refuses the next one.
+    pub async fn set_vault_state(
+        &self,
+        vault_address: &VaultAddress,
+        state: VaultState,
+        reason: Option<String>,
+        helm_key: &HelmKey,
+    ) -> (Progress, impl Future<Output = Result<()>> + Send) {
+        let (progress, task) = Progress::new(1, "Updating Vault".to_string());
+
+        let fut = with_receipt("Update Vault State", async move |receipt| {
+            self._modify_vault(
+                vault_address,
+                helm_key,
+                &ModificationRequest::builder()
+                    .status(VaultStatus::new(state, reason))
+                    .build(),
+                receipt,
+                task,
+            )
+            .await
+        });
+
+        (progress, fut)
+    }
+
+    /// Places the vault under legal hold, see [`LegalHold`].
+    pub async fn place_legal_hold(
+        &self,
+        vault_address: &VaultAddress,
+        reason: Option<String>,
+        helm_key: &HelmKey,
+    ) -> (Progress, impl Future<Output = Result<()>> + Send) {
+        let (progress, task) = Progress::new(1, "Updating Vault".to_string());
+
+        let fut = with_receipt("Place Legal Hold", async move |receipt| {
             self._modify_vault(
                 vault_address,
                 helm_key,
-                &ModificationRequest::builder().active(false).build(),
+                &ModificationRequest::builder()
+                    .legal_hold(LegalHoldChange::Place(reason))
+                    .build(),
+                receipt,
+                task,
+            )
+            .await
+        });
+
+        (progress, fut)
+    }
+
+    /// Releases the legal hold currently in effect. Requires the Ark Seed.
+    pub async fn release_legal_hold(
+        &self,
+        vault_address: &VaultAddress,
+        reason: Option<String>,
+        ark_seed: &ArkSeed,
+    ) -> (Progress, impl Future<Output = Result<()>> + Send) {
+        let (progress, task) = Progress::new(1, "Updating Vault".to_string());
+
+        let fut = with_receipt("Release Legal Hold", async move |receipt| {
+            self.verify_ark_seed(ark_seed)?;
+            let helm_key = self.helm_key(ark_seed).await?;
+            let key: LegalHoldKey =
+                ark_seed.derive_child::<LegalHoldKeyKind>(LEGAL_HOLD_KEY_DERIVATOR.deref());
+            self._modify_vault(
+                vault_address,
+                &helm_key,
+                &ModificationRequest::builder()
+                    .legal_hold(LegalHoldChange::Release { reason, key })
+                    .build(),
+                receipt,
+                task,
+            )
+            .await
+        });
+
+        (progress, fut)
+    }
+
+    /// Removes the vault from the manifest. Data already uploaded remains on the network.
+    ///
+    /// Refused while the vault is under legal hold.
+    pub async fn remove_vault(
+        &self,
+        vault_address: &VaultAddress,
+        helm_key: &HelmKey,
+    ) -> (Progress, impl Future<Output = Result<()>> + Send) {
+        let (progress, mut task) = Progress::new(1, "Remove Vault".to_string());
+
+        let fut = with_receipt("Remove Vault", async move |receipt| {
+            let mut verify_helm = task.child(1, "Verify Helm Key".to_string());
+            let mut update_manifest = task.child(1, "Updating Manifest".to_string());
+            task.start();
+            receipt.set_vault(vault_address);
+            verify_helm.start();
+            self.verify_helm_key(helm_key).await?;
+            verify_helm.complete();
+
+            update_manifest.start();
+            self.modify_manifest(helm_key, receipt, |manifest| {
+                let vault = manifest.vault(vault_address).ok_or(Error::VaultNotFound {
+                    vault_address: vault_address.clone(),
+                })?;
+                if vault.legal_hold(&self.ark_address).is_some() {
+                    return Err(Error::VaultUnderLegalHold {
+                        vault_address: vault_address.clone(),
+                    }
+                    .into());
+                }
+                manifest.vaults.retain(|v| &v.address != vault_address);
+                manifest.last_modified = Utc::now();
+                Ok(())
+            })
+            .await?;
+            update_manifest.complete();
+            task.complete();
+            Ok(())
+        });
+
+        (progress, fut)
+    }
+
+    pub async fn rename_vault(
+        &self,
+        vault_address: &VaultAddress,
+        name: String,
+        helm_key: &HelmKey,
+    ) -> (Progress, impl Future<Output = Result<()>> + Send) {
+        let (progress, task) = Progress::new(1, "Rename Vault".to_string());
+
+        let fut = with_receipt("Rename Vault", async move |receipt| {
+            let name = name.trim().to_string();
+            if name.is_empty() {
+                bail!("vault name must not be empty");
+            }
+            self._modify_vault(
+                vault_address,
+                helm_key,
+                &ModificationRequest::builder().name(name).build(),
+                receipt,
+                task,
+            )
+            .await
+        });
+
+        (progress, fut)
+    }
+
+    /// Sets or, if `None`, removes the description of the vault.
+    pub async fn update_vault_description(
+        &self,
+        vault_address: &VaultAddress,
+        description: Option<String>,
+        helm_key: &HelmKey,
+    ) -> (Progress, impl Future<Output = Result<()>> + Send) {
+        let (progress, task) = Progress::new(1, "Updating Vault".to_string());
+
+        let fut = with_receipt("Update Vault Description", async move |receipt| {
+            self._modify_vault(
+                vault_address,
+                helm_key,
+                &ModificationRequest::builder()
+                    .description(description)
+                    .build(),
+                receipt,
+                task,
+            )
+            .await
+        });
+
+        (progress, fut)
+    }
+
+    /// Replaces the configuration of the vault's source, e.g. its root path or bucket.
+    ///
+    /// The kind of object type itself cannot be changed.
+    pub async fn update_vault_object_type(
+        &self,
+        vault_address: &VaultAddress,
+        object_type: ObjectType,
+        helm_key: &HelmKey,
+    ) -> (Progress, impl Future<Output = Result<()>> + Send) {
+        let (progress, task) = Progress::new(1, "Updating Vault".to_string());
+
+        let fut = with_receipt("Update Vault Object Type", async move |receipt| {
+            self._modify_vault(
+                vault_address,
+                helm_key,
+                &ModificationRequest::builder()
+                    .object_type(object_type)
+                    .build(),
                 receipt,
                 task,
             )
@@ -262,7 +817,7 @@ impl Core {
     ) -> (Progress, impl Future<Output = Result<()>> + Send) {
         let (progress, task) = Progress::new(1, "Updating Vault".to_string());
 
-        let fut = with_receipt(async move |receipt| {
+        let fut = with_receipt("Update Vault Bridge", async move |receipt| {
             self._modify_vault(
                 vault_address,
                 helm_key,
@@ -288,19 +843,27 @@ impl Core {
             //nothing to do
             return Ok(());
         }
-        let mut read_manifest = task.child(1, "Retrieve Current Manifest".to_string());
+        let mut verify_helm = task.child(1, "Verify Helm Key".to_string());
         let mut update_manifest = task.child(1, "Updating Manifest".to_string());
         task.start();
-        read_manifest.start();
-        let mut manifest = self.get_manifest(helm_key).await?;
-        read_manifest.complete();
-        let vault_config = manifest
-            .vault_mut(vault_address)
-            .ok_or(anyhow!("vault not found"))?;
-        vault_config.apply(modification_request);
+        receipt.set_vault(vault_address);
+        verify_helm.start();
+        self.verify_helm_key(helm_key).await?;
+        verify_helm.complete();
 
+        // concurrent changes are merged by re-applying the request to the latest manifest
         update_manifest.start();
-        self.update_manifest(&manifest, helm_key, receipt).await?;
+        self.modify_manifest(helm_key, receipt, |manifest| {
+            manifest
+                .vault_mut(vault_address)
+                .ok_or(Error::VaultNotFound {
+                    vault_address: vault_address.clone(),
+                })?
+                .apply(modification_request, &self.ark_address)?;
+            manifest.last_modified = Utc::now();
+            Ok(())
+        })
+        .await?;
         update_manifest.complete();
         task.complete();
         Ok(())
@@ -309,17 +872,284 @@ impl Core {
 
 #[derive(Builder)]
 struct ModificationRequest {
-    active: Option<bool>,
+    status: Option<VaultStatus>,
     bridge: Option<Option<BridgeAddress>>,
     name: Option<String>,
     description: Option<Option<String>>,
+    object_type: Option<ObjectType>,
+    legal_hold: Option<LegalHoldChange>,
+}
+
+enum LegalHoldChange {
+    Place(Option<String>),
+    /// Only possible with the Ark Seed, `key` signs the release
+    Release {
+        reason: Option<String>,
+        key: LegalHoldKey,
+    },
 }
 
 impl ModificationRequest {
     fn is_empty(&self) -> bool {
-        self.active.is_none()
+        self.legal_hold.is_none()
+            && self.status.is_none()
             && self.bridge.is_none()
             && self.name.is_none()
             && self.description.is_none()
+            && self.object_type.is_none()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::objects::{FileSystem, FileSystemConfig};
+    use crate::{ArkCreationSettings, BridgeKey, EvmWallet, MemoryStorage};
+    use blsttc::SecretKey;
+
+    fn posix(root: &str) -> ObjectType {
+        ObjectType::FileSystem(FileSystem::Posix(FileSystemConfig {
+            root: Some(root.to_string()),
+            ..Default::default()
+        }))
+    }
+
+    fn vault_settings() -> VaultCreationSettings {
+        VaultCreationSettings::builder()
+            .name("Test Vault")
+            .object_type(posix("/"))
+            .build()
+    }
+
+    /// Returns the value of a successful operation, panics otherwise.
+    fn settled<T>(res: Result<T>) -> T {
+        match res {
+            Ok((value, _)) => value,
+            Err((err, _)) => panic!("operation failed: {:?}", err),
+        }
+    }
+
+    /// Returns the error of a failed operation, panics otherwise.
+    fn refused<T>(res: Result<T>) -> Error {
+        match res {
+            Ok(_) => panic!("operation succeeded unexpectedly"),
+            Err((err, _)) => err,
+        }
+    }
+
+    async fn create_vault() -> (ArkSeed, HelmKey, Core, VaultAddress) {
+        let storage: Storage = MemoryStorage::new().into();
+        let wallet = EvmWallet::new_with_random_wallet(autonomi::Network::ArbitrumSepoliaTest);
+        let (ark_seed, _) = ArkSeed::random(None);
+        let settings = ArkCreationSettings::builder().name("Test Ark").build();
+        let details = settled(
+            Core::create_ark(settings, &ark_seed, &storage, &wallet, None)
+                .1
+                .await,
+        );
+        let core = Core::builder()
+            .storage(storage)
+            .wallet(wallet)
+            .ark_address(ark_seed.address().clone())
+            .build();
+        let vault = settled(
+            core.create_vault(vault_settings(), &details.helm_key)
+                .1
+                .await,
+        );
+        (ark_seed, details.helm_key.clone(), core, vault.address)
+    }
+
+    #[tokio::test]
+    async fn refuse_uploads_unless_active() {
+        let (_, helm_key, core, vault_address) = create_vault().await;
+        let accessor = ArkAccessor::from(helm_key.clone());
+        settled(core.writable_vault(&vault_address, &accessor).await);
+
+        settled(
+            core.set_vault_state(
+                &vault_address,
+                VaultState::Paused,
+                Some("maintenance".to_string()),
+                &helm_key,
+            )
+            .await
+            .1
+            .await,
+        );
+        let err = refused(core.writable_vault(&vault_address, &accessor).await);
+        assert!(
+            matches!(
+                err,
+                Error::VaultNotWritable {
+                    state: VaultState::Paused,
+                    ..
+                }
+            ),
+            "{:?}",
+            err
+        );
+        let vault = settled(core.vault_details(&vault_address, &accessor).1.await);
+        assert_eq!(vault.status.reason.as_deref(), Some("maintenance"));
+
+        settled(
+            core.set_vault_state(&vault_address, VaultState::Active, None, &helm_key)
+                .await
+                .1
+                .await,
+        );
+        settled(core.writable_vault(&vault_address, &accessor).await);
+    }
+
+    #[test]
+    fn record_reason_only_status_change() {
+        let (ark_seed, _) = ArkSeed::random(None);
+        let mut vault = VaultConfig::from(vault_settings());
+        let pause = |reason: &str| {
+            ModificationRequest::builder()
+                .status(VaultStatus::new(
+                    VaultState::Paused,
+                    Some(reason.to_string()),
+                ))
+                .build()
+        };
+
+        vault
+            .apply(&pause("maintenance"), ark_seed.address())
+            .unwrap();
+        vault
+            .apply(&pause("migration"), ark_seed.address())
+            .unwrap();
+        assert_eq!(vault.status.reason.as_deref(), Some("migration"));
+        assert_eq!(vault.status_history.len(), 2);
+        assert_eq!(
+            vault.status_history[1].reason.as_deref(),
+            Some("maintenance")
+        );
+
+        // nothing to record, nothing to pay for
+        let before = vault.clone();
+        assert!(
+            vault
+                .apply(&pause("migration"), ark_seed.address())
+                .is_err()
+        );
+        assert_eq!(vault, before);
+    }
+
+    #[tokio::test]
+    async fn refuse_changes_under_legal_hold() {
+        let (ark_seed, helm_key, core, vault_address) = create_vault().await;
+        let helm_key = &helm_key;
+
+        settled(
+            core.place_legal_hold(&vault_address, Some("litigation".to_string()), helm_key)
+                .await
+                .1
+                .await,
+        );
+        let vault = settled(
+            core.vault_details(&vault_address, &helm_key.clone().into())
+                .1
+                .await,
+        );
+        assert!(vault.legal_hold(ark_seed.address()).is_some());
+
+        let bridge = BridgeKey::new(SecretKey::random()).public_key().clone();
+        let refusals = [
+            refused(
+                core.deactivate_vault(&vault_address, helm_key)
+                    .await
+                    .1
+                    .await,
+            ),
+            refused(
+                core.set_vault_state(&vault_address, VaultState::Decommissioned, None, helm_key)
+                    .await
+                    .1
+                    .await,
+            ),
+            refused(
+                core.update_vault_bridge(&vault_address, Some(bridge), helm_key)
+                    .await
+                    .1
+                    .await,
+            ),
+            refused(
+                core.update_vault_object_type(&vault_address, posix("/elsewhere"), helm_key)
+                    .await
+                    .1
+                    .await,
+            ),
+            refused(core.remove_vault(&vault_address, helm_key).await.1.await),
+        ];
+        for err in refusals {
+            assert!(
+                matches!(err, Error::VaultUnderLegalHold { .. }),
+                "{:?}",
+                err
+            );
+        }
+
+        // changes that do not affect what the vault retains remain possible
+        settled(
+            core.rename_vault(&vault_address, "Renamed".to_string(), helm_key)
+                .await
+                .1
+                .await,
+        );
+
+        settled(
+            core.release_legal_hold(&vault_address, None, &ark_seed)
+                .await
+                .1
+                .await,
+        );
+        settled(
+            core.deactivate_vault(&vault_address, helm_key)
+                .await
+                .1
+                .await,
+        );
+        settled(core.remove_vault(&vault_address, helm_key).await.1.await);
+    }
+
+    #[test]
+    fn ignore_release_not_signed_with_the_ark_seed() {
+        let (ark_seed, _) = ArkSeed::random(None);
+        let ark_address = ark_seed.address();
+        let mut vault = VaultConfig::from(vault_settings());
+        vault
+            .apply(
+                &ModificationRequest::builder()
+                    .legal_hold(LegalHoldChange::Place(None))
+                    .build(),
+                ark_address,
+            )
+            .unwrap();
+
+        let forged = LegalHoldKey::new(SecretKey::random());
+        let err = vault
+            .apply(
+                &ModificationRequest::builder()
+                    .legal_hold(LegalHoldChange::Release {
+                        reason: None,
+                        key: forged.clone(),
+                    })
+                    .build(),
+                ark_address,
+            )
+            .unwrap_err();
+        assert!(err.to_string().contains("requires the Ark Seed"));
+
+        // a release record written directly into the manifest is not enough either
+        let at = Utc::now();
+        let message = release_message(&vault.address, &vault.legal_holds[0].placed, &at);
+        vault.legal_holds[0].released = Some(LegalHoldRelease {
+            at,
+            reason: None,
+            signature: forged.as_ref().sign(message).to_bytes().to_vec(),
+        });
+        assert!(vault.legal_hold(ark_address).is_some());
     }
 }
@@ -0,0 +1,478 @@
+use crate::crypto::ScratchpadState;
+use crate::data_key::DataRegister;
+use crate::helm_key::HelmRegister;
+use crate::manifest::Manifest;
+use crate::vault::ArkPointerAddress;
+use crate::{ArkAccessor, ArkAddress, DataKey, Progress, ReadOnlyCore, VaultAddress, with_receipt};
+use std::fmt::{Display, Formatter};
+
+/// Outcome of [`ReadOnlyCore::verify_ark`].
+#[derive(Debug, Clone)]
+pub struct VerificationReport {
+    pub ark_address: ArkAddress,
+    pub checks: Vec<CheckResult>,
+}
+
+impl VerificationReport {
+    /// `true` if no check failed. Skipped checks do not count as failures.
+    pub fn is_consistent(&self) -> bool {
+        self.failures().next().is_none()
+    }
+
+    pub fn failures(&self) -> impl Iterator<Item = &CheckResult> {
+        self.checks
+            .iter()
+            .filter(|c| matches!(c.outcome, CheckOutcome::Failed { .. }))
+    }
+
+    /// All distinct repairs suggested by failed checks, in order of appearance.
+    pub fn repairs(&self) -> Vec<&Repair> {
+        let mut repairs: Vec<&Repair> = vec![];
+        for check in self.failures() {
+            if let CheckOutcome::Failed {
+                repair: Some(repair),
+                ..
+            } = &check.outcome
+            {
+                if !repairs.contains(&repair) {
+                    repairs.push(repair);
+                }
+            }
+        }
+        repairs
+    }
+
+    fn passed(&mut self, check: impl Into<String>) {
+        self.push(check, CheckOutcome::Passed);
+    }
+
+    fn failed(
+        &mut self,
+        check: impl Into<String>,
+        problem: impl Into<String>,
+        repair: Option<Repair>,
+    ) {
+        self.push(
+            check,
+            CheckOutcome::Failed {
+                problem: problem.into(),
+                repair,
+            },
+        );
+    }
+
+    fn skipped(&mut self, check: impl Into<String>, reason: impl Into<String>) {
+        self.push(check, CheckOutcome::Skipped(reason.into()));
+    }
+
+    fn push(&mut self, check: impl Into<String>, outcome: CheckOutcome) {
+        self.checks.push(CheckResult {
+            check: check.into(),
+            outcome,
+        });
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CheckResult {
+    pub check: String,
+    pub outcome: CheckOutcome,
+}
+
+#[derive(Debug, Clone)]
+pub enum CheckOutcome {
+    Passed,
+    Failed {
+        problem: String,
+        repair: Option<Repair>,
+    },
+    /// The check could not be run, e.g. because the given key is not sufficient.
+    Skipped(String),
+}
+
+/// Suggested action to fix an inconsistency.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Repair {
+    /// Ark creation was interrupted, resume it with the Ark Seed.
+    ResumeArkCreation,
+    /// A Helm Key rotation was interrupted, rotating again with the Ark Seed rolls it forward.
+    RotateHelmKey,
+    /// Rebuild the data keyring from the Ark Seed.
+    UpdateDataKeyring,
+    /// The authorized Worker Key has been retired before, replace it.
+    RotateWorkerKey,
+    /// The vault cannot be trusted and should no longer be used.
+    DeactivateVault(VaultAddress),
+}
+
+impl Display for Repair {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ResumeArkCreation => write!(f, "resume the ark creation (requires the Ark Seed)"),
+            Self::RotateHelmKey => write!(f, "rotate the Helm Key (requires the Ark Seed)"),
+            Self::UpdateDataKeyring => {
+                write!(f, "update the data keyring (requires the Ark Seed)")
+            }
+            Self::RotateWorkerKey => write!(f, "rotate the Worker Key"),
+            Self::DeactivateVault(vault) => write!(f, "deactivate vault [{}]", vault),
+        }
+    }
+}
+
+impl ReadOnlyCore {
+    /// Checks the Ark for consistency, without modifying anything.
+    ///
+    /// Every key is able to check the helm register, manifests & vaults. Checking the
+    /// data keyring requires the Ark Seed or the Data Key, it is skipped otherwise.
+    pub fn verify_ark<'a>(
+        &'a self,
+        ark_accessor: &'a ArkAccessor,
+    ) -> (
+        Progress,
+        impl Future<Output = crate::Result<VerificationReport>> + Send + 'a,
+    ) {
+        let (progress, mut task) = Progress::new(1, "Verify Ark".to_string());
+        (
+            progress,
+            with_receipt("Verify Ark", async move |_| {
+                task.start();
+                let mut helm_task = task.child(1, "Helm Register & Manifests".to_string());
+                let mut data_task = task.child(1, "Data Keyring".to_string());
+                let mut manifest_task = task.child(1, "Manifest".to_string());
+                let mut vault_task = task.child(1, "Vault Pointers".to_string());
+
+                let mut report = VerificationReport {
+                    ark_address: self.ark_address.clone(),
+                    checks: vec![],
+                };
+
+                helm_task.start();
+                let helm_register = HelmRegister::derive_address(&self.ark_address);
+                if !self.storage.register_exists(helm_register.as_ref()).await? {
+                    report.failed(
+                        "helm register",
+                        "helm register not found, the ark does not exist or its creation was interrupted",
+                        Some(Repair::ResumeArkCreation),
+                    );
+                    task.complete();
+                    return Ok(report);
+                }
+                report.passed("helm register");
+                let manifest_live = self.verify_manifests(&mut report).await?;
+                helm_task.complete();
+
+                data_task.start();
+                let data_register = DataRegister::derive_address(&self.ark_address);
+                if !self.storage.register_exists(data_register.as_ref()).await? {
+                    report.failed(
+                        "data register",
+                        "data register not found, the ark creation was interrupted",
+                        Some(Repair::ResumeArkCreation),
+                    );
+                } else {
+                    report.passed("data register");
+                    let data_key = match ark_accessor {
+                        ArkAccessor::ArkSeed(ark_seed) => {
+                            self.verify_ark_seed(ark_seed)?;
+                            Some(self.data_key(ark_seed).await?)
+                        }
+                        ArkAccessor::DataKey(data_key) => {
+                            self.verify_data_key(data_key).await?;
+                            Some(data_key.clone())
+                        }
+                        _ => None,
+                    };
+                    match data_key {
+                        Some(data_key) => self.verify_data_keyring(&data_key, &mut report).await?,
+                        None => {
+                            report.skipped("data keyring", "requires the Ark Seed or the Data Key")
+                        }
+                    }
+                }
+                data_task.complete();
+
+                manifest_task.start();
+                let manifest = if manifest_live {
+                    Some(self.get_manifest(ark_accessor).await?)
+                } else {
+                    report.skipped("authorized worker", "manifest is not available");
+                    report.skipped("vault pointers", "manifest is not available");
+                    None
+                };
+                if let Some(manifest) = &manifest {
+                    if manifest
+                        .retired_workers
+                        .iter()
+                        .any(|retired| retired.as_ref() == &manifest.authorized_worker)
+                    {
+                        report.failed(
+                            "authorized worker",
+                            "the authorized Worker Key has already been retired",
+                            Some(Repair::RotateWorkerKey),
+                        );
+                    } else {
+                        report.passed("authorized worker");
+                    }
+                }
+                manifest_task.complete();
+
+                vault_task.start();
+                if let Some(manifest) = &manifest {
+                    self.verify_vault_pointers(manifest, &mut report).await?;
+                }
+                vault_task.complete();
+
+                task.complete();
+                Ok(report)
+            }),
+        )
+    }
+
+    /// Checks the manifests of all Helm Keys, returns whether the current one is live.
+    async fn verify_manifests(&self, report: &mut VerificationReport) -> anyhow::Result<bool> {
+        let history = self
+            .register_history(&HelmRegister::derive_address(&self.ark_address))
+            .await?;
+        let mut manifests = history
+            .iter()
+            .map(|seed| self.ark_address.helm_key(seed.as_ref()).manifest())
+            .enumerate()
+            .rev();
+
+        let manifest_live = match manifests.next() {
+            Some((_, current)) => match self.scratchpad_state(&current).await? {
+                ScratchpadState::Live => {
+                    report.passed("current manifest");
+                    true
+                }
+                ScratchpadState::Missing => {
+                    report.failed(
+                        "current manifest",
+                        "the current Helm Key has no manifest",
+                        Some(if history.len() == 1 {
+                            Repair::ResumeArkCreation
+                        } else {
+                            Repair::RotateHelmKey
+                        }),
+                    );
+                    false
+                }
+                ScratchpadState::Retired => {
+                    report.failed(
+                        "current manifest",
+                        "the manifest of the current Helm Key has been retired",
+                        Some(Repair::RotateHelmKey),
+                    );
+                    false
+                }
+            },
+            None => {
+                report.failed("current manifest", "helm register is empty", None);
+                false
+            }
+        };
+
+        for (i, previous) in manifests {
+            let check = format!("retired manifest of helm key #{}", i + 1);
+            match self.scratchpad_state(&previous).await? {
+                ScratchpadState::Retired => report.passed(check),
+                ScratchpadState::Live => report.failed(
+                    check,
+                    "manifest of a retired Helm Key is still live",
+                    Some(Repair::RotateHelmKey),
+                ),
+                ScratchpadState::Missing => {
+                    report.failed(check, "manifest of a retired Helm Key does not exist", None)
+                }
+            }
+        }
+
+        Ok(manifest_live)
+    }
+
+    async fn verify_data_keyring(
+        &self,
+        data_key: &DataKey,
+        report: &mut VerificationReport,
+    ) -> anyhow::Result<()> {
+        let keyring = match self.get_data_keyring(data_key).await {
+            Ok(keyring) => keyring,
+            Err(err) => {
+                report.failed(
+                    "data keyring",
+                    format!(
+                        "unable to read the data keyring with the current Data Key: {}",
+                        err
+                    ),
+                    Some(Repair::UpdateDataKeyring),
+                );
+                return Ok(());
+            }
+        };
+
+        let missing = self
+            .register_history(&DataRegister::derive_address(&self.ark_address))
+            .await?
+            .iter()
+            .filter(|seed| {
+                keyring
+                    .get(&self.ark_address.seal_key(seed.as_ref()))
+                    .is_none()
+            })
+            .count();
+        if missing > 0 {
+            report.failed(
+                "data keyring",
+                format!(
+                    "{} Data Key(s) of the data register are missing from the keyring",
+                    missing
+                ),
+                Some(Repair::UpdateDataKeyring),
+            );
+        } else {
+            report.passed("data keyring");
+        }
+        Ok(())
+    }
+
+    async fn verify_vault_pointers(
+        &self,
+        manifest: &Manifest,
+        report: &mut VerificationReport,
+    ) -> anyhow::Result<()> {
+        for vault in &manifest.vaults {
+            let check = format!("ark pointer of vault [{}]", vault.name);
+            let pointer = Self::read_pointer_directly(
+                &self.storage,
+                &ArkPointerAddress::from(vault.address.clone()),
+            )
+            .await?;
+            let repair = Some(Repair::DeactivateVault(vault.address.clone()));
+            match pointer {
+                None => report.failed(check, "ark pointer not found", repair),
+                Some(pointer) if pointer.is_mutable() => {
+                    report.failed(check, "ark pointer is mutable", repair)
+                }
+                Some(pointer) if pointer.target() != &self.ark_address => report.failed(
+                    check,
+                    format!(
+                        "ark pointer points to a different ark [{}]",
+                        pointer.target()
+                    ),
+                    repair,
+                ),
+                Some(_) => report.passed(check),
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helm_key::OwnedHelmRegister;
+    use crate::{
+        ArkCreationSettings, ArkSeed, Core, EvmWallet, MemoryStorage, ObjectType, Receipt, Storage,
+        VaultCreationSettings,
+    };
+    use std::str::FromStr;
+
+    fn core(storage: &Storage, ark_seed: &ArkSeed) -> Core {
+        Core::builder()
+            .storage(storage.clone())
+            .wallet(EvmWallet::new_with_random_wallet(
+                autonomi::Network::ArbitrumSepoliaTest,
+            ))
+            .ark_address(ark_seed.address().clone())
+            .build()
+    }
+
+    async fn verify(core: &ReadOnlyCore, ark_accessor: ArkAccessor) -> VerificationReport {
+        let Ok((report, _)) = core.verify_ark(&ark_accessor).1.await else {
+            panic!("verification failed to run");
+        };
+        report
+    }
+
+    fn outcome<'a>(report: &'a VerificationReport, check: &str) -> &'a CheckOutcome {
+        &report
+            .checks
+            .iter()
+            .find(|c| c.check == check)
+            .unwrap_or_else(|| panic!("check [{}] missing", check))
+            .outcome
+    }
+
+    #[tokio::test]
+    async fn verify_consistent_ark() {
+        let storage: Storage = MemoryStorage::new().into();
+        let (ark_seed, _) = ArkSeed::random(None);
+        let core = core(&storage, &ark_seed);
+        let settings = ArkCreationSettings::builder().name("Test Ark").build();
+        let wallet = EvmWallet::new_with_random_wallet(autonomi::Network::ArbitrumSepoliaTest);
+        if Core::create_ark(settings, &ark_seed, &storage, &wallet, None)
+            .1
+            .await
+            .is_err()
+        {
+            panic!("ark creation failed");
+        }
+        let Ok((helm_key, _)) = core.rotate_helm_key(&ark_seed).1.await else {
+            panic!("helm key rotation failed");
+        };
+        let settings = VaultCreationSettings::builder()
+            .name("Test Vault")
+            .object_type(ObjectType::from_str("posix").unwrap())
+            .build();
+        if core.create_vault(settings, &helm_key).1.await.is_err() {
+            panic!("vault creation failed");
+        }
+
+        let report = verify(&core, ArkAccessor::from(ark_seed.clone())).await;
+        assert!(report.is_consistent(), "{:?}", report);
+        assert!(matches!(
+            outcome(&report, "retired manifest of helm key #1"),
+            CheckOutcome::Passed
+        ));
+        assert!(matches!(
+            outcome(&report, "ark pointer of vault [Test Vault]"),
+            CheckOutcome::Passed
+        ));
+        assert!(matches!(
+            outcome(&report, "data keyring"),
+            CheckOutcome::Passed
+        ));
+
+        // the helm key is not sufficient to check the keyring
+        let report = verify(&core, ArkAccessor::from(helm_key)).await;
+        assert!(report.is_consistent(), "{:?}", report);
+        assert!(matches!(
+            outcome(&report, "data keyring"),
+            CheckOutcome::Skipped(_)
+        ));
+    }
+
+    #[tokio::test]
+    async fn suggest_resuming_interrupted_ark_creation() {
+        let storage: Storage = MemoryStorage::new().into();
+        let (ark_seed, _) = ArkSeed::random(None);
+        let core = core(&storage, &ark_seed);
+
+        // an earlier attempt only got as far as creating the helm register
+        core.create_register(
+            OwnedHelmRegister::new_derived(&ark_seed),
+            &mut Receipt::new("Interrupted"),
+        )
+        .await
+        .unwrap();
+
+        let report = verify(&core, ArkAccessor::from(ark_seed.clone())).await;
+        assert!(!report.is_consistent());
+        assert!(matches!(
+            outcome(&report, "current manifest"),
+            CheckOutcome::Failed { .. }
+        ));
+        assert_eq!(report.repairs(), vec![&Repair::ResumeArkCreation]);
+    }
+}
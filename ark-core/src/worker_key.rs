@@ -5,8 +5,9 @@ use crate::crypto::{
 };
 use crate::manifest::ManifestDecryptor;
 use crate::progress::Task;
-use crate::{ArkSeed, Core, Progress, Receipt, with_receipt};
-use anyhow::bail;
+use crate::{
+    ArkSeed, Core, Error, KeyKind, Progress, ReadOnlyCore, Receipt, with_quote, with_receipt,
+};
 use chrono::Utc;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -27,13 +28,17 @@ pub type RetiredWorkerKey = RetiredKey<WorkerKeyKind>;
 pub type EitherWorkerKey = EitherKey<WorkerKeyKind>;
 pub type PublicWorkerKey = TypedPublicKey<WorkerKeyKind>;
 
-impl Core {
+impl ReadOnlyCore {
     /// Verify the given `worker_key` against the Ark.
     /// Ensures the key is the current, active one for the Ark.
     pub(super) async fn verify_worker_key(&self, worker_key: &WorkerKey) -> anyhow::Result<()> {
         let manifest = self.get_manifest(worker_key).await?;
         if &manifest.authorized_worker != worker_key.public_key() {
-            bail!("worker_key not valid for ark [{}]", self.ark_address)
+            return Err(Error::InvalidKey {
+                key: KeyKind::WorkerKey,
+                ark_address: self.ark_address.clone(),
+            }
+            .into());
         }
         Ok(())
     }
@@ -46,6 +51,51 @@ impl Core {
         Ok(self.get_manifest(decryptor).await?.authorized_worker)
     }
 
+    /// Estimates the cost of [`Core::rotate_worker_key_with_seed`] without spending anything.
+    pub fn quote_rotate_worker_key_with_seed<'a>(
+        &'a self,
+        ark_seed: &'a ArkSeed,
+        new_worker_key: Option<PublicWorkerKey>,
+    ) -> (
+        Progress,
+        impl Future<Output = anyhow::Result<Receipt>> + Send + 'a,
+    ) {
+        let (progress, task) = Progress::new(1, "Worker Key Rotation Quote".to_string());
+        (
+            progress,
+            with_quote("Worker Key Rotation", async move |receipt| {
+                self.verify_ark_seed(ark_seed)?;
+                let helm_key = self.helm_key(ark_seed).await?;
+
+                self.quoting()
+                    ._rotate_worker_key(&helm_key, new_worker_key, receipt, task)
+                    .await
+            }),
+        )
+    }
+
+    /// Estimates the cost of [`Core::rotate_worker_key`] without spending anything.
+    pub fn quote_rotate_worker_key<'a>(
+        &'a self,
+        helm_key: &'a HelmKey,
+        new_worker_key: Option<PublicWorkerKey>,
+    ) -> (
+        Progress,
+        impl Future<Output = anyhow::Result<Receipt>> + Send + 'a,
+    ) {
+        let (progress, task) = Progress::new(1, "Worker Key Rotation Quote".to_string());
+        (
+            progress,
+            with_quote("Worker Key Rotation", async move |receipt| {
+                self.quoting()
+                    ._rotate_worker_key(helm_key, new_worker_key, receipt, task)
+                    .await
+            }),
+        )
+    }
+}
+
+impl Core {
     pub fn rotate_worker_key_with_seed(
         &self,
         ark_seed: &ArkSeed,
@@ -54,13 +104,18 @@ impl Core {
         Progress,
         impl Future<Output = crate::Result<EitherWorkerKey>> + Send,
     ) {
-        let (progress, task) = Progress::new(1, "Worker Key Rotation".to_string());
+        let (progress, mut task) = Progress::new(1, "Worker Key Rotation".to_string());
         (
             progress,
-            with_receipt(async move |receipt| {
+            with_receipt("Worker Key Rotation", async move |receipt| {
                 self.verify_ark_seed(ark_seed)?;
                 let helm_key = self.helm_key(ark_seed).await?;
 
+                self.check_budget(receipt, &mut task, async |core, receipt, task| {
+                    core._rotate_worker_key(&helm_key, new_worker_key.clone(), receipt, task)
+                        .await
+                })
+                .await?;
                 self._rotate_worker_key(&helm_key, new_worker_key, receipt, task)
                     .await
             }),
@@ -75,10 +130,15 @@ impl Core {
         Progress,
         impl Future<Output = crate::Result<EitherWorkerKey>> + Send + 'a,
     ) {
-        let (progress, task) = Progress::new(1, "Worker Key Rotation".to_string());
+        let (progress, mut task) = Progress::new(1, "Worker Key Rotation".to_string());
         (
             progress,
-            with_receipt(async move |receipt| {
+            with_receipt("Worker Key Rotation", async move |receipt| {
+                self.check_budget(receipt, &mut task, async |core, receipt, task| {
+                    core._rotate_worker_key(helm_key, new_worker_key.clone(), receipt, task)
+                        .await
+                })
+                .await?;
                 self._rotate_worker_key(helm_key, new_worker_key, receipt, task)
                     .await
             }),
@@ -101,7 +161,7 @@ impl Core {
         let mut read_manifest = task.child(2, "Read Manifest".to_string());
         let mut update_manifest = task.child(1, "Update Manifest".to_string());
         read_manifest.start();
-        let mut manifest = self.get_manifest(helm_key).await?;
+        let (mut manifest, counter) = self.get_manifest_for_update(helm_key).await?;
         read_manifest += 1;
         let mut manifest_encryptor = self.manifest_encryptor(helm_key).await?;
         manifest_encryptor.public_worker_key = new_worker_key.public_key().clone();
@@ -111,7 +171,8 @@ impl Core {
         manifest.last_modified = Utc::now();
 
         update_manifest.start();
-        self.update_manifest(&manifest, &helm_key, receipt).await?;
+        self.update_manifest(&manifest, counter, &helm_key, receipt)
+            .await?;
         update_manifest.complete();
 
         task.complete();
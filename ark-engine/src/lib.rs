@@ -2,7 +2,10 @@ mod util;
 
 use crate::util::{Comparison, diff_maps};
 use chrono::{DateTime, Utc};
-use core::{ArkAddress, AutonomiClient, AutonomiWallet, Manifest, VaultConfig, VaultId};
+use core::{
+    ArkAccessor, ArkAddress, AutonomiClient, AutonomiWallet, Error, Manifest, ReadOnlyCore,
+    VaultAddress, VaultConfig, VaultId, VaultState,
+};
 use std::collections::HashMap;
 
 pub struct Engine {
@@ -10,6 +13,25 @@ pub struct Engine {
     wallet: AutonomiWallet,
 }
 
+impl Engine {
+    /// Every upload to a vault has to start here.
+    ///
+    /// Refuses vaults that are not `Active` with [`Error::VaultNotWritable`]. The state is
+    /// read from the latest manifest, so vaults paused, archived or decommissioned
+    /// elsewhere stop receiving uploads.
+    pub async fn begin_upload(
+        &self,
+        core: &ReadOnlyCore,
+        vault_address: &VaultAddress,
+        ark_accessor: &ArkAccessor,
+    ) -> Result<VaultConfig, Error> {
+        core.writable_vault(vault_address, ark_accessor)
+            .await
+            .map(|(vault, _)| vault)
+            .map_err(|(err, _)| err)
+    }
+}
+
 pub struct Ark {
     address: ArkAddress,
     created: DateTime<Utc>,
@@ -90,7 +112,7 @@ pub struct Vault {
     description: Option<String>,
     created: DateTime<Utc>,
     last_modified: DateTime<Utc>,
-    active: bool,
+    state: VaultState,
 }
 
 impl Vault {
@@ -117,11 +139,15 @@ impl Vault {
             change_counter += 1;
         }
 
-        if self.active != config.active {
-            self.active = config.active;
+        if self.state != config.status.state {
+            self.state = config.status.state;
             change_counter += 1;
         }
 
         change_counter
     }
+
+    pub fn accepts_uploads(&self) -> bool {
+        self.state.accepts_uploads()
+    }
 }